@@ -0,0 +1,148 @@
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::time::Duration;
+use crate::agent::{AgentCommand, AgentKey, AgentRequest, AgentResponse, PROTOCOL_VERSION, SOCKET_FILE, TOKEN_FILE};
+use crate::error::AppError;
+
+// 确认提示最长等待 60 秒，读超时留出余量
+const READ_TIMEOUT: Duration = Duration::from_secs(90);
+
+// 向桌面端代理发送一个请求；代理未运行（无令牌或无法连接）时返回 None
+pub fn request(app_dir: &Path, client: &str, command: AgentCommand) -> Result<Option<Value>, AppError> {
+    let Ok(token) = std::fs::read_to_string(app_dir.join(TOKEN_FILE)) else {
+        return Ok(None);
+    };
+    let Ok(mut stream) = UnixStream::connect(app_dir.join(SOCKET_FILE)) else {
+        return Ok(None);
+    };
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+
+    let request = AgentRequest {
+        version: PROTOCOL_VERSION,
+        id: None,
+        token: token.trim().to_string(),
+        client: Some(client.to_string()),
+        command,
+    };
+    let mut body = serde_json::to_string(&request)?;
+    body.push('\n');
+    stream.write_all(body.as_bytes())?;
+
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    let response: AgentResponse = serde_json::from_str(&line)?;
+    if response.ok {
+        return Ok(Some(response.result.unwrap_or(Value::Null)));
+    }
+    let error = response.error.ok_or_else(|| AppError::Io("Malformed agent response".to_string()))?;
+    Err(into_app_error(&error.code, &error.message))
+}
+
+// 通过代理获取Key（含 keyValue）
+pub fn get_key(app_dir: &Path, client: &str, key: &str) -> Result<Option<AgentKey>, AppError> {
+    let result = request(app_dir, client, AgentCommand::Get { key: key.to_string() })?;
+    match result {
+        Some(value) => Ok(Some(serde_json::from_value(value)?)),
+        None => Ok(None),
+    }
+}
+
+// 按错误码还原 AppError；message 为 Display 结果，去掉其中的类别前缀
+fn into_app_error(code: &str, message: &str) -> AppError {
+    let detail = message.split_once(": ").map(|(_, rest)| rest).unwrap_or(message).to_string();
+    match code {
+        "DATABASE_NOT_READY" => AppError::DatabaseNotReady,
+        "VAULT_LOCKED" => AppError::VaultLocked,
+        "UNAUTHORIZED" => AppError::Unauthorized(detail),
+        "FORBIDDEN" => AppError::Forbidden(detail),
+        "NOT_FOUND" => AppError::NotFound(detail),
+        "VALIDATION_FAILED" => AppError::Validation(detail),
+        "CONFLICT" => AppError::Conflict(detail),
+        "DATABASE_ERROR" => AppError::Database(detail),
+        _ => AppError::Io(message.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixListener;
+
+    fn temp_dir() -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("akm-agent-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // 在临时目录中启动只处理一个请求的代理，返回收到的请求
+    fn serve_once(dir: &Path, response: AgentResponse) -> std::thread::JoinHandle<AgentRequest> {
+        std::fs::write(dir.join(TOKEN_FILE), "secret-token\n").unwrap();
+        let listener = UnixListener::bind(dir.join(SOCKET_FILE)).unwrap();
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut line = String::new();
+            BufReader::new(&stream).read_line(&mut line).unwrap();
+            let mut body = serde_json::to_string(&response).unwrap();
+            body.push('\n');
+            (&stream).write_all(body.as_bytes()).unwrap();
+            serde_json::from_str(&line).unwrap()
+        })
+    }
+
+    // 错误码与消息可还原为相同的 AppError
+    #[test]
+    fn test_into_app_error() {
+        for error in [
+            AppError::VaultLocked,
+            AppError::DatabaseNotReady,
+            AppError::Unauthorized("Invalid agent token".into()),
+            AppError::NotFound("API key main".into()),
+            AppError::Conflict("Multiple API keys are named main".into()),
+        ] {
+            let restored = into_app_error(error.code(), &error.to_string());
+            assert_eq!(restored.code(), error.code());
+            assert_eq!(restored.to_string(), error.to_string());
+        }
+        assert!(matches!(into_app_error("SOMETHING_ELSE", "boom"), AppError::Io(_)));
+    }
+
+    // 没有令牌文件或无法连接时视为代理未运行
+    #[test]
+    fn test_request_without_agent() {
+        let dir = temp_dir();
+        assert!(get_key(&dir, "akm", "main").unwrap().is_none());
+        std::fs::write(dir.join(TOKEN_FILE), "token").unwrap();
+        assert!(get_key(&dir, "akm", "main").unwrap().is_none());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // 请求携带令牌与客户端名称，成功时返回Key
+    #[test]
+    fn test_get_key() {
+        let dir = temp_dir();
+        let key = serde_json::json!({"id": "k1", "name": "main", "platform": null, "tags": null, "keyValue": "sk-value"});
+        let server = serve_once(&dir, AgentResponse::success(None, key));
+
+        let fetched = get_key(&dir, "akm", "main").unwrap().unwrap();
+        assert_eq!(fetched.key_value.as_deref(), Some("sk-value"));
+
+        let request = server.join().unwrap();
+        assert_eq!(request.token, "secret-token");
+        assert_eq!(request.client.as_deref(), Some("akm"));
+        assert!(matches!(request.command, AgentCommand::Get { ref key } if key == "main"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // 代理返回的错误还原为对应的 AppError
+    #[test]
+    fn test_get_key_error() {
+        let dir = temp_dir();
+        let server = serve_once(&dir, AgentResponse::failure(None, &AppError::Forbidden("Request denied".into())));
+
+        assert!(matches!(get_key(&dir, "akm", "main"), Err(AppError::Forbidden(ref m)) if m == "Request denied"));
+        server.join().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,224 @@
+#[cfg(unix)]
+pub mod client;
+#[cfg(unix)]
+mod server;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use crate::database::api_key::ApiKey;
+use crate::database::error::DatabaseError;
+use crate::database::settings::{get_setting, set_setting};
+use crate::error::AppError;
+
+// 协议版本，请求中的 version 不一致时拒绝处理
+pub const PROTOCOL_VERSION: u32 = 1;
+// 套接字与令牌文件，位于应用数据目录
+pub const SOCKET_FILE: &str = "agent.sock";
+pub const TOKEN_FILE: &str = "agent.token";
+// settings 表中控制是否需要在桌面端确认取Key请求的键
+pub const CONFIRM_SETTING: &str = "agent_confirm_requests";
+
+// 代理启动成功后记录套接字路径
+static SOCKET_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+// 代理支持的请求，按 command 字段区分
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "command", rename_all = "camelCase")]
+pub enum AgentCommand {
+    Ping,
+    // 保险库是否就绪、是否已锁定
+    Status,
+    // 列出Key（不含 key_value）
+    List,
+    // 按ID或名称获取Key
+    Get { key: String },
+}
+
+// 一行一个 JSON 请求，如 {"version":1,"token":"...","command":"get","key":"openai-prod"}
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentRequest {
+    pub version: u32,
+    // 客户端自定义的请求ID，原样返回
+    #[serde(default)]
+    pub id: Option<String>,
+    pub token: String,
+    // 客户端自报的名称，显示在确认提示与审计日志中
+    #[serde(default)]
+    pub client: Option<String>,
+    #[serde(flatten)]
+    pub command: AgentCommand,
+}
+
+// 错误码与 AppError::code 一致
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AgentError {
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AgentResponse {
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<AgentError>,
+}
+
+impl AgentResponse {
+    pub fn success(id: Option<String>, result: Value) -> Self {
+        Self {
+            version: PROTOCOL_VERSION,
+            id,
+            ok: true,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    pub fn failure(id: Option<String>, error: &AppError) -> Self {
+        Self {
+            version: PROTOCOL_VERSION,
+            id,
+            ok: false,
+            result: None,
+            error: Some(AgentError {
+                code: error.code().to_string(),
+                message: error.to_string(),
+            }),
+        }
+    }
+}
+
+// 返回给客户端的Key，仅 get 请求包含 keyValue
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentKey {
+    pub id: String,
+    pub name: String,
+    pub platform: Option<String>,
+    pub tags: Option<String>,
+    #[serde(rename = "keyValue", default, skip_serializing_if = "Option::is_none")]
+    pub key_value: Option<String>,
+}
+
+impl AgentKey {
+    pub fn new(key: &ApiKey, include_value: bool) -> Self {
+        Self {
+            id: key.id.clone(),
+            name: key.name.clone(),
+            platform: key.platform.clone(),
+            tags: key.tags.clone(),
+            key_value: include_value.then(|| key.key_value.clone()),
+        }
+    }
+}
+
+// 返回给前端的代理状态
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentStatus {
+    pub running: bool,
+    #[serde(rename = "socketPath")]
+    pub socket_path: Option<String>,
+    #[serde(rename = "confirmRequests")]
+    pub confirm_requests: bool,
+}
+
+// 代理正在监听的套接字路径
+pub fn socket_path() -> Option<&'static PathBuf> {
+    SOCKET_PATH.get()
+}
+
+// 是否需要在桌面端确认取Key请求，默认需要
+pub async fn get_confirm_requests(pool: &SqlitePool) -> Result<bool, DatabaseError> {
+    Ok(get_setting(pool, CONFIRM_SETTING)
+        .await?
+        .map(|v| v != "false")
+        .unwrap_or(true))
+}
+
+pub async fn set_confirm_requests(pool: &SqlitePool, enabled: bool) -> Result<(), DatabaseError> {
+    set_setting(pool, CONFIRM_SETTING, if enabled { "true" } else { "false" }).await
+}
+
+// 启动本地代理（类似 ssh-agent），仅支持 Unix 平台
+#[cfg(unix)]
+pub fn spawn_agent(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = server::serve(app_handle).await {
+            eprintln!("Key agent stopped: {}", e);
+        }
+    });
+}
+
+#[cfg(not(unix))]
+pub fn spawn_agent(_app_handle: tauri::AppHandle) {
+    println!("Key agent is only available on Unix platforms");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::TestDatabase;
+
+    // 请求按 command 字段区分，缺省字段取默认值
+    #[test]
+    fn test_parse_request() {
+        let request: AgentRequest =
+            serde_json::from_str(r#"{"version":1,"token":"t","command":"get","key":"openai-prod"}"#).unwrap();
+        assert_eq!(request.token, "t");
+        assert!(request.id.is_none() && request.client.is_none());
+        assert!(matches!(request.command, AgentCommand::Get { ref key } if key == "openai-prod"));
+
+        let request: AgentRequest =
+            serde_json::from_str(r#"{"version":1,"id":"42","token":"t","client":"akm","command":"list"}"#).unwrap();
+        assert_eq!(request.id.as_deref(), Some("42"));
+        assert!(matches!(request.command, AgentCommand::List));
+
+        assert!(serde_json::from_str::<AgentRequest>(r#"{"version":1,"token":"t","command":"delete"}"#).is_err());
+    }
+
+    // 失败响应携带错误码，成功响应不输出 error 字段
+    #[test]
+    fn test_responses() {
+        let failure = AgentResponse::failure(Some("1".into()), &AppError::VaultLocked);
+        let json = serde_json::to_value(&failure).unwrap();
+        assert_eq!(json["ok"], false);
+        assert_eq!(json["id"], "1");
+        assert_eq!(json["error"]["code"], "VAULT_LOCKED");
+        assert!(json.get("result").is_none());
+
+        let json = serde_json::to_value(AgentResponse::success(None, Value::Bool(true))).unwrap();
+        assert_eq!(json["ok"], true);
+        assert!(json.get("error").is_none() && json.get("id").is_none());
+    }
+
+    // 只有 get 请求返回 keyValue
+    #[test]
+    fn test_agent_key() {
+        let key = ApiKey::new("main".into(), "secret".into(), Some("openai".into()), None, None, None);
+        let listed = serde_json::to_value(AgentKey::new(&key, false)).unwrap();
+        assert!(listed.get("keyValue").is_none());
+        let fetched = serde_json::to_value(AgentKey::new(&key, true)).unwrap();
+        assert_eq!(fetched["keyValue"], "secret");
+    }
+
+    // 默认需要在桌面端确认取Key请求
+    #[tokio::test]
+    async fn test_confirm_requests_setting() {
+        let db = TestDatabase::open().await;
+        assert!(get_confirm_requests(&db.pool).await.unwrap());
+        set_confirm_requests(&db.pool, false).await.unwrap();
+        assert!(!get_confirm_requests(&db.pool).await.unwrap());
+        set_confirm_requests(&db.pool, true).await.unwrap();
+        assert!(get_confirm_requests(&db.pool).await.unwrap());
+    }
+}
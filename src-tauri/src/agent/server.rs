@@ -0,0 +1,239 @@
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::Manager;
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use crate::agent::{
+    get_confirm_requests, AgentCommand, AgentKey, AgentRequest, AgentResponse, PROTOCOL_VERSION,
+    SOCKET_FILE, SOCKET_PATH, TOKEN_FILE,
+};
+use crate::database::api_key::{find_api_key, get_all_api_keys};
+use crate::database::app_data_dir;
+use crate::database::audit::{record_audit, AuditEntry};
+use crate::database::error::DatabaseError;
+use crate::database::usage_history::record_usage;
+use crate::error::AppError;
+use crate::security::{constant_time_eq, generate_token, write_private_file};
+use crate::{vaults, AppState};
+
+// 审计日志中的请求来源
+const AUDIT_SOURCE: &str = "agent";
+// 单个请求的最大长度
+const MAX_REQUEST_BYTES: u64 = 64 * 1024;
+// 等待用户确认的时长，超时视为拒绝
+const APPROVAL_TIMEOUT: Duration = Duration::from_secs(60);
+
+// 监听套接字并处理连接，直到出错退出
+pub(super) async fn serve(app: tauri::AppHandle) -> Result<(), AppError> {
+    let dir = app_data_dir(&app)?;
+    let socket_path = dir.join(SOCKET_FILE);
+    if socket_path.exists() {
+        // 另一个实例仍在监听时不抢占；否则是上次异常退出留下的文件
+        if std::os::unix::net::UnixStream::connect(&socket_path).is_ok() {
+            return Err(AppError::Conflict(format!("Another agent is listening on {:?}", socket_path)));
+        }
+        std::fs::remove_file(&socket_path)?;
+    }
+
+    // 每次启动生成新的令牌，客户端从仅当前用户可读的令牌文件中读取
+    let token = Arc::new(generate_token());
+    write_private_file(&dir.join(TOKEN_FILE), &token)?;
+
+    let listener = UnixListener::bind(&socket_path)?;
+    std::fs::set_permissions(&socket_path, std::fs::Permissions::from_mode(0o600))?;
+    let owner = std::fs::metadata(&socket_path)?.uid();
+    let _ = SOCKET_PATH.set(socket_path.clone());
+    println!("Key agent listening on {:?}", socket_path);
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let app = app.clone();
+        let token = token.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = handle_connection(&app, stream, &token, owner).await {
+                eprintln!("Agent connection failed: {}", e);
+            }
+        });
+    }
+}
+
+// 逐行读取请求并返回响应；只接受与代理同一用户的进程
+async fn handle_connection(
+    app: &tauri::AppHandle,
+    stream: UnixStream,
+    token: &str,
+    owner: u32,
+) -> Result<(), AppError> {
+    let peer = stream.peer_cred()?;
+    if peer.uid() != owner {
+        eprintln!("Rejected agent connection from uid {}", peer.uid());
+        return Ok(());
+    }
+    let client_pid = peer.pid();
+
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+    loop {
+        let mut line = String::new();
+        let read = (&mut reader).take(MAX_REQUEST_BYTES).read_line(&mut line).await?;
+        if read == 0 {
+            return Ok(());
+        }
+        let oversized = !line.ends_with('\n') && read as u64 >= MAX_REQUEST_BYTES;
+        let response = if oversized {
+            AgentResponse::failure(None, &AppError::Validation("Request is too large".to_string()))
+        } else if line.trim().is_empty() {
+            continue;
+        } else {
+            handle_request(app, &line, token, client_pid).await
+        };
+
+        let mut body = serde_json::to_string(&response)?;
+        body.push('\n');
+        writer.write_all(body.as_bytes()).await?;
+        if oversized {
+            return Ok(());
+        }
+    }
+}
+
+async fn handle_request(app: &tauri::AppHandle, line: &str, token: &str, client_pid: Option<i32>) -> AgentResponse {
+    let request: AgentRequest = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(e) => return AgentResponse::failure(None, &AppError::Validation(format!("Invalid request: {}", e))),
+    };
+    if request.version != PROTOCOL_VERSION {
+        let error = AppError::Validation(format!(
+            "Unsupported protocol version {}, expected {}",
+            request.version, PROTOCOL_VERSION
+        ));
+        return AgentResponse::failure(request.id, &error);
+    }
+
+    let client = match client_pid {
+        Some(pid) => format!("{} (pid {})", request.client.as_deref().unwrap_or("unknown client"), pid),
+        None => request.client.clone().unwrap_or_else(|| "unknown client".to_string()),
+    };
+    let result = if constant_time_eq(&request.token, token) {
+        dispatch(app, &request.command, &client).await
+    } else {
+        let error = AppError::Unauthorized("Invalid agent token".to_string());
+        if let Ok(pool) = app.state::<AppState>().pool() {
+            audit(&pool, &request.command, &client, None, Err(&error)).await;
+        }
+        Err(error)
+    };
+
+    match result {
+        Ok(value) => AgentResponse::success(request.id, value),
+        Err(e) => AgentResponse::failure(request.id, &e),
+    }
+}
+
+async fn dispatch(app: &tauri::AppHandle, command: &AgentCommand, client: &str) -> Result<Value, AppError> {
+    let state = app.state::<AppState>();
+    match command {
+        AgentCommand::Ping => Ok(json!({ "pong": true, "version": PROTOCOL_VERSION })),
+        AgentCommand::Status => {
+            let pool = state.pool().ok();
+            let locked = match &pool {
+                Some(pool) => state.session.ensure_unlocked(pool).await.is_err(),
+                None => true,
+            };
+            let vault = app_data_dir(app)
+                .and_then(|dir| vaults::load_registry(&dir))
+                .ok()
+                .map(|registry| registry.active_vault());
+            Ok(json!({
+                "ready": pool.is_some(),
+                "locked": locked,
+                "vault": vault.map(|v| json!({ "id": v.id, "name": v.name })),
+            }))
+        }
+        AgentCommand::List => {
+            let pool = state.pool()?;
+            let result = async {
+                state.session.ensure_unlocked(&pool).await?;
+                let keys = get_all_api_keys(&pool).await?;
+                let keys: Vec<AgentKey> = keys.iter().map(|k| AgentKey::new(k, false)).collect();
+                Ok(serde_json::to_value(keys)?)
+            }
+            .await;
+            audit(&pool, command, client, None, result.as_ref()).await;
+            result
+        }
+        AgentCommand::Get { key } => {
+            let pool = state.pool()?;
+            let mut key_id = None;
+            let result = async {
+                state.session.ensure_unlocked(&pool).await?;
+                let api_key = find_api_key(&pool, key).await.map_err(|e| match e {
+                    DatabaseError::KeyNotFound => AppError::NotFound(format!("API key {}", key)),
+                    e => e.into(),
+                })?;
+                key_id = Some(api_key.id.clone());
+
+                if get_confirm_requests(&pool).await? && !confirm(app, client, &api_key.name).await {
+                    return Err(AppError::Forbidden("Request was denied".to_string()));
+                }
+                if let Err(e) = record_usage(&pool, &api_key.id, "agent").await {
+                    eprintln!("Failed to record usage for {}: {}", api_key.id, e);
+                }
+                Ok(serde_json::to_value(AgentKey::new(&api_key, true))?)
+            }
+            .await;
+            audit(&pool, command, client, key_id, result.as_ref()).await;
+            result
+        }
+    }
+}
+
+// 在桌面端弹出确认框，超时或关闭视为拒绝
+async fn confirm(app: &tauri::AppHandle, client: &str, key_name: &str) -> bool {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .message(format!("{} is requesting the API key \"{}\".", client, key_name))
+        .title("API Key Request")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom("Allow".to_string(), "Deny".to_string()))
+        .show(move |allowed| {
+            let _ = sender.send(allowed);
+        });
+    matches!(tokio::time::timeout(APPROVAL_TIMEOUT, receiver).await, Ok(Ok(true)))
+}
+
+// 记录审计日志：成功为 allowed，被拒绝为 denied，其余错误为 failed
+async fn audit(
+    pool: &SqlitePool,
+    command: &AgentCommand,
+    client: &str,
+    key_id: Option<String>,
+    result: Result<&Value, &AppError>,
+) {
+    let (action, target) = match command {
+        AgentCommand::Ping => ("ping", None),
+        AgentCommand::Status => ("status", None),
+        AgentCommand::List => ("list", None),
+        AgentCommand::Get { key } => ("get", Some(key.clone())),
+    };
+    let outcome = match result {
+        Ok(_) => "allowed",
+        Err(AppError::Forbidden(_) | AppError::Unauthorized(_)) => "denied",
+        Err(_) => "failed",
+    };
+
+    let mut entry = AuditEntry::new(AUDIT_SOURCE, action, outcome);
+    entry.target = target;
+    entry.key_id = key_id;
+    entry.detail = Some(match result {
+        Ok(_) => client.to_string(),
+        Err(e) => format!("{}: {}", client, e),
+    });
+    if let Err(e) = record_audit(pool, &entry).await {
+        eprintln!("Failed to write audit log: {}", e);
+    }
+}
@@ -0,0 +1,415 @@
+use chrono::{Datelike, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Connection, SqlitePool};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::Manager;
+use crate::database::active_vault_dir;
+use crate::database::error::DatabaseError;
+use crate::database::health::classify_error;
+use crate::database::settings::{get_setting, is_local_only_setting, set_setting};
+use crate::AppState;
+
+// 备份文件名格式：api_keys-20240101-120000.db
+const BACKUP_PREFIX: &str = "api_keys-";
+const BACKUP_SUFFIX: &str = ".db";
+const BACKUP_TIME_FORMAT: &str = "%Y%m%d-%H%M%S";
+// 调度器检查是否需要备份的间隔
+const SCHEDULER_TICK: Duration = Duration::from_secs(15 * 60);
+// 恢复时需要复制的表，顺序满足外键依赖。
+// api_tokens 保持当前状态：恢复旧备份不能让已吊销的访问令牌重新生效
+const RESTORE_TABLES: &[&str] = &[
+    "groups",
+    "api_keys",
+    "api_key_versions",
+    "usage_history",
+    "settings",
+    "batch_imports",
+    "project_bindings",
+];
+// 合法的备份文件必须包含的表
+const REQUIRED_TABLES: &[&str] = &["api_keys", "groups", "settings"];
+
+// 备份相关配置，保存在 settings 表中
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupConfig {
+    pub enabled: bool,
+    pub interval_hours: u32,
+    // 保留最近 N 份
+    pub keep_last: u32,
+    // 额外保留最近 N 天中每天最新的一份
+    pub keep_daily: u32,
+    // 额外保留最近 N 周中每周最新的一份
+    pub keep_weekly: u32,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_hours: 24,
+            keep_last: 5,
+            keep_daily: 7,
+            keep_weekly: 4,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupInfo {
+    pub file_name: String,
+    pub path: String,
+    pub created_at: i64,
+    pub size: u64,
+}
+
+// 读取备份配置，缺失的项使用默认值
+pub async fn load_config(pool: &SqlitePool) -> Result<BackupConfig, DatabaseError> {
+    let defaults = BackupConfig::default();
+    let read = |key: &'static str| get_setting(pool, key);
+
+    Ok(BackupConfig {
+        enabled: read("backup_enabled")
+            .await?
+            .map(|v| v == "true")
+            .unwrap_or(defaults.enabled),
+        interval_hours: read("backup_interval_hours")
+            .await?
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(defaults.interval_hours),
+        keep_last: read("backup_keep_last")
+            .await?
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(defaults.keep_last),
+        keep_daily: read("backup_keep_daily")
+            .await?
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(defaults.keep_daily),
+        keep_weekly: read("backup_keep_weekly")
+            .await?
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(defaults.keep_weekly),
+    })
+}
+
+// 保存备份配置
+pub async fn save_config(pool: &SqlitePool, config: &BackupConfig) -> Result<(), DatabaseError> {
+    let values = [
+        ("backup_enabled", config.enabled.to_string()),
+        ("backup_interval_hours", config.interval_hours.max(1).to_string()),
+        ("backup_keep_last", config.keep_last.max(1).to_string()),
+        ("backup_keep_daily", config.keep_daily.to_string()),
+        ("backup_keep_weekly", config.keep_weekly.to_string()),
+    ];
+    for (key, value) in values {
+        set_setting(pool, key, &value).await?;
+    }
+    Ok(())
+}
+
+// 备份目录：当前保险库目录下的 backups
+pub fn backups_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, DatabaseError> {
+    let dir = active_vault_dir(app_handle)?.join("backups");
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| DatabaseError::Io(format!("Failed to create backups directory: {}", e)))?;
+    Ok(dir)
+}
+
+fn parse_backup_time(file_name: &str) -> Option<i64> {
+    let stamp = file_name
+        .strip_prefix(BACKUP_PREFIX)?
+        .strip_suffix(BACKUP_SUFFIX)?;
+    // 同一秒内的多份备份会带有 -2、-3 等后缀
+    let stamp = stamp.get(..15)?;
+    NaiveDateTime::parse_from_str(stamp, BACKUP_TIME_FORMAT)
+        .ok()
+        .map(|t| Utc.from_utc_datetime(&t).timestamp())
+}
+
+// 同一秒内第几份备份（无后缀为第1份）
+fn backup_sequence(file_name: &str) -> u32 {
+    file_name
+        .strip_suffix(BACKUP_SUFFIX)
+        .and_then(|name| name.get(BACKUP_PREFIX.len() + 16..))
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(1)
+}
+
+// 列出备份目录中的所有备份，按时间从新到旧排序
+pub fn list_backups(dir: &Path) -> Result<Vec<BackupInfo>, DatabaseError> {
+    let mut backups = Vec::new();
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| DatabaseError::Io(format!("Failed to read backups directory: {}", e)))?;
+
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        let Some(created_at) = parse_backup_time(&file_name) else {
+            continue;
+        };
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        backups.push(BackupInfo {
+            path: entry.path().to_string_lossy().into_owned(),
+            file_name,
+            created_at,
+            size,
+        });
+    }
+
+    backups.sort_by(|a, b| {
+        b.created_at
+            .cmp(&a.created_at)
+            .then(backup_sequence(&b.file_name).cmp(&backup_sequence(&a.file_name)))
+    });
+    Ok(backups)
+}
+
+// 使用 VACUUM INTO 生成一致的数据库快照，连接池保持可用
+pub async fn create_backup(pool: &SqlitePool, dir: &Path) -> Result<BackupInfo, DatabaseError> {
+    let stamp = Utc::now().format(BACKUP_TIME_FORMAT).to_string();
+    let mut path = dir.join(format!("{}{}{}", BACKUP_PREFIX, stamp, BACKUP_SUFFIX));
+    let mut n = 2;
+    while path.exists() {
+        path = dir.join(format!("{}{}-{}{}", BACKUP_PREFIX, stamp, n, BACKUP_SUFFIX));
+        n += 1;
+    }
+
+    sqlx::query("VACUUM INTO ?1")
+        .bind(path.to_string_lossy().into_owned())
+        .execute(pool)
+        .await
+        .map_err(|e| classify_error("Failed to create backup", e))?;
+
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    println!("Database backup created at: {:?}", path);
+
+    Ok(BackupInfo {
+        created_at: parse_backup_time(&file_name).unwrap_or_else(|| Utc::now().timestamp()),
+        path: path.to_string_lossy().into_owned(),
+        file_name,
+        size,
+    })
+}
+
+// 按保留策略删除多余的备份，返回删除的数量
+pub fn apply_retention(dir: &Path, config: &BackupConfig) -> Result<usize, DatabaseError> {
+    let backups = list_backups(dir)?;
+    let mut keep: HashSet<&str> = HashSet::new();
+
+    // 最近 N 份
+    for backup in backups.iter().take(config.keep_last.max(1) as usize) {
+        keep.insert(&backup.file_name);
+    }
+
+    // 每天、每周最新的一份（backups 已按从新到旧排序）
+    let mut days = Vec::new();
+    let mut weeks = Vec::new();
+    for backup in &backups {
+        let Some(time) = Utc.timestamp_opt(backup.created_at, 0).single() else {
+            continue;
+        };
+        let day = time.date_naive();
+        if !days.contains(&day) && days.len() < config.keep_daily as usize {
+            days.push(day);
+            keep.insert(&backup.file_name);
+        }
+        let week = (time.iso_week().year(), time.iso_week().week());
+        if !weeks.contains(&week) && weeks.len() < config.keep_weekly as usize {
+            weeks.push(week);
+            keep.insert(&backup.file_name);
+        }
+    }
+
+    let mut removed = 0;
+    for backup in backups.iter().filter(|b| !keep.contains(b.file_name.as_str())) {
+        match std::fs::remove_file(&backup.path) {
+            Ok(_) => removed += 1,
+            Err(e) => eprintln!("Failed to remove old backup {}: {}", backup.file_name, e),
+        }
+    }
+    Ok(removed)
+}
+
+// 校验备份文件：完整性检查通过且包含必要的表
+pub async fn validate_backup(path: &Path) -> Result<(), DatabaseError> {
+    if !path.is_file() {
+        return Err(DatabaseError::InvalidInput(format!("Backup file not found: {:?}", path)));
+    }
+
+    let mut conn = SqliteConnectOptions::new()
+        .filename(path)
+        .read_only(true)
+        .connect()
+        .await
+        .map_err(|e| classify_error("Failed to open backup", e))?;
+
+    let check: String = sqlx::query_scalar("PRAGMA integrity_check")
+        .fetch_one(&mut conn)
+        .await
+        .map_err(|e| classify_error("Backup integrity check failed", e))?;
+    if check != "ok" {
+        return Err(DatabaseError::Corrupted(format!("Backup integrity check failed: {}", check)));
+    }
+
+    let tables: Vec<String> = sqlx::query_scalar("SELECT name FROM sqlite_master WHERE type = 'table'")
+        .fetch_all(&mut conn)
+        .await?;
+    for required in REQUIRED_TABLES {
+        if !tables.iter().any(|t| t == required) {
+            return Err(DatabaseError::InvalidInput(format!("Backup is missing table '{}'", required)));
+        }
+    }
+
+    let _ = conn.close().await;
+    Ok(())
+}
+
+// 将备份内容恢复到当前数据库：先校验，再在单个事务中替换所有表的数据
+pub async fn restore_backup(pool: &SqlitePool, backup_path: &Path) -> Result<(), DatabaseError> {
+    validate_backup(backup_path).await?;
+
+    let mut conn = pool.acquire().await?;
+    sqlx::query("ATTACH DATABASE ?1 AS restore_source")
+        .bind(backup_path.to_string_lossy().into_owned())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| classify_error("Failed to attach backup", e))?;
+
+    let result = copy_tables_from_attached(&mut conn).await;
+
+    let _ = sqlx::query("DETACH DATABASE restore_source")
+        .execute(&mut *conn)
+        .await;
+    result
+}
+
+async fn copy_tables_from_attached(conn: &mut sqlx::SqliteConnection) -> Result<(), DatabaseError> {
+    let mut tx = conn.begin().await?;
+
+    // 本机的安全配置（主密码、解锁计数等）不随备份回退，恢复后写回当前的值
+    let local_settings: Vec<(String, String)> = sqlx::query_as::<_, (String, String)>("SELECT key, value FROM main.settings")
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .filter(|(key, _)| is_local_only_setting(key))
+        .collect();
+
+    // 先按依赖的逆序清空，再按依赖顺序写入
+    for table in RESTORE_TABLES.iter().rev() {
+        sqlx::query(&format!("DELETE FROM main.{}", table))
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| classify_error(&format!("Failed to clear {}", table), e))?;
+    }
+
+    for table in RESTORE_TABLES {
+        let target: Vec<String> = sqlx::query_scalar(&format!("SELECT name FROM pragma_table_info('{}', 'main')", table))
+            .fetch_all(&mut *tx)
+            .await?;
+        let source: Vec<String> = sqlx::query_scalar(&format!("SELECT name FROM pragma_table_info('{}', 'restore_source')", table))
+            .fetch_all(&mut *tx)
+            .await?;
+
+        // 旧版本备份可能缺少新增列，只复制两边都有的列
+        let columns: Vec<&String> = target.iter().filter(|c| source.contains(c)).collect();
+        if columns.is_empty() {
+            continue;
+        }
+        let column_list = columns
+            .iter()
+            .map(|c| c.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        // 旧备份中可能存在孤立的使用记录，跳过以满足外键约束
+        let filter = if matches!(*table, "usage_history" | "api_key_versions") {
+            " WHERE key_id IN (SELECT id FROM main.api_keys)"
+        } else {
+            ""
+        };
+        sqlx::query(&format!(
+            "INSERT INTO main.{table} ({cols}) SELECT {cols} FROM restore_source.{table}{filter}",
+            table = table,
+            cols = column_list,
+            filter = filter
+        ))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| classify_error(&format!("Failed to restore {}", table), e))?;
+    }
+
+    let restored: Vec<String> = sqlx::query_scalar("SELECT key FROM main.settings")
+        .fetch_all(&mut *tx)
+        .await?;
+    for key in restored.iter().filter(|key| is_local_only_setting(key)) {
+        sqlx::query("DELETE FROM main.settings WHERE key = ?1")
+            .bind(key)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| classify_error("Failed to restore settings", e))?;
+    }
+    for (key, value) in &local_settings {
+        sqlx::query("INSERT INTO main.settings (key, value) VALUES (?1, ?2)")
+            .bind(key)
+            .bind(value)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| classify_error("Failed to restore settings", e))?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+// 如果距离上次备份已超过设定间隔，则执行一次备份并清理旧备份
+pub async fn run_scheduled_backup(pool: &SqlitePool, dir: &Path) -> Result<Option<BackupInfo>, DatabaseError> {
+    let config = load_config(pool).await?;
+    if !config.enabled {
+        return Ok(None);
+    }
+
+    let now = Utc::now().timestamp();
+    let interval = i64::from(config.interval_hours.max(1)) * 3600;
+    let last = list_backups(dir)?.first().map(|b| b.created_at);
+    if matches!(last, Some(last) if now - last < interval) {
+        return Ok(None);
+    }
+
+    let backup = create_backup(pool, dir).await?;
+    let removed = apply_retention(dir, &config)?;
+    if removed > 0 {
+        println!("Removed {} old backups", removed);
+    }
+    Ok(Some(backup))
+}
+
+// 启动后台备份调度器
+pub fn spawn_scheduler(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+        state.wait_for_status().await;
+        loop {
+            // 数据库未就绪（如等待修复）时跳过本轮
+            let Ok(pool) = state.pool() else {
+                tokio::time::sleep(SCHEDULER_TICK).await;
+                continue;
+            };
+            match backups_dir(&app_handle) {
+                Ok(dir) => {
+                    if let Err(e) = run_scheduled_backup(&pool, &dir).await {
+                        eprintln!("Scheduled backup failed: {}", e);
+                    }
+                }
+                Err(e) => eprintln!("Scheduled backup failed: {}", e),
+            }
+            tokio::time::sleep(SCHEDULER_TICK).await;
+        }
+    });
+}
@@ -0,0 +1,4 @@
+// 命令行入口：akm list / get / add / rm / search / export / import / unlock / run
+fn main() -> std::process::ExitCode {
+    api_key_manager_lib::cli::run()
+}
@@ -0,0 +1,185 @@
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use std::process::ExitCode;
+use crate::database::api_key::ApiKey;
+use crate::database::audit::{audit, AuditEntry, CLI_SOURCE};
+use crate::database::usage_history::record_usage;
+use crate::error::AppError;
+use crate::export::formats::assign_env_var_names;
+use super::{find_key, unlock};
+
+// 环境变量名：字母或下划线开头，仅包含字母、数字与下划线
+fn is_valid_env_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// 解析 --key 参数：NAME 或 VAR=NAME
+pub(super) fn parse_spec(spec: &str) -> Result<(Option<&str>, &str), AppError> {
+    match spec.split_once('=') {
+        Some((var, key)) => {
+            let var = var.trim();
+            if !is_valid_env_name(var) {
+                return Err(AppError::Validation(format!("Invalid environment variable name: {}", var)));
+            }
+            Ok((Some(var), key.trim()))
+        }
+        None => Ok((None, spec.trim())),
+    }
+}
+
+// 确定每个Key注入的变量名：显式指定的优先，其余按平台注册表的约定命名
+async fn resolve_env(pool: &SqlitePool, specs: &[String]) -> Result<Vec<(String, ApiKey)>, AppError> {
+    let mut explicit = Vec::new();
+    let mut implicit = Vec::new();
+    for spec in specs {
+        let (var, name) = parse_spec(spec)?;
+        let key = find_key(pool, name).await?;
+        match var {
+            Some(var) => explicit.push((var.to_string(), key)),
+            None => implicit.push(key),
+        }
+    }
+
+    let mut used: HashSet<String> = HashSet::new();
+    for (var, _) in &explicit {
+        if !used.insert(var.clone()) {
+            return Err(AppError::Conflict(format!("Environment variable {} is assigned twice", var)));
+        }
+    }
+    let names = assign_env_var_names(&implicit);
+    for (var, key) in names.iter().zip(&implicit) {
+        if used.contains(var) {
+            return Err(AppError::Conflict(format!(
+                "Environment variable {} for {} is already assigned, use VAR={} to rename it",
+                var, key.name, key.name
+            )));
+        }
+    }
+
+    explicit.extend(names.into_iter().zip(implicit));
+    Ok(explicit)
+}
+
+// 以注入了Key的环境变量运行子进程，返回子进程的退出码。
+// Key只通过环境变量传递给子进程，不会写入文件或输出到终端
+pub(super) async fn run_with_keys(
+    pool: &SqlitePool,
+    specs: &[String],
+    command: &[String],
+) -> Result<ExitCode, AppError> {
+    let Some((program, args)) = command.split_first() else {
+        return Err(AppError::Validation("No command given".to_string()));
+    };
+    unlock(pool).await?;
+    let env = resolve_env(pool, specs).await?;
+
+    let mut child = tokio::process::Command::new(program)
+        .args(args)
+        .envs(env.iter().map(|(var, key)| (var.as_str(), key.key_value.as_str())))
+        .spawn()
+        .map_err(|e| AppError::Io(format!("Failed to run {}: {}", program, e)))?;
+
+    for (var, key) in &env {
+        if let Err(e) = record_usage(pool, &key.id, "inject").await {
+            eprintln!("Failed to record usage for {} ({}): {}", key.id, var, e);
+        }
+        audit(
+            pool,
+            AuditEntry::new(CLI_SOURCE, "inject", "allowed")
+                .with_key(&key.id)
+                .with_detail(format!("{} for {}", var, program)),
+        )
+        .await;
+    }
+    // 子进程可能长时间运行，先释放数据库连接
+    pool.close().await;
+
+    // Ctrl+C 会同时发送给子进程，这里继续等待子进程退出以传递其退出码
+    let status = loop {
+        tokio::select! {
+            status = child.wait() => break status?,
+            _ = tokio::signal::ctrl_c() => continue,
+        }
+    };
+
+    Ok(exit_code(status))
+}
+
+// 子进程被信号终止时按 shell 惯例返回 128 + 信号值
+fn exit_code(status: std::process::ExitStatus) -> ExitCode {
+    if let Some(code) = status.code() {
+        return ExitCode::from(code.clamp(0, 255) as u8);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return ExitCode::from((128 + signal).clamp(0, 255) as u8);
+        }
+    }
+    ExitCode::FAILURE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::api_key::insert_api_key;
+    use crate::database::TestDatabase;
+
+    async fn insert_key(pool: &SqlitePool, name: &str, value: &str, platform: Option<&str>) -> ApiKey {
+        let key = ApiKey::new(name.into(), value.into(), platform.map(str::to_string), None, None, None);
+        insert_api_key(pool, &key).await.unwrap();
+        key
+    }
+
+    // 解析 NAME 与 VAR=NAME 两种形式，变量名不合法时报错
+    #[test]
+    fn test_parse_spec() {
+        assert_eq!(parse_spec(" main ").unwrap(), (None, "main"));
+        assert_eq!(parse_spec("MY_KEY = main").unwrap(), (Some("MY_KEY"), "main"));
+        assert!(matches!(parse_spec("1KEY=main"), Err(AppError::Validation(_))));
+        assert!(matches!(parse_spec("MY-KEY=main"), Err(AppError::Validation(_))));
+    }
+
+    // 显式变量名优先，其余按平台约定命名；变量名重复时报冲突
+    #[tokio::test]
+    async fn test_resolve_env() {
+        let db = TestDatabase::open().await;
+        insert_key(&db.pool, "main", "v1", Some("openai")).await;
+        insert_key(&db.pool, "backup", "v2", Some("openai")).await;
+
+        let env = resolve_env(&db.pool, &["CUSTOM=backup".to_string(), "main".to_string()]).await.unwrap();
+        let pairs: Vec<(&str, &str)> = env.iter().map(|(var, key)| (var.as_str(), key.key_value.as_str())).collect();
+        assert_eq!(pairs, vec![("CUSTOM", "v2"), ("OPENAI_API_KEY", "v1")]);
+
+        let twice = resolve_env(&db.pool, &["A=main".to_string(), "A=backup".to_string()]).await;
+        assert!(matches!(twice, Err(AppError::Conflict(_))));
+        let taken = resolve_env(&db.pool, &["OPENAI_API_KEY=backup".to_string(), "main".to_string()]).await;
+        assert!(matches!(taken, Err(AppError::Conflict(_))));
+        let missing = resolve_env(&db.pool, &["missing".to_string()]).await;
+        assert!(matches!(missing, Err(AppError::NotFound(_))));
+    }
+
+    // 子进程能读到注入的变量，并返回子进程的退出码
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_with_keys() {
+        let db = TestDatabase::open().await;
+        insert_key(&db.pool, "main", "sk-injected", Some("openai")).await;
+
+        let command = ["sh", "-c", r#"test "$OPENAI_API_KEY" = sk-injected && exit 7"#].map(str::to_string);
+        let code = run_with_keys(&db.pool, &["main".to_string()], &command).await.unwrap();
+        assert_eq!(code, ExitCode::from(7));
+
+        assert!(matches!(run_with_keys(&db.pool, &[], &[]).await, Err(AppError::Validation(_))));
+    }
+
+    // 被信号终止的子进程按 128 + 信号值返回
+    #[cfg(unix)]
+    #[test]
+    fn test_exit_code_for_signal() {
+        let status = std::process::Command::new("sh").args(["-c", "kill -TERM $$"]).status().unwrap();
+        assert_eq!(exit_code(status), ExitCode::from(143));
+    }
+}
@@ -0,0 +1,680 @@
+mod inject;
+
+use clap::{Parser, Subcommand};
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use std::io::{IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+use crate::clipboard::hold_clipboard_content;
+use crate::database::api_key::{
+    delete_api_key, find_api_key, get_all_api_keys, get_existing_key_values, insert_api_key,
+    search_api_keys, ApiKey, ApiKeySummary,
+};
+use crate::database::audit::{audit, AuditEntry, CLI_SOURCE};
+use crate::database::error::DatabaseError;
+use crate::database::project_binding::upsert_binding;
+use crate::database::usage_history::record_usage;
+use crate::database::{open_database, DATABASE_FILE};
+use crate::error::AppError;
+use crate::export::formats::{render, ExportFilter, ExportFormat};
+use crate::importers::{self, ImportFormat};
+use crate::projects;
+use crate::security::lockout::verify_unlock_attempt;
+use crate::security::password::has_master_password;
+use crate::security::write_private_file;
+use crate::templates;
+use crate::validation::normalize_api_key;
+use crate::vaults::{self, VaultEntry};
+
+// 与 tauri.conf.json 中的 identifier 一致，桌面应用的数据目录以此命名
+pub const APP_IDENTIFIER: &str = "com.hongyu-li.api-key-manager";
+// 非交互环境（脚本、CI）中提供主密码的环境变量
+pub const PASSWORD_ENV: &str = "AKM_MASTER_PASSWORD";
+// get --copy 在 Linux 上保持剪贴板内容的时长
+const CLIPBOARD_TIMEOUT: Duration = Duration::from_secs(45);
+
+#[derive(Debug, Parser)]
+#[command(name = "akm", version, about = "Manage API keys from the terminal using the desktop app's database")]
+pub struct Cli {
+    /// Vault id or name (defaults to the vault last opened in the desktop app)
+    #[arg(long, global = true, env = "AKM_VAULT")]
+    vault: Option<String>,
+    /// Application data directory (defaults to the desktop app's data directory)
+    #[arg(long, global = true, env = "AKM_DATA_DIR")]
+    data_dir: Option<PathBuf>,
+    /// Read the database directly instead of asking the running desktop app's agent
+    #[arg(long, global = true)]
+    no_agent: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// List API keys without their values
+    List {
+        /// Only show keys of this platform
+        #[arg(long)]
+        platform: Option<String>,
+        /// Print JSON instead of tab-separated lines
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the value of an API key, looked up by id or name
+    Get {
+        name: String,
+        /// Copy the value to the clipboard instead of printing it
+        #[arg(long)]
+        copy: bool,
+    },
+    /// Add an API key; the value is read from stdin when --value is omitted
+    Add {
+        name: String,
+        #[arg(long)]
+        value: Option<String>,
+        #[arg(long)]
+        platform: Option<String>,
+        #[arg(long)]
+        description: Option<String>,
+        /// Comma-separated tags
+        #[arg(long)]
+        tags: Option<String>,
+    },
+    /// Move an API key to the trash
+    Rm { name: String },
+    /// Search API keys by name, platform or description
+    Search {
+        keyword: String,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Export API key values as plain text
+    Export {
+        /// env, shell, json, yaml or csv
+        #[arg(long, default_value = "env", value_parser = parse_export_format)]
+        format: ExportFormat,
+        #[arg(long)]
+        platform: Option<String>,
+        #[arg(long)]
+        keyword: Option<String>,
+        /// Write to a file instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Import API keys from a password manager export or a .env file
+    Import {
+        file: PathBuf,
+        /// bitwarden, onepassword, keepass, lastpass or dotenv (guessed from the file name by default)
+        #[arg(long, value_parser = parse_import_format)]
+        format: Option<ImportFormat>,
+        /// Only report what would be imported
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Check the master password of the vault
+    Unlock,
+    /// Run a command with API keys injected as environment variables
+    Run {
+        /// Key to inject, by id or name; use VAR=KEY to choose the variable name
+        #[arg(long = "key", short = 'k', required = true)]
+        keys: Vec<String>,
+        /// Command to run, given after --
+        #[arg(last = true, required = true)]
+        command: Vec<String>,
+    },
+    /// Fill a template such as .env.example with stored keys
+    Render {
+        template: PathBuf,
+        /// Output file (defaults to the template name without .example/.template)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// Use KEY for variable VAR, given as VAR=KEY
+        #[arg(long = "map", short = 'm')]
+        mappings: Vec<String>,
+        /// Write the file even if some placeholders cannot be resolved
+        #[arg(long)]
+        allow_unresolved: bool,
+    },
+    /// Write or refresh the .env of the bound project containing the current directory
+    Env {
+        /// Project directory (defaults to the current directory)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+        /// Bind variable VAR to KEY in this directory first, given as VAR=KEY
+        #[arg(long = "bind", short = 'b')]
+        bindings: Vec<String>,
+        /// Only report whether the .env is stale, exiting with 1 if it is
+        #[arg(long)]
+        check: bool,
+    },
+}
+
+// 格式名称与前端使用的 serde 名称保持一致
+fn parse_export_format(value: &str) -> Result<ExportFormat, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_lowercase()))
+        .map_err(|_| format!("unsupported export format: {}", value))
+}
+
+fn parse_import_format(value: &str) -> Result<ImportFormat, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_lowercase()))
+        .map_err(|_| format!("unsupported import format: {}", value))
+}
+
+// 命令行入口，由 src/bin/akm.rs 调用
+pub fn run() -> ExitCode {
+    let cli = Cli::parse();
+    let runtime = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match runtime.block_on(execute(cli)) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            if matches!(e, AppError::VaultLocked) {
+                eprintln!("hint: run in a terminal or set {} to provide the master password", PASSWORD_ENV);
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn execute(cli: Cli) -> Result<ExitCode, AppError> {
+    let app_dir = resolve_data_dir(cli.data_dir)?;
+    // 桌面端运行时优先通过代理取Key，由桌面端负责解锁状态、确认提示与审计
+    #[cfg(unix)]
+    if let Command::Get { name, copy } = &cli.command {
+        if !cli.no_agent && cli.vault.is_none() && get_via_agent(&app_dir, name, *copy)? {
+            return Ok(ExitCode::SUCCESS);
+        }
+    }
+    let (vault, pool) = open_vault(&app_dir, cli.vault.as_deref()).await?;
+    let result = match cli.command {
+        Command::List { platform, json } => list(&pool, platform, json).await,
+        Command::Get { name, copy } => get(&pool, &name, copy).await,
+        Command::Add { name, value, platform, description, tags } => {
+            add(&pool, name, value, platform, description, tags).await
+        }
+        Command::Rm { name } => remove(&pool, &name).await,
+        Command::Search { keyword, json } => search(&pool, &keyword, json).await,
+        Command::Export { format, platform, keyword, output } => {
+            export(&pool, format, platform, keyword, output).await
+        }
+        Command::Import { file, format, dry_run } => import(&pool, &file, format, dry_run).await,
+        Command::Unlock => unlock_command(&pool, &vault).await,
+        Command::Render { template, output, mappings, allow_unresolved } => {
+            render_template(&pool, &template, output.as_deref(), &mappings, allow_unresolved).await
+        }
+        Command::Env { dir, bindings, check } => {
+            let result = project_env(&pool, dir, &bindings, check).await;
+            pool.close().await;
+            return result;
+        }
+        // run 的退出码来自子进程
+        Command::Run { keys, command } => {
+            let result = inject::run_with_keys(&pool, &keys, &command).await;
+            pool.close().await;
+            return result;
+        }
+    };
+    pool.close().await;
+    result.map(|_| ExitCode::SUCCESS)
+}
+
+// 桌面应用的数据目录：dirs::data_dir()/<identifier>，与 Tauri 的 app_data_dir 相同
+fn resolve_data_dir(data_dir: Option<PathBuf>) -> Result<PathBuf, AppError> {
+    let dir = match data_dir {
+        Some(dir) => dir,
+        None => dirs::data_dir()
+            .ok_or_else(|| AppError::Io("Cannot determine the application data directory, use --data-dir".to_string()))?
+            .join(APP_IDENTIFIER),
+    };
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+// 打开指定保险库（按 id 或名称），未指定时使用桌面应用当前的保险库
+async fn open_vault(app_dir: &Path, vault: Option<&str>) -> Result<(VaultEntry, SqlitePool), AppError> {
+    let registry = vaults::load_registry(app_dir)?;
+    let entry = match vault {
+        Some(vault) => registry
+            .vaults
+            .iter()
+            .find(|v| v.id == vault || v.name.eq_ignore_ascii_case(vault))
+            .cloned()
+            .ok_or_else(|| AppError::NotFound(format!("Vault {}", vault)))?,
+        None => registry.active_vault(),
+    };
+    let dir = vaults::vault_dir(app_dir, &entry.id)?;
+    let pool = open_database(&dir.join(DATABASE_FILE)).await?;
+    Ok((entry, pool))
+}
+
+// 已设置主密码时要求验证：优先读取环境变量，其次在终端中提示输入
+async fn unlock(pool: &SqlitePool) -> Result<(), AppError> {
+    if !has_master_password(pool).await? {
+        return Ok(());
+    }
+    let password = match std::env::var(PASSWORD_ENV) {
+        Ok(password) => password,
+        // 没有可用终端（如在脚本中运行）时视为未解锁
+        Err(_) => rpassword::prompt_password("Master password: ").map_err(|_| AppError::VaultLocked)?,
+    };
+    if verify_unlock_attempt(pool, &password, CLI_SOURCE).await? {
+        Ok(())
+    } else {
+        Err(AppError::Unauthorized("Incorrect master password".to_string()))
+    }
+}
+
+// 读取Key的值：终端中不回显输入，管道输入时读取全部内容
+fn read_secret(prompt: &str) -> Result<String, AppError> {
+    if std::io::stdin().is_terminal() {
+        return Ok(rpassword::prompt_password(prompt)?);
+    }
+    let mut value = String::new();
+    std::io::stdin().read_to_string(&mut value)?;
+    Ok(value.trim_end_matches(['\r', '\n']).to_string())
+}
+
+// 按 id 或名称（忽略大小写）查找Key，名称重复时要求使用 id
+async fn find_key(pool: &SqlitePool, name: &str) -> Result<ApiKey, AppError> {
+    find_api_key(pool, name).await.map_err(|e| match e {
+        DatabaseError::KeyNotFound => AppError::NotFound(format!("API key {}", name)),
+        e => e.into(),
+    })
+}
+
+fn print_keys(keys: &[ApiKey], json: bool) -> Result<(), AppError> {
+    if json {
+        // 与前端使用相同的摘要结构，不包含 key_value
+        let summaries: Vec<ApiKeySummary> = keys.iter().cloned().map(ApiKeySummary::from).collect();
+        println!("{}", serde_json::to_string_pretty(&summaries)?);
+        return Ok(());
+    }
+    for key in keys {
+        println!(
+            "{}\t{}\t{}\t{}",
+            key.id,
+            key.name,
+            key.platform.as_deref().unwrap_or("-"),
+            key.tags.as_deref().unwrap_or("-")
+        );
+    }
+    Ok(())
+}
+
+async fn list(pool: &SqlitePool, platform: Option<String>, json: bool) -> Result<(), AppError> {
+    let filter = ExportFilter {
+        platform,
+        ..Default::default()
+    };
+    let keys: Vec<ApiKey> = get_all_api_keys(pool)
+        .await?
+        .into_iter()
+        .filter(|k| filter.matches(k))
+        .collect();
+    print_keys(&keys, json)
+}
+
+async fn get(pool: &SqlitePool, name: &str, copy: bool) -> Result<(), AppError> {
+    unlock(pool).await?;
+    let key = find_key(pool, name).await?;
+    if let Err(e) = record_usage(pool, &key.id, "cli").await {
+        eprintln!("Failed to record usage for {}: {}", key.id, e);
+    }
+    let action = if copy { "copy" } else { "reveal" };
+    audit(pool, AuditEntry::new(CLI_SOURCE, action, "allowed").with_key(&key.id)).await;
+    output_key(&key.name, &key.key_value, copy)
+}
+
+// 通过桌面端代理获取Key；代理未运行、桌面端已锁定或数据库未就绪时返回 false，改为直接读取数据库
+#[cfg(unix)]
+fn get_via_agent(app_dir: &Path, name: &str, copy: bool) -> Result<bool, AppError> {
+    match crate::agent::client::get_key(app_dir, "akm", name) {
+        Ok(Some(key)) => {
+            let value = key.key_value.unwrap_or_default();
+            output_key(&key.name, &value, copy)?;
+            Ok(true)
+        }
+        Ok(None) | Err(AppError::VaultLocked | AppError::DatabaseNotReady) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+// 打印Key的值，或复制到剪贴板
+fn output_key(name: &str, value: &str, copy: bool) -> Result<(), AppError> {
+    if !copy {
+        println!("{}", value);
+        return Ok(());
+    }
+    if cfg!(target_os = "linux") {
+        eprintln!(
+            "Copied {} to the clipboard, keeping it for {} seconds",
+            name,
+            CLIPBOARD_TIMEOUT.as_secs()
+        );
+    } else {
+        eprintln!("Copied {} to the clipboard", name);
+    }
+    hold_clipboard_content(value, CLIPBOARD_TIMEOUT).map_err(|e| AppError::Clipboard(e.to_string()))
+}
+
+async fn add(
+    pool: &SqlitePool,
+    name: String,
+    value: Option<String>,
+    platform: Option<String>,
+    description: Option<String>,
+    tags: Option<String>,
+) -> Result<(), AppError> {
+    let value = match value {
+        Some(value) => value,
+        None => read_secret("Key value: ")?,
+    };
+    let mut api_key = ApiKey::new(name, value, platform, description, None, tags);
+    normalize_api_key(&mut api_key).map_err(AppError::InvalidFields)?;
+    insert_api_key(pool, &api_key).await?;
+    println!("{}", api_key.id);
+    Ok(())
+}
+
+async fn remove(pool: &SqlitePool, name: &str) -> Result<(), AppError> {
+    let key = find_key(pool, name).await?;
+    delete_api_key(pool, &key.id).await?;
+    audit(pool, AuditEntry::new(CLI_SOURCE, "delete", "allowed").with_key(&key.id)).await;
+    println!("Moved {} to the trash", key.name);
+    Ok(())
+}
+
+async fn search(pool: &SqlitePool, keyword: &str, json: bool) -> Result<(), AppError> {
+    let keys = search_api_keys(pool, keyword).await?;
+    print_keys(&keys, json)
+}
+
+async fn export(
+    pool: &SqlitePool,
+    format: ExportFormat,
+    platform: Option<String>,
+    keyword: Option<String>,
+    output: Option<PathBuf>,
+) -> Result<(), AppError> {
+    unlock(pool).await?;
+    let filter = ExportFilter {
+        keyword,
+        platform,
+        ..Default::default()
+    };
+    let keys: Vec<ApiKey> = get_all_api_keys(pool)
+        .await?
+        .into_iter()
+        .filter(|k| filter.matches(k))
+        .collect();
+    let content = render(&keys, format)?;
+
+    for key in &keys {
+        if let Err(e) = record_usage(pool, &key.id, "export").await {
+            eprintln!("Failed to record usage for {}: {}", key.id, e);
+        }
+    }
+    let mut entry =
+        AuditEntry::new(CLI_SOURCE, "export", "allowed").with_detail(format!("{:?}, {} keys", format, keys.len()));
+    if let Some(path) = &output {
+        entry = entry.with_target(&path.to_string_lossy());
+    }
+    audit(pool, entry).await;
+
+    match output {
+        Some(path) => {
+            // 导出文件包含明文Key
+            write_private_file(&path, &content)?;
+            eprintln!("Exported {} API keys to {}", keys.len(), path.display());
+        }
+        None => {
+            let mut stdout = std::io::stdout();
+            stdout.write_all(content.as_bytes())?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+async fn import(
+    pool: &SqlitePool,
+    file: &Path,
+    format: Option<ImportFormat>,
+    dry_run: bool,
+) -> Result<(), AppError> {
+    let parsed = importers::parse_file(file, format)?;
+    let values: Vec<String> = parsed.iter().map(|k| k.key_value.clone()).collect();
+    let mut seen: HashSet<String> = get_existing_key_values(pool, &values).await?.into_iter().collect();
+
+    let (mut imported, mut skipped, mut failed) = (0, 0, 0);
+    for key in parsed {
+        if !seen.insert(key.key_value.clone()) {
+            skipped += 1;
+            continue;
+        }
+        let mut api_key = ApiKey::new(key.name, key.key_value, key.platform, key.description, None, key.tags);
+        if let Err(field_errors) = normalize_api_key(&mut api_key) {
+            failed += 1;
+            eprintln!("Skipped {}: {}", api_key.name, AppError::InvalidFields(field_errors));
+            continue;
+        }
+        if dry_run {
+            println!("Would import {}", api_key.name);
+        } else if let Err(e) = insert_api_key(pool, &api_key).await {
+            failed += 1;
+            eprintln!("Failed to import {}: {}", api_key.name, e);
+            continue;
+        }
+        imported += 1;
+    }
+
+    if !dry_run {
+        audit(
+            pool,
+            AuditEntry::new(CLI_SOURCE, "import", "allowed")
+                .with_target(&file.to_string_lossy())
+                .with_detail(format!("{} keys imported, {} failed", imported, failed)),
+        )
+        .await;
+    }
+    let verb = if dry_run { "Would import" } else { "Imported" };
+    println!("{} {} API keys, skipped {} duplicates, {} failed", verb, imported, skipped, failed);
+    Ok(())
+}
+
+async fn unlock_command(pool: &SqlitePool, vault: &VaultEntry) -> Result<(), AppError> {
+    if !has_master_password(pool).await? {
+        println!("Vault {} has no master password", vault.name);
+        return Ok(());
+    }
+    unlock(pool).await?;
+    println!("Master password for vault {} is correct", vault.name);
+    Ok(())
+}
+
+async fn render_template(
+    pool: &SqlitePool,
+    template: &Path,
+    output: Option<&Path>,
+    mappings: &[String],
+    allow_unresolved: bool,
+) -> Result<(), AppError> {
+    let mut map = HashMap::new();
+    for mapping in mappings {
+        match inject::parse_spec(mapping)? {
+            (Some(var), key) => map.insert(var.to_string(), key.to_string()),
+            (None, _) => return Err(AppError::Validation(format!("Mapping must be VAR=KEY: {}", mapping))),
+        };
+    }
+    unlock(pool).await?;
+
+    let report = templates::render_file(pool, CLI_SOURCE, template, output, &map, allow_unresolved).await?;
+    for entry in &report.resolved {
+        eprintln!("line {}: {} <- {}", entry.line, entry.placeholder, entry.key_name);
+    }
+    for entry in &report.unresolved {
+        eprintln!("line {}: {} unresolved: {}", entry.line, entry.placeholder, entry.reason);
+    }
+    if !report.written {
+        return Err(AppError::Validation(format!(
+            "{} placeholders could not be resolved, nothing was written (use --allow-unresolved to write anyway)",
+            report.unresolved.len()
+        )));
+    }
+    eprintln!("Wrote {}", report.output_path);
+    Ok(())
+}
+
+// 同步当前目录所属项目的 .env；--bind 时先在该目录建立绑定
+async fn project_env(
+    pool: &SqlitePool,
+    dir: Option<PathBuf>,
+    bindings: &[String],
+    check: bool,
+) -> Result<ExitCode, AppError> {
+    let dir = match dir {
+        Some(dir) => dir,
+        None => std::env::current_dir()?,
+    };
+    let project = if bindings.is_empty() {
+        projects::find_project_for(pool, &dir).await?.ok_or_else(|| {
+            AppError::NotFound(format!("Project bound to {} (bind keys with --bind VAR=KEY)", dir.display()))
+        })?
+    } else {
+        let project = projects::normalize_project_path(&dir)?;
+        for spec in bindings {
+            let (Some(var), name) = inject::parse_spec(spec)? else {
+                return Err(AppError::Validation(format!("Binding must be VAR=KEY: {}", spec)));
+            };
+            let key = find_key(pool, name).await?;
+            upsert_binding(pool, &project, var, &key.id).await?;
+            eprintln!("Bound {} to {}", var, key.name);
+        }
+        project
+    };
+
+    if check {
+        let info = projects::get_project(pool, &project).await?;
+        for status in &info.bindings {
+            let state = if status.missing {
+                "missing"
+            } else if status.stale {
+                "stale"
+            } else {
+                "ok"
+            };
+            eprintln!(
+                "{}\t{}\t{}",
+                status.binding.env_var,
+                status.key_name.as_deref().unwrap_or("-"),
+                state
+            );
+        }
+        return Ok(if info.stale { ExitCode::FAILURE } else { ExitCode::SUCCESS });
+    }
+
+    unlock(pool).await?;
+    let report = projects::sync_project(pool, CLI_SOURCE, &project).await?;
+    for var in &report.missing {
+        eprintln!("{} skipped: its API key was deleted", var);
+    }
+    eprintln!("Wrote {} variables to {}", report.written.len(), report.env_file);
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+    use crate::database::TestDatabase;
+    use crate::security::password::store_master_password;
+
+    // 命令行定义合法，各子命令的参数可以正确解析
+    #[test]
+    fn test_parse_args() {
+        Cli::command().debug_assert();
+
+        let cli = Cli::try_parse_from(["akm", "--vault", "work", "list", "--platform", "openai", "--json"]).unwrap();
+        assert_eq!(cli.vault.as_deref(), Some("work"));
+        assert!(matches!(cli.command, Command::List { platform: Some(ref p), json: true } if p == "openai"));
+
+        let cli = Cli::try_parse_from(["akm", "export", "--format", "YAML"]).unwrap();
+        assert!(matches!(cli.command, Command::Export { format: ExportFormat::Yaml, .. }));
+        assert!(Cli::try_parse_from(["akm", "export", "--format", "xml"]).is_err());
+
+        let cli = Cli::try_parse_from(["akm", "import", "keys.csv", "--format", "lastpass", "--dry-run"]).unwrap();
+        assert!(matches!(cli.command, Command::Import { format: Some(ImportFormat::Lastpass), dry_run: true, .. }));
+
+        let cli = Cli::try_parse_from(["akm", "run", "-k", "OPENAI_API_KEY=main", "--", "env", "-i"]).unwrap();
+        match cli.command {
+            Command::Run { keys, command } => {
+                assert_eq!(keys, vec!["OPENAI_API_KEY=main"]);
+                assert_eq!(command, vec!["env", "-i"]);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    // 未指定保险库时打开默认保险库，找不到指定的保险库时返回 NotFound
+    #[tokio::test]
+    async fn test_open_vault() {
+        let dir = std::env::temp_dir().join(format!("akm-cli-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let (entry, pool) = open_vault(&dir, None).await.unwrap();
+        assert_eq!(entry.id, vaults::DEFAULT_VAULT_ID);
+        assert!(dir.join(DATABASE_FILE).exists());
+        pool.close().await;
+        assert!(matches!(open_vault(&dir, Some("missing")).await, Err(AppError::NotFound(_))));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // 添加的Key经过规范化，可按名称（忽略大小写）查找，名称重复时要求使用 id
+    #[tokio::test]
+    async fn test_add_and_find_key() {
+        let db = TestDatabase::open().await;
+        add(&db.pool, " Main ".into(), Some(" value ".into()), Some("OpenAI".into()), None, None)
+            .await
+            .unwrap();
+
+        let key = find_key(&db.pool, "main").await.unwrap();
+        assert_eq!(key.name, "Main");
+        assert_eq!(key.key_value, "value");
+        assert_eq!(key.platform.as_deref(), Some("openai"));
+        assert_eq!(find_key(&db.pool, &key.id).await.unwrap().id, key.id);
+        assert!(matches!(find_key(&db.pool, "missing").await, Err(AppError::NotFound(_))));
+
+        add(&db.pool, "main".into(), Some("other".into()), None, None, None).await.unwrap();
+        assert!(matches!(find_key(&db.pool, "MAIN").await, Err(AppError::Conflict(_))));
+
+        let result = add(&db.pool, "bad".into(), Some("has space".into()), None, None, None).await;
+        assert!(matches!(result, Err(AppError::InvalidFields(_))));
+    }
+
+    // 未设置主密码时无需验证；密码错误时返回 Unauthorized
+    #[tokio::test]
+    async fn test_unlock() {
+        let db = TestDatabase::open().await;
+        unlock(&db.pool).await.unwrap();
+
+        store_master_password(&db.pool, "correct horse").await.unwrap();
+        std::env::set_var(PASSWORD_ENV, "correct horse");
+        let correct = unlock(&db.pool).await;
+        std::env::set_var(PASSWORD_ENV, "wrong");
+        let wrong = unlock(&db.pool).await;
+        std::env::remove_var(PASSWORD_ENV);
+
+        assert!(correct.is_ok());
+        assert!(matches!(wrong, Err(AppError::Unauthorized(_))));
+    }
+}
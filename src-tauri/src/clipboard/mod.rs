@@ -1,4 +1,5 @@
 use arboard::Clipboard;
+use std::time::Duration;
 
 
 
@@ -9,6 +10,25 @@ pub fn set_clipboard_content(content: &str) -> Result<(), Box<dyn std::error::Er
     Ok(())
 }
 
+// 设置剪贴板内容并保持到超时或被其他程序替换。
+// Linux 上剪贴板内容由写入进程提供，命令行进程退出后内容即失效，因此需要阻塞等待
+#[cfg(target_os = "linux")]
+pub fn hold_clipboard_content(content: &str, timeout: Duration) -> Result<(), Box<dyn std::error::Error>> {
+    use arboard::SetExtLinux;
+
+    let mut clipboard = Clipboard::new()?;
+    clipboard
+        .set()
+        .wait_until(std::time::Instant::now() + timeout)
+        .text(content.to_string())?;
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn hold_clipboard_content(content: &str, _timeout: Duration) -> Result<(), Box<dyn std::error::Error>> {
+    set_clipboard_content(content)
+}
+
 // 监控剪贴板变化
 #[allow(dead_code)]
 pub fn start_clipboard_monitor<F>(_callback: F) -> Result<(), Box<dyn std::error::Error>>
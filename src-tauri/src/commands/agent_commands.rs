@@ -0,0 +1,36 @@
+use tauri::State;
+use crate::agent::{self, AgentStatus};
+use crate::database::audit::{audit, AuditEntry, APP_SOURCE};
+use crate::error::AppError;
+use crate::AppState;
+
+// 获取本地代理状态：是否在监听、套接字路径以及是否需要确认取Key请求
+#[tauri::command]
+pub async fn get_agent_status(state: State<'_, AppState>) -> Result<AgentStatus, AppError> {
+    let pool = &state.pool()?;
+    let socket_path = agent::socket_path();
+    Ok(AgentStatus {
+        running: socket_path.is_some(),
+        socket_path: socket_path.map(|p| p.to_string_lossy().to_string()),
+        confirm_requests: agent::get_confirm_requests(pool).await?,
+    })
+}
+
+// 设置代理收到取Key请求时是否弹出确认提示（需要解锁），记录审计日志
+#[tauri::command]
+pub async fn set_agent_confirm_requests(
+    state: State<'_, AppState>,
+    enabled: bool,
+) -> Result<bool, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    agent::set_confirm_requests(pool, enabled).await?;
+    audit(
+        pool,
+        AuditEntry::new(APP_SOURCE, "config", "allowed")
+            .with_target("agent")
+            .with_detail(format!("confirm_requests={}", enabled)),
+    )
+    .await;
+    Ok(enabled)
+}
@@ -1,32 +1,61 @@
 use tauri::State;
 use crate::{database::api_key::ApiKey, AppState};
-use crate::database::api_key::{insert_api_key, update_api_key, delete_api_key as delete_api_key_db, get_all_api_keys, search_api_keys as search_api_keys_db, get_all_platforms as get_all_platforms_db, get_existing_key_values};
+use crate::database::api_key::{ApiKeySummary, insert_api_key, update_api_key, delete_api_key as delete_api_key_db, get_all_api_keys, search_api_keys as search_api_keys_db, get_all_platforms as get_all_platforms_db, get_existing_key_values, get_api_key_by_id};
+use crate::database::audit::{audit, AuditEntry, APP_SOURCE};
+use crate::database::error::DatabaseError;
+use crate::database::key_version::{get_key_version, list_key_versions as list_key_versions_db, KeyVersionSummary};
+use crate::error::AppError;
+use crate::projects;
+use crate::validation::normalize_api_key;
 
-// 添加新的API Key
+// 添加新的API Key；id 与时间戳由后端分配，返回规范化后的记录
 #[tauri::command]
 pub async fn add_api_key(
     state: State<'_, AppState>,
     api_key: ApiKey,
-) -> Result<bool, String> {
+) -> Result<ApiKey, AppError> {
+    let mut api_key = ApiKey {
+        expires_at: api_key.expires_at,
+        rotate_every_days: api_key.rotate_every_days,
+        ..ApiKey::new(
+            api_key.name,
+            api_key.key_value,
+            api_key.platform,
+            api_key.description,
+            api_key.group_id,
+            api_key.tags,
+        )
+    };
+    normalize_api_key(&mut api_key).map_err(AppError::InvalidFields)?;
     println!("Adding API key: {} (platform: {:?})", api_key.name, api_key.platform);
-    let pool = &state.db;
+    let pool = &state.pool()?;
     insert_api_key(pool, &api_key).await.map_err(|e| {
         eprintln!("Failed to insert API key: {}", e);
-        e.to_string()
+        e
     })?;
     println!("API key added successfully");
-    Ok(true)
+    Ok(api_key)
 }
 
-// 编辑现有API Key
+// 编辑现有API Key（需要解锁）；传入 expected_updated_at 时启用乐观并发检查，reason 记录到历史版本中，返回更新后的摘要
 #[tauri::command]
 pub async fn edit_api_key(
+    app: tauri::AppHandle,
     state: State<'_, AppState>,
-    api_key: ApiKey,
-) -> Result<bool, String> {
-    let pool = &state.db;
-    update_api_key(pool, &api_key).await.map_err(|e| e.to_string())?;
-    Ok(true)
+    mut api_key: ApiKey,
+    expected_updated_at: Option<i64>,
+    reason: Option<String>,
+) -> Result<ApiKeySummary, AppError> {
+    normalize_api_key(&mut api_key).map_err(AppError::InvalidFields)?;
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    api_key.updated_at = chrono::Utc::now().timestamp();
+    update_api_key(pool, &api_key, expected_updated_at, reason.as_deref()).await?;
+    projects::notify_stale_projects(&app, pool, &api_key.id).await;
+    let updated = get_api_key_by_id(pool, &api_key.id)
+        .await?
+        .ok_or(DatabaseError::KeyNotFound)?;
+    Ok(updated.into())
 }
 
 // 删除API Key
@@ -34,44 +63,61 @@ pub async fn edit_api_key(
 pub async fn delete_api_key(
     state: State<'_, AppState>,
     key_id: String,
-) -> Result<bool, String> {
-    let pool = &state.db;
-    delete_api_key_db(pool, &key_id).await.map_err(|e| e.to_string())?;
+) -> Result<bool, AppError> {
+    let pool = &state.pool()?;
+    delete_api_key_db(pool, &key_id).await?;
+    audit(pool, AuditEntry::new(APP_SOURCE, "delete", "allowed").with_key(&key_id)).await;
     Ok(true)
 }
 
-// 获取API Key列表
+// 查看Key的值（需要解锁），每次查看都记录审计日志
+#[tauri::command]
+pub async fn reveal_api_key(
+    state: State<'_, AppState>,
+    key_id: String,
+) -> Result<String, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    let key = get_api_key_by_id(pool, &key_id)
+        .await?
+        .ok_or(DatabaseError::KeyNotFound)?;
+    audit(pool, AuditEntry::new(APP_SOURCE, "reveal", "allowed").with_key(&key.id)).await;
+    Ok(key.key_value)
+}
+
+// 获取API Key列表，只返回摘要，不包含Key的值
 #[tauri::command]
 pub async fn list_api_keys(
     state: State<'_, AppState>,
-) -> Result<Vec<ApiKey>, String> {
-    let pool = &state.db;
-    get_all_api_keys(pool).await.map_err(|e| e.to_string())
+) -> Result<Vec<ApiKeySummary>, AppError> {
+    let pool = &state.pool()?;
+    let keys = get_all_api_keys(pool).await?;
+    Ok(keys.into_iter().map(ApiKeySummary::from).collect())
 }
 
-// 搜索API Key
+// 搜索API Key，只返回摘要，不包含Key的值
 #[tauri::command]
 pub async fn search_api_keys(
     state: State<'_, AppState>,
     keyword: String,
-) -> Result<Vec<ApiKey>, String> {
+) -> Result<Vec<ApiKeySummary>, AppError> {
     println!("Searching API keys with keyword: '{}'", keyword);
-    let pool = &state.db;
+    let pool = &state.pool()?;
     let results = search_api_keys_db(pool, &keyword).await.map_err(|e| {
         eprintln!("Failed to search API keys: {}", e);
-        e.to_string()
+        e
     })?;
     println!("Found {} API keys", results.len());
-    Ok(results)
+    Ok(results.into_iter().map(ApiKeySummary::from).collect())
 }
 
 // 获取所有platform
 #[tauri::command]
 pub async fn get_all_platforms(
     state: State<'_, AppState>,
-) -> Result<Vec<String>, String> {
-    let pool = &state.db;
-    get_all_platforms_db(pool).await.map_err(|e| e.to_string())
+) -> Result<Vec<String>, AppError> {
+    let pool = &state.pool()?;
+    get_all_platforms_db(pool).await.map_err(AppError::from)
 }
 
 // 批量检查 API Key 是否已存在（按 key_value）
@@ -79,9 +125,82 @@ pub async fn get_all_platforms(
 pub async fn check_api_keys_exists(
     state: State<'_, AppState>,
     keys: Vec<String>,
-) -> Result<Vec<String>, String> {
-    let pool = &state.db;
+) -> Result<Vec<String>, AppError> {
+    let pool = &state.pool()?;
     get_existing_key_values(pool, &keys)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
+}
+
+// 获取API Key的历史版本（需要解锁），不包含旧的Key值
+#[tauri::command]
+pub async fn list_key_versions(
+    state: State<'_, AppState>,
+    key_id: String,
+) -> Result<Vec<KeyVersionSummary>, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    let versions = list_key_versions_db(pool, &key_id).await?;
+    Ok(versions.into_iter().map(KeyVersionSummary::from).collect())
+}
+
+// 查看历史版本中的Key值（需要解锁），每次查看都记录审计日志
+#[tauri::command]
+pub async fn reveal_key_version(
+    state: State<'_, AppState>,
+    version_id: String,
+) -> Result<String, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    let version = get_key_version(pool, &version_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Key version {}", version_id)))?;
+    audit(
+        pool,
+        AuditEntry::new(APP_SOURCE, "reveal", "allowed")
+            .with_key(&version.key_id)
+            .with_detail(format!("version {}", version.id)),
+    )
+    .await;
+    Ok(version.key_value)
+}
+
+// 回滚到指定历史版本；当前内容会作为新的历史版本保留
+#[tauri::command]
+pub async fn restore_key_version(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    version_id: String,
+) -> Result<ApiKeySummary, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    let version = get_key_version(pool, &version_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Key version {}", version_id)))?;
+    let mut api_key = get_api_key_by_id(pool, &version.key_id)
+        .await?
+        .ok_or(DatabaseError::KeyNotFound)?;
+
+    api_key.name = version.name;
+    api_key.key_value = version.key_value;
+    api_key.platform = version.platform;
+    api_key.description = version.description;
+    api_key.group_id = version.group_id;
+    api_key.tags = version.tags;
+    api_key.updated_at = chrono::Utc::now().timestamp();
+    let reason = format!("Restored version {}", version.id);
+    update_api_key(pool, &api_key, None, Some(&reason)).await?;
+    projects::notify_stale_projects(&app, pool, &api_key.id).await;
+    audit(
+        pool,
+        AuditEntry::new(APP_SOURCE, "restore", "allowed")
+            .with_key(&api_key.id)
+            .with_detail(format!("version {}", version.id)),
+    )
+    .await;
+
+    let restored = get_api_key_by_id(pool, &api_key.id)
+        .await?
+        .ok_or(DatabaseError::KeyNotFound)?;
+    Ok(restored.into())
 }
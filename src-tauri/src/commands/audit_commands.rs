@@ -0,0 +1,47 @@
+use tauri::State;
+use crate::database::audit::{self, AuditChainReport, AuditEntry, AuditFilter};
+use crate::error::AppError;
+use crate::AppState;
+
+// 审计日志默认返回的条数
+const DEFAULT_AUDIT_LIMIT: i64 = 200;
+
+// 获取最近的审计记录
+#[tauri::command]
+pub async fn list_audit_log(
+    state: State<'_, AppState>,
+    limit: Option<i64>,
+) -> Result<Vec<AuditEntry>, AppError> {
+    list_audit_events(
+        state,
+        Some(AuditFilter {
+            limit,
+            ..Default::default()
+        }),
+    )
+    .await
+}
+
+// 按来源、操作、Key、结果与时间范围查询审计记录，最新的在前
+#[tauri::command]
+pub async fn list_audit_events(
+    state: State<'_, AppState>,
+    filter: Option<AuditFilter>,
+) -> Result<Vec<AuditEntry>, AppError> {
+    let pool = &state.pool()?;
+    let mut filter = filter.unwrap_or_default();
+    filter.limit = Some(filter.limit.unwrap_or(DEFAULT_AUDIT_LIMIT).clamp(1, 10_000));
+    filter.offset = filter.offset.map(|offset| offset.max(0));
+    Ok(audit::list_audit_events(pool, &filter).await?)
+}
+
+// 校验审计日志的哈希链是否完整
+#[tauri::command]
+pub async fn verify_audit_chain(state: State<'_, AppState>) -> Result<AuditChainReport, AppError> {
+    let pool = &state.pool()?;
+    let report = audit::verify_audit_chain(pool).await?;
+    if !report.valid {
+        eprintln!("Audit log verification failed: {:?}", report.reason);
+    }
+    Ok(report)
+}
@@ -0,0 +1,91 @@
+use std::path::Path;
+use tauri::State;
+use crate::backup::{self, BackupConfig, BackupInfo};
+use crate::commands::vault_commands::emit_vault_changed;
+use crate::database::app_data_dir;
+use crate::database::audit::{audit, AuditEntry, APP_SOURCE};
+use crate::vaults::{self, VaultInfo};
+use crate::AppState;
+use crate::error::AppError;
+
+// 列出所有自动/手动备份
+#[tauri::command]
+pub async fn list_backups(app: tauri::AppHandle) -> Result<Vec<BackupInfo>, AppError> {
+    let dir = backup::backups_dir(&app)?;
+    Ok(backup::list_backups(&dir)?)
+}
+
+// 立即创建一份备份
+#[tauri::command]
+pub async fn create_backup(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<BackupInfo, AppError> {
+    let pool = &state.pool()?;
+    let dir = backup::backups_dir(&app)?;
+    let info = backup::create_backup(pool, &dir).await?;
+    let config = backup::load_config(pool).await?;
+    backup::apply_retention(&dir, &config)?;
+    Ok(info)
+}
+
+// 从备份恢复（需要解锁）：先校验备份文件，并在恢复前为当前数据再做一份备份
+#[tauri::command]
+pub async fn restore_backup(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    file_name: String,
+) -> Result<bool, AppError> {
+    // 只允许恢复备份目录中的文件
+    if Path::new(&file_name).file_name().map(|n| n.to_string_lossy() != file_name).unwrap_or(true) {
+        return Err(AppError::Validation(format!("Invalid backup file name: {}", file_name)));
+    }
+
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    let dir = backup::backups_dir(&app)?;
+    let backup_path = dir.join(&file_name);
+    backup::validate_backup(&backup_path).await?;
+
+    let safety = backup::create_backup(pool, &dir).await?;
+    if let Err(e) = backup::restore_backup(pool, &backup_path).await {
+        eprintln!("Failed to restore backup {}: {}", file_name, e);
+        audit(
+            pool,
+            AuditEntry::new(APP_SOURCE, "restore", "failed")
+                .with_target(&file_name)
+                .with_detail(e.to_string()),
+        )
+        .await;
+        return Err(e.into());
+    }
+    println!("Database restored from backup: {}", file_name);
+    audit(
+        pool,
+        AuditEntry::new(APP_SOURCE, "restore", "allowed")
+            .with_target(&file_name)
+            .with_detail(format!("previous data saved as {}", safety.file_name)),
+    )
+    .await;
+
+    // 恢复后各窗口中的数据已过期，按重新打开当前保险库通知刷新
+    let vault = vaults::load_registry(&app_data_dir(&app)?)?.active_vault();
+    emit_vault_changed(&app, Some(&VaultInfo::new(&vault, true)));
+    Ok(true)
+}
+
+// 获取备份配置
+#[tauri::command]
+pub async fn get_backup_config(state: State<'_, AppState>) -> Result<BackupConfig, AppError> {
+    Ok(backup::load_config(&state.pool()?).await?)
+}
+
+// 更新备份配置
+#[tauri::command]
+pub async fn set_backup_config(
+    state: State<'_, AppState>,
+    config: BackupConfig,
+) -> Result<bool, AppError> {
+    backup::save_config(&state.pool()?, &config).await?;
+    Ok(true)
+}
@@ -1,7 +1,10 @@
 use tauri::command;
 use serde::{Deserialize, Serialize};
 use crate::database::api_key::{insert_api_key, ApiKey};
+use crate::database::audit::{audit, AuditEntry, APP_SOURCE};
 use crate::AppState;
+use crate::error::AppError;
+use crate::validation::normalize_api_key;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -11,6 +14,7 @@ pub struct BatchApiKey {
     pub key_value: String,
     pub platform: Option<String>,
     pub description: Option<String>,
+    pub tags: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -26,22 +30,28 @@ pub struct BatchImportResult {
 pub async fn import_api_keys_batch(
     state: tauri::State<'_, AppState>,
     keys: Vec<BatchApiKey>,
-) -> Result<BatchImportResult, String> {
+) -> Result<BatchImportResult, AppError> {
+    let pool = &state.pool()?;
     let mut succeeded = 0;
     let mut failed = 0;
     let mut errors = Vec::new();
     
     for (index, key) in keys.iter().enumerate() {
-        let api_key = ApiKey::new(
+        let mut api_key = ApiKey::new(
             key.name.clone(),
             key.key_value.clone(),
             key.platform.clone(),
             key.description.clone(),
             None, // group_id
-            None, // tags
+            key.tags.clone(),
         );
-        
-        match insert_api_key(&state.db, &api_key).await {
+        if let Err(field_errors) = normalize_api_key(&mut api_key) {
+            failed += 1;
+            errors.push(format!("第{}条记录失败: {}", index + 1, AppError::InvalidFields(field_errors)));
+            continue;
+        }
+
+        match insert_api_key(pool, &api_key).await {
             Ok(_) => {
                 succeeded += 1;
             }
@@ -51,7 +61,13 @@ pub async fn import_api_keys_batch(
             }
         }
     }
-    
+
+    audit(
+        pool,
+        AuditEntry::new(APP_SOURCE, "import", if succeeded > 0 { "allowed" } else { "failed" })
+            .with_detail(format!("batch, {} keys imported, {} failed", succeeded, failed)),
+    )
+    .await;
     Ok(BatchImportResult {
         success: failed == 0,
         total: keys.len(),
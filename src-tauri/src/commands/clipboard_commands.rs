@@ -1,12 +1,35 @@
+use tauri::State;
 use crate::clipboard;
+use crate::database::api_key::get_api_key_by_id;
+use crate::database::audit::{audit, AuditEntry, APP_SOURCE};
+use crate::database::error::DatabaseError;
+use crate::error::AppError;
+use crate::AppState;
 
 
 
-// 复制内容到剪贴板
+// 复制Key的值到剪贴板（需要解锁），每次复制都记录审计日志；值只在后端读取，不经过前端
 #[tauri::command]
-pub fn copy_to_clipboard(content: String) -> Result<bool, String> {
-    match clipboard::set_clipboard_content(&content) {
-        Ok(_) => Ok(true),
-        Err(e) => Err(e.to_string()),
+pub async fn copy_to_clipboard(
+    state: State<'_, AppState>,
+    key_id: String,
+) -> Result<bool, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    let key = get_api_key_by_id(pool, &key_id)
+        .await?
+        .ok_or(DatabaseError::KeyNotFound)?;
+
+    if let Err(e) = clipboard::set_clipboard_content(&key.key_value) {
+        return Err(AppError::Clipboard(e.to_string()));
     }
-}
\ No newline at end of file
+    audit(pool, AuditEntry::new(APP_SOURCE, "copy", "allowed").with_key(&key.id)).await;
+    Ok(true)
+}
+
+// 清空剪贴板
+#[tauri::command]
+pub fn clear_clipboard() -> Result<bool, AppError> {
+    clipboard::set_clipboard_content("").map_err(|e| AppError::Clipboard(e.to_string()))?;
+    Ok(true)
+}
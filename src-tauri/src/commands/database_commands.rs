@@ -0,0 +1,71 @@
+use std::time::Duration;
+use tauri::State;
+use crate::backup;
+use crate::database::database_path;
+use crate::database::health::{integrity_check, DatabaseStatusEvent};
+use crate::database::repair::{repair_with_backup, repair_with_salvage, RepairResult, RepairStrategy};
+use crate::{setup_database, AppState};
+use crate::error::AppError;
+
+// 获取最近一次的数据库状态
+#[tauri::command]
+pub async fn get_database_status(state: State<'_, AppState>) -> Result<DatabaseStatusEvent, AppError> {
+    Ok(state.status())
+}
+
+// 等待数据库初始化结束，可选超时（毫秒）
+#[tauri::command]
+pub async fn wait_for_database(
+    state: State<'_, AppState>,
+    timeout_ms: Option<u64>,
+) -> Result<DatabaseStatusEvent, AppError> {
+    match timeout_ms {
+        Some(ms) => tokio::time::timeout(Duration::from_millis(ms), state.wait_for_status())
+            .await
+            .map_err(|_| AppError::DatabaseNotReady),
+        None => Ok(state.wait_for_status().await),
+    }
+}
+
+// 执行完整的数据库完整性检查
+#[tauri::command]
+pub async fn check_database_integrity(state: State<'_, AppState>) -> Result<bool, AppError> {
+    let pool = state.pool()?;
+    match integrity_check(&pool).await {
+        Ok(_) => Ok(true),
+        Err(e) => {
+            eprintln!("Database integrity check failed: {}", e);
+            Ok(false)
+        }
+    }
+}
+
+// 修复损坏的数据库：恢复最新备份或抢救可读数据，完成后重新初始化
+#[tauri::command]
+pub async fn repair_database(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    strategy: RepairStrategy,
+) -> Result<RepairResult, AppError> {
+    if state.pool().is_ok() {
+        return Err(AppError::Conflict(
+            "Database is already open and cannot be repaired while in use".to_string(),
+        ));
+    }
+
+    let db_path = database_path(&app)?;
+    let result = match strategy {
+        RepairStrategy::RestoreBackup => {
+            let dir = backup::backups_dir(&app)?;
+            repair_with_backup(&db_path, &dir).await
+        }
+        RepairStrategy::Salvage => repair_with_salvage(&db_path).await,
+    }
+    .map_err(|e| {
+        eprintln!("Database repair failed: {}", e);
+        e
+    })?;
+
+    setup_database(&app).await?;
+    Ok(result)
+}
@@ -0,0 +1,109 @@
+use std::path::Path;
+use tauri::State;
+use crate::database::api_key::{get_all_api_keys, get_api_keys_by_ids};
+use crate::database::audit::{audit, AuditEntry, APP_SOURCE};
+use crate::database::usage_history::record_usage;
+use crate::database::snapshot::{apply_snapshot, load_snapshot, ImportMode, SnapshotImportResult};
+use crate::export::formats::{render, ExportFilter, ExportFormat};
+use crate::export::vault_file::{read_vault_file, read_vault_header, write_vault_file, VaultFileHeader};
+use crate::AppState;
+use crate::error::AppError;
+
+// 导出加密的保险库备份文件，需要保险库处于解锁状态
+#[tauri::command]
+pub async fn export_vault(
+    state: State<'_, AppState>,
+    path: String,
+    passphrase: String,
+    include_usage_history: Option<bool>,
+) -> Result<VaultFileHeader, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    let snapshot = load_snapshot(pool, include_usage_history.unwrap_or(false))
+        .await?;
+
+    let header = write_vault_file(Path::new(&path), &passphrase, &snapshot).map_err(|e| {
+        eprintln!("Failed to export vault: {}", e);
+        e
+    })?;
+    println!("Exported {} API keys to vault file", header.key_count);
+    audit(
+        pool,
+        AuditEntry::new(APP_SOURCE, "export", "allowed")
+            .with_target(&path)
+            .with_detail(format!("vault file, {} keys", header.key_count)),
+    )
+    .await;
+    Ok(header)
+}
+
+// 读取保险库文件头（无需口令），用于导入前展示文件信息
+#[tauri::command]
+pub async fn inspect_vault_file(path: String) -> Result<VaultFileHeader, AppError> {
+    Ok(read_vault_header(Path::new(&path))?)
+}
+
+// 从加密的保险库文件导入，需要保险库处于解锁状态，校验通过后才会写入数据库
+#[tauri::command]
+pub async fn import_vault(
+    state: State<'_, AppState>,
+    path: String,
+    passphrase: String,
+    mode: ImportMode,
+) -> Result<SnapshotImportResult, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    let (_header, snapshot) = read_vault_file(Path::new(&path), &passphrase).map_err(|e| {
+        eprintln!("Failed to read vault file: {}", e);
+        e
+    })?;
+
+    let result = apply_snapshot(pool, &snapshot, mode)
+        .await?;
+    println!(
+        "Vault import finished: {} keys imported, {} skipped",
+        result.keys_imported, result.keys_skipped
+    );
+    audit(
+        pool,
+        AuditEntry::new(APP_SOURCE, "import", "allowed")
+            .with_target(&path)
+            .with_detail(format!("vault file, {} keys imported", result.keys_imported)),
+    )
+    .await;
+    Ok(result)
+}
+
+// 以 .env、shell、JSON、YAML 或 CSV 格式导出明文Key，需要保险库处于解锁状态
+#[tauri::command]
+pub async fn export_keys(
+    state: State<'_, AppState>,
+    ids: Option<Vec<String>>,
+    filter: Option<ExportFilter>,
+    format: ExportFormat,
+) -> Result<String, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+
+    let keys = match ids {
+        Some(ids) => get_api_keys_by_ids(pool, &ids).await,
+        None => get_all_api_keys(pool).await,
+    }?;
+    let filter = filter.unwrap_or_default();
+    let keys: Vec<_> = keys.into_iter().filter(|k| filter.matches(k)).collect();
+
+    let content = render(&keys, format)?;
+
+    for key in &keys {
+        if let Err(e) = record_usage(pool, &key.id, "export").await {
+            eprintln!("Failed to record export usage for {}: {}", key.id, e);
+        }
+    }
+    println!("Exported {} API keys as {:?}", keys.len(), format);
+    audit(
+        pool,
+        AuditEntry::new(APP_SOURCE, "export", "allowed").with_detail(format!("{:?}, {} keys", format, keys.len())),
+    )
+    .await;
+    Ok(content)
+}
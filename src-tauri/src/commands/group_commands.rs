@@ -1,15 +1,16 @@
 use tauri::State;
 use crate::{database::group::Group, AppState};
 use crate::database::group::{insert_group, get_all_groups};
+use crate::error::AppError;
 
 // Add a new group
 #[tauri::command]
 pub async fn add_group(
     state: State<'_, AppState>,
     group: Group,
-) -> Result<bool, String> {
-    let pool = &state.db;
-    insert_group(pool, &group).await.map_err(|e| e.to_string())?;
+) -> Result<bool, AppError> {
+    let pool = &state.pool()?;
+    insert_group(pool, &group).await?;
     Ok(true)
 }
 
@@ -17,7 +18,7 @@ pub async fn add_group(
 #[tauri::command]
 pub async fn list_groups(
     state: State<'_, AppState>,
-) -> Result<Vec<Group>, String> {
-    let pool = &state.db;
-    get_all_groups(pool).await.map_err(|e| e.to_string())
+) -> Result<Vec<Group>, AppError> {
+    let pool = &state.pool()?;
+    get_all_groups(pool).await.map_err(AppError::from)
 }
\ No newline at end of file
@@ -0,0 +1,84 @@
+use tauri::State;
+use crate::database::api_token::{self, ApiToken};
+use crate::database::audit::{audit, AuditEntry, APP_SOURCE};
+use crate::error::AppError;
+use crate::http_api::{self, HttpApiConfig, HttpApiStatus, IssuedApiToken};
+use crate::AppState;
+
+// 获取本地 HTTP API 的配置与运行状态
+#[tauri::command]
+pub async fn get_http_api_status(state: State<'_, AppState>) -> Result<HttpApiStatus, AppError> {
+    let pool = &state.pool()?;
+    Ok(HttpApiStatus {
+        config: http_api::load_config(pool).await?,
+        port: http_api::running_port(),
+    })
+}
+
+// 保存配置并按配置启动或停止服务（需要解锁），记录审计日志
+#[tauri::command]
+pub async fn set_http_api_config(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    config: HttpApiConfig,
+) -> Result<HttpApiStatus, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    http_api::save_config(pool, &config).await?;
+    let config = http_api::load_config(pool).await?;
+    audit(
+        pool,
+        AuditEntry::new(APP_SOURCE, "config", "allowed")
+            .with_target("http_api")
+            .with_detail(format!(
+                "enabled={}, port={}, rate_limit_per_minute={}",
+                config.enabled, config.port, config.rate_limit_per_minute
+            )),
+    )
+    .await;
+    let port = if config.enabled {
+        Some(http_api::start(&app, &config).await?)
+    } else {
+        http_api::stop();
+        None
+    };
+    Ok(HttpApiStatus { config, port })
+}
+
+// 列出访问令牌（不含令牌值）
+#[tauri::command]
+pub async fn list_api_tokens(state: State<'_, AppState>) -> Result<Vec<ApiToken>, AppError> {
+    let pool = &state.pool()?;
+    Ok(api_token::list_api_tokens(pool).await?)
+}
+
+// 创建访问令牌，令牌值只在返回结果中出现一次
+#[tauri::command]
+pub async fn create_api_token(
+    state: State<'_, AppState>,
+    name: String,
+    group_ids: Option<Vec<String>>,
+    tags: Option<Vec<String>>,
+    allow_secrets: bool,
+) -> Result<IssuedApiToken, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    let issued = http_api::issue_token(
+        pool,
+        &name,
+        group_ids.unwrap_or_default(),
+        tags.unwrap_or_default(),
+        allow_secrets,
+    )
+    .await?;
+    println!("Created API token {} ({})", issued.token.name, issued.token.id);
+    Ok(issued)
+}
+
+// 吊销访问令牌
+#[tauri::command]
+pub async fn revoke_api_token(state: State<'_, AppState>, token_id: String) -> Result<bool, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    Ok(api_token::delete_api_token(pool, &token_id).await?)
+}
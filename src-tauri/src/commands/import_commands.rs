@@ -0,0 +1,33 @@
+use std::path::Path;
+use crate::commands::window_commands::create_preview_window;
+use crate::importers::{self, ImportFormat, ImportedKey};
+use crate::error::AppError;
+
+// 解析其他密码管理器的导出文件
+#[tauri::command]
+pub async fn parse_import_file(
+    path: String,
+    format: Option<ImportFormat>,
+) -> Result<Vec<ImportedKey>, AppError> {
+    Ok(importers::parse_file(Path::new(&path), format)?)
+}
+
+// 解析导出文件并在批量导入预览窗口中展示
+#[tauri::command]
+pub async fn preview_import_file(
+    app: tauri::AppHandle,
+    path: String,
+    format: Option<ImportFormat>,
+    theme: Option<String>,
+    language: Option<String>,
+) -> Result<usize, AppError> {
+    let keys = importers::parse_file(Path::new(&path), format).map_err(|e| {
+        eprintln!("Failed to parse import file {}: {}", path, e);
+        e
+    })?;
+    println!("Parsed {} keys from {}", keys.len(), path);
+
+    let preview_data = serde_json::to_string(&keys)?;
+    create_preview_window(app, preview_data, theme, language).await?;
+    Ok(keys.len())
+}
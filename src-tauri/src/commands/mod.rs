@@ -1,7 +1,22 @@
+pub mod agent_commands;
 pub mod api_key_commands;
+pub mod audit_commands;
+pub mod backup_commands;
 pub mod batch_import_commands;
 pub mod clipboard_commands;
+pub mod database_commands;
+pub mod export_commands;
+pub mod import_commands;
+pub mod project_commands;
+pub mod provider_commands;
+pub mod reminder_commands;
+pub mod scan_commands;
 pub mod security_commands;
 pub mod shortcut_commands;
+pub mod template_commands;
+pub mod trash_commands;
+pub mod vault_commands;
+pub mod verify_commands;
 pub mod window_commands;
-pub mod group_commands;
\ No newline at end of file
+pub mod group_commands;
+pub mod http_api_commands;
\ No newline at end of file
@@ -0,0 +1,64 @@
+use std::path::Path;
+use tauri::State;
+use crate::database::api_key::find_api_key;
+use crate::database::audit::APP_SOURCE;
+use crate::database::error::DatabaseError;
+use crate::database::project_binding::{delete_bindings, upsert_binding};
+use crate::error::AppError;
+use crate::projects::{self, ProjectInfo, SyncReport};
+use crate::AppState;
+
+// 列出所有绑定了Key的项目，并标出 .env 已过期的项目
+#[tauri::command]
+pub async fn list_projects(state: State<'_, AppState>) -> Result<Vec<ProjectInfo>, AppError> {
+    let pool = &state.pool()?;
+    projects::list_projects(pool).await
+}
+
+// 将项目中的变量绑定到Key（按ID或名称），已绑定的变量会被替换
+#[tauri::command]
+pub async fn bind_project_key(
+    state: State<'_, AppState>,
+    project_path: String,
+    env_var: String,
+    key: String,
+) -> Result<ProjectInfo, AppError> {
+    let pool = &state.pool()?;
+    let path = projects::normalize_project_path(Path::new(&project_path))?;
+    let env_var = env_var.trim();
+    if env_var.is_empty() {
+        return Err(AppError::Validation("Variable name cannot be empty".to_string()));
+    }
+    let api_key = find_api_key(pool, &key).await.map_err(|e| match e {
+        DatabaseError::KeyNotFound => AppError::NotFound(format!("API key {}", key)),
+        e => e.into(),
+    })?;
+    upsert_binding(pool, &path, env_var, &api_key.id).await?;
+    projects::get_project(pool, &path).await
+}
+
+// 解除项目中变量的绑定；未指定变量时解除整个项目
+#[tauri::command]
+pub async fn unbind_project(
+    state: State<'_, AppState>,
+    project_path: String,
+    env_var: Option<String>,
+) -> Result<u64, AppError> {
+    let pool = &state.pool()?;
+    // 项目目录可能已被删除，此时按原样匹配
+    let path = projects::normalize_project_path(Path::new(&project_path)).unwrap_or(project_path);
+    Ok(delete_bindings(pool, &path, env_var.as_deref()).await?)
+}
+
+// 按绑定写入或刷新项目的 .env
+#[tauri::command]
+pub async fn sync_project(
+    state: State<'_, AppState>,
+    project_path: String,
+) -> Result<SyncReport, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    let report = projects::sync_project(pool, APP_SOURCE, &project_path).await?;
+    println!("Synced {} variables into {}", report.written.len(), report.env_file);
+    Ok(report)
+}
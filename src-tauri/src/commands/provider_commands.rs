@@ -0,0 +1,8 @@
+use crate::error::AppError;
+use crate::providers::{ProviderInfo, PROVIDERS};
+
+// 列出内置平台（名称、别名、Key格式、环境变量名等），前端以此为准
+#[tauri::command]
+pub async fn list_providers() -> Result<Vec<ProviderInfo>, AppError> {
+    Ok(PROVIDERS.iter().map(ProviderInfo::from).collect())
+}
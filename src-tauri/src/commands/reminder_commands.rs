@@ -0,0 +1,55 @@
+use tauri::State;
+use crate::database::expiry::{self, ExpiringKey};
+use crate::error::AppError;
+use crate::reminders;
+use crate::validation::validate_schedule;
+use crate::AppState;
+
+// 查询 within_days 天内过期或需要轮换的Key，未指定时使用提醒设置
+#[tauri::command]
+pub async fn expiring_keys(
+    state: State<'_, AppState>,
+    within_days: Option<i64>,
+) -> Result<Vec<ExpiringKey>, AppError> {
+    let pool = &state.pool()?;
+    let days = match within_days {
+        Some(days) => days,
+        None => reminders::get_reminder_days(pool).await?,
+    };
+    Ok(expiry::expiring_keys(pool, days).await?)
+}
+
+// 获取提前提醒天数
+#[tauri::command]
+pub async fn get_expiry_reminder_days(state: State<'_, AppState>) -> Result<i64, AppError> {
+    Ok(reminders::get_reminder_days(&state.pool()?).await?)
+}
+
+// 设置提前提醒天数，并立即重新检查以刷新前端角标
+#[tauri::command]
+pub async fn set_expiry_reminder_days(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    days: i64,
+) -> Result<bool, AppError> {
+    let pool = &state.pool()?;
+    reminders::set_reminder_days(pool, days).await?;
+    reminders::check_and_notify(&app, pool).await?;
+    Ok(true)
+}
+
+// 设置或清除Key的过期时间与轮换周期；编辑Key时不提交这两个字段则保持不变
+#[tauri::command]
+pub async fn set_key_schedule(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    key_id: String,
+    expires_at: Option<i64>,
+    rotate_every_days: Option<i64>,
+) -> Result<bool, AppError> {
+    validate_schedule(expires_at, rotate_every_days).map_err(AppError::InvalidFields)?;
+    let pool = &state.pool()?;
+    expiry::set_key_schedule(pool, &key_id, expires_at, rotate_every_days).await?;
+    reminders::check_and_notify(&app, pool).await?;
+    Ok(true)
+}
@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+use tauri::State;
+use crate::error::AppError;
+use crate::scanner::git::GitScanReport;
+use crate::scanner::{self, Detector, ScanReport};
+use crate::AppState;
+
+// 扫描目录中泄露的Key：按平台Key格式检测，并通过哈希比对找出保险库中已保存的Key。
+// 结果只包含脱敏后的片段，不包含Key的值
+#[tauri::command]
+pub async fn scan_directory(
+    state: State<'_, AppState>,
+    path: String,
+    include_ignored: Option<bool>,
+) -> Result<ScanReport, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    let detector = Detector::load(pool).await?;
+
+    let root = PathBuf::from(&path);
+    let include_ignored = include_ignored.unwrap_or(false);
+    let report = tauri::async_runtime::spawn_blocking(move || {
+        scanner::scan_directory(&detector, &root, include_ignored)
+    })
+    .await
+    .map_err(|e| AppError::Io(format!("Scan failed: {}", e)))??;
+
+    println!(
+        "Scanned {} files in {}, found {} possible leaks",
+        report.files_scanned,
+        path,
+        report.findings.len()
+    );
+    Ok(report)
+}
+
+// 扫描本地 git 仓库的全部提交历史，找出曾被提交过的Key
+#[tauri::command]
+pub async fn scan_git_history(state: State<'_, AppState>, path: String) -> Result<GitScanReport, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    let detector = Detector::load(pool).await?;
+
+    let root = PathBuf::from(&path);
+    let report = tauri::async_runtime::spawn_blocking(move || scanner::git::scan_repository(&detector, &root))
+        .await
+        .map_err(|e| AppError::Io(format!("Scan failed: {}", e)))??;
+
+    println!(
+        "Scanned {} commits in {}, {} vault keys were committed",
+        report.commits_scanned,
+        report.repository,
+        report.leaked_keys.len()
+    );
+    Ok(report)
+}
@@ -1,45 +1,93 @@
 use tauri::State;
 use crate::{security, AppState};
+use crate::security::lockout::{self, verify_unlock_attempt, LockoutPolicy, UnlockAttemptStatus};
+use crate::security::password::{has_master_password, store_master_password};
+use crate::database::audit::{audit, AuditEntry, APP_SOURCE};
+use crate::error::AppError;
 
 // 设置主密码
 #[tauri::command]
 pub async fn set_master_password(
-    _state: State<'_, AppState>,
+    state: State<'_, AppState>,
     password: String,
-) -> Result<bool, String> {
-    match security::hash_password(&password) {
-        Ok(_hash) => {
-            // 这里应该将哈希存储到数据库或配置中
-            // 为了简化，我们暂时只返回成功
-            Ok(true)
-        },
-        Err(e) => Err(e.to_string()),
+) -> Result<bool, AppError> {
+    let pool = &state.pool()?;
+    // 已设置过主密码时，只有在解锁状态下才能修改
+    if has_master_password(pool).await? && !state.session.is_unlocked() {
+        return Err(AppError::VaultLocked);
+    }
+    if password.is_empty() {
+        return Err(AppError::Validation("Master password cannot be empty".to_string()));
     }
+
+    let action = if has_master_password(pool).await? { "password-change" } else { "password-set" };
+    store_master_password(pool, &password).await?;
+    state.session.unlock();
+    audit(pool, AuditEntry::new(APP_SOURCE, action, "allowed")).await;
+    Ok(true)
 }
 
 // 验证主密码
 #[tauri::command]
 pub async fn verify_master_password(
-    _state: State<'_, AppState>,
-    _password: String,
-) -> Result<bool, String> {
-    // 这里应该从数据库或配置中获取存储的哈希
-    // 为了简化，我们暂时只返回成功
+    state: State<'_, AppState>,
+    password: String,
+) -> Result<bool, AppError> {
+    let pool = state.pool()?;
+    let is_valid = verify_unlock_attempt(&pool, &password, APP_SOURCE).await?;
+    if is_valid {
+        state.session.unlock();
+    }
+    Ok(is_valid)
+}
+
+// 查询主密码的连续失败次数与需要等待的时间，锁定状态下也可调用
+#[tauri::command]
+pub async fn get_unlock_attempt_status(state: State<'_, AppState>) -> Result<UnlockAttemptStatus, AppError> {
+    lockout::get_attempt_status(&state.pool()?).await
+}
+
+// 设置连续输错多少次后锁定以及锁定时长，需要解锁
+#[tauri::command]
+pub async fn set_lockout_policy(
+    state: State<'_, AppState>,
+    policy: LockoutPolicy,
+) -> Result<bool, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    lockout::set_policy(pool, &policy).await?;
     Ok(true)
 }
 
+// 锁定保险库
+#[tauri::command]
+pub async fn lock_vault(state: State<'_, AppState>) -> Result<bool, AppError> {
+    state.session.lock();
+    if let Ok(pool) = state.pool() {
+        audit(&pool, AuditEntry::new(APP_SOURCE, "lock", "allowed")).await;
+    }
+    Ok(true)
+}
+
+// 查询保险库是否已锁定
+#[tauri::command]
+pub async fn is_vault_locked(state: State<'_, AppState>) -> Result<bool, AppError> {
+    let pool = state.pool()?;
+    Ok(state.session.ensure_unlocked(&pool).await.is_err())
+}
+
 // 加密API Key
 #[tauri::command]
 pub async fn encrypt_key(
     _state: State<'_, AppState>,
     key: String,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     // 生成加密密钥（实际应用中应该使用主密码）
     let encryption_key = [0u8; 32]; // 简化处理
     
     match security::encrypt_data(&key, &encryption_key) {
         Ok(encrypted) => Ok(encrypted),
-        Err(e) => Err(e.to_string()),
+        Err(e) => Err(AppError::Encryption(e)),
     }
 }
 
@@ -48,12 +96,12 @@ pub async fn encrypt_key(
 pub async fn decrypt_key(
     _state: State<'_, AppState>,
     encrypted_key: String,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     // 生成加密密钥（实际应用中应该使用主密码）
     let encryption_key = [0u8; 32]; // 简化处理
     
     match security::decrypt_data(&encrypted_key, &encryption_key) {
         Ok(decrypted) => Ok(decrypted),
-        Err(e) => Err(e.to_string()),
+        Err(e) => Err(AppError::Decryption(e)),
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,35 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::State;
+use crate::database::audit::APP_SOURCE;
+use crate::error::AppError;
+use crate::templates::{self, RenderReport};
+use crate::AppState;
+
+// 用保存的Key填充模板文件（如 .env.example），mappings 为变量名到Key ID或名称的映射。
+// 存在无法解析的引用时默认不写入文件，只返回报告
+#[tauri::command]
+pub async fn render_template(
+    state: State<'_, AppState>,
+    path: String,
+    mappings: Option<HashMap<String, String>>,
+    output_path: Option<String>,
+    allow_unresolved: Option<bool>,
+) -> Result<RenderReport, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    let output = output_path.map(PathBuf::from);
+    let report = templates::render_file(
+        pool,
+        APP_SOURCE,
+        &PathBuf::from(&path),
+        output.as_deref(),
+        &mappings.unwrap_or_default(),
+        allow_unresolved.unwrap_or(false),
+    )
+    .await?;
+    if report.written {
+        println!("Rendered {} into {}", path, report.output_path);
+    }
+    Ok(report)
+}
@@ -0,0 +1,72 @@
+use tauri::State;
+use crate::database::api_key::{get_api_key_by_id, ApiKeySummary};
+use crate::database::audit::{audit, AuditEntry, APP_SOURCE};
+use crate::database::error::DatabaseError;
+use crate::database::trash;
+use crate::error::AppError;
+use crate::AppState;
+
+// 获取回收站中的API Key（不含Key的值），顺带清理已过保留期的条目
+#[tauri::command]
+pub async fn list_trash(state: State<'_, AppState>) -> Result<Vec<ApiKeySummary>, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    if let Err(e) = trash::purge_expired(pool).await {
+        eprintln!("Failed to purge expired trash: {}", e);
+    }
+    let keys = trash::list_trashed_api_keys(pool).await?;
+    Ok(keys.into_iter().map(ApiKeySummary::from).collect())
+}
+
+// 从回收站恢复API Key，返回恢复后的记录（不含Key的值）
+#[tauri::command]
+pub async fn restore_api_key(
+    state: State<'_, AppState>,
+    key_id: String,
+) -> Result<ApiKeySummary, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    trash::restore_api_key(pool, &key_id).await?;
+    let restored = get_api_key_by_id(pool, &key_id)
+        .await?
+        .ok_or(DatabaseError::KeyNotFound)?;
+    audit(pool, AuditEntry::new(APP_SOURCE, "restore", "allowed").with_key(&key_id)).await;
+    Ok(restored.into())
+}
+
+// 彻底删除回收站中的API Key；未指定 key_ids 时清空回收站
+#[tauri::command]
+pub async fn purge_trash(
+    state: State<'_, AppState>,
+    key_ids: Option<Vec<String>>,
+) -> Result<u64, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    let purged = trash::purge_trash(pool, key_ids.as_deref()).await?;
+    println!("Purged {} API keys from trash", purged);
+    let entry = match key_ids.as_deref() {
+        Some([key_id]) => AuditEntry::new(APP_SOURCE, "purge", "allowed").with_key(key_id),
+        _ => AuditEntry::new(APP_SOURCE, "purge", "allowed"),
+    };
+    audit(pool, entry.with_detail(format!("{} keys permanently deleted", purged))).await;
+    Ok(purged)
+}
+
+// 获取回收站保留天数
+#[tauri::command]
+pub async fn get_trash_retention_days(state: State<'_, AppState>) -> Result<i64, AppError> {
+    Ok(trash::get_retention_days(&state.pool()?).await?)
+}
+
+// 设置回收站保留天数，0 表示不自动清理
+#[tauri::command]
+pub async fn set_trash_retention_days(
+    state: State<'_, AppState>,
+    days: i64,
+) -> Result<bool, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    trash::set_retention_days(pool, days).await?;
+    trash::purge_expired(pool).await?;
+    Ok(true)
+}
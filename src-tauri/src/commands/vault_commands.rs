@@ -0,0 +1,121 @@
+use tauri::{Emitter, State};
+use crate::database::audit::APP_SOURCE;
+use crate::database::health::{DatabaseStatus, DatabaseStatusEvent};
+use crate::database::{app_data_dir, open_database, DATABASE_FILE};
+use crate::error::AppError;
+use crate::security::lockout::verify_unlock_attempt;
+use crate::security::password::{has_master_password, store_master_password};
+use crate::vaults::{self, VaultInfo};
+use crate::{activate_pool, report_database_status, AppState};
+
+// 通知所有窗口刷新：切换时携带新的保险库，关闭时为 null
+pub(crate) fn emit_vault_changed(app: &tauri::AppHandle, vault: Option<&VaultInfo>) {
+    if let Err(e) = app.emit("vault-changed", vault) {
+        eprintln!("Failed to emit vault-changed: {}", e);
+    }
+}
+
+// 列出所有保险库
+#[tauri::command]
+pub async fn list_vaults(app: tauri::AppHandle) -> Result<Vec<VaultInfo>, AppError> {
+    let app_dir = app_data_dir(&app)?;
+    Ok(vaults::list_vaults(&app_dir)?)
+}
+
+// 创建保险库：建立独立的数据库文件，可选设置主密码；不会切换到新保险库
+#[tauri::command]
+pub async fn create_vault(
+    app: tauri::AppHandle,
+    name: String,
+    master_password: Option<String>,
+) -> Result<VaultInfo, AppError> {
+    if master_password.as_deref() == Some("") {
+        return Err(AppError::Validation("Master password cannot be empty".to_string()));
+    }
+
+    let app_dir = app_data_dir(&app)?;
+    let entry = vaults::new_vault_entry(&app_dir, &name)?;
+    let dir = vaults::vault_dir(&app_dir, &entry.id)?;
+
+    let pool = open_database(&dir.join(DATABASE_FILE)).await?;
+    let initialized = match master_password {
+        Some(password) => store_master_password(&pool, &password).await,
+        None => Ok(()),
+    };
+    pool.close().await;
+    let registered = initialized.and_then(|_| vaults::add_vault(&app_dir, entry.clone()).map_err(AppError::from));
+    if let Err(e) = registered {
+        if let Err(cleanup) = std::fs::remove_dir_all(&dir) {
+            eprintln!("Failed to remove vault directory {:?}: {}", dir, cleanup);
+        }
+        return Err(e);
+    }
+
+    println!("Created vault {} at {:?}", entry.id, dir);
+    Ok(VaultInfo::new(&entry, false))
+}
+
+// 打开并切换到指定保险库；提供主密码时一并解锁，密码错误则保持当前保险库不变
+#[tauri::command]
+pub async fn open_vault(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    vault_id: String,
+    password: Option<String>,
+) -> Result<VaultInfo, AppError> {
+    let _guard = state.vault_switch.lock().await;
+    let app_dir = app_data_dir(&app)?;
+    let registry = vaults::load_registry(&app_dir)?;
+    let entry = registry
+        .find(&vault_id)
+        .cloned()
+        .ok_or_else(|| AppError::NotFound(format!("Vault {}", vault_id)))?;
+
+    let dir = vaults::vault_dir(&app_dir, &entry.id)?;
+    let pool = open_database(&dir.join(DATABASE_FILE)).await?;
+    let unlocked = match password {
+        Some(password) if has_master_password(&pool).await? => {
+            match verify_unlock_attempt(&pool, &password, APP_SOURCE).await {
+                Ok(true) => {}
+                result => {
+                    pool.close().await;
+                    result?;
+                    return Err(AppError::Unauthorized("Incorrect master password".to_string()));
+                }
+            }
+            true
+        }
+        _ => false,
+    };
+
+    if let Err(e) = vaults::set_active_vault(&app_dir, &entry.id) {
+        pool.close().await;
+        return Err(e.into());
+    }
+    state.session.lock();
+    if let Some(previous) = activate_pool(&app, pool).await {
+        previous.close().await;
+    }
+    if unlocked {
+        state.session.unlock();
+    }
+
+    println!("Switched to vault {}", entry.id);
+    let info = VaultInfo::new(&entry, true);
+    emit_vault_changed(&app, Some(&info));
+    Ok(info)
+}
+
+// 关闭当前保险库：释放连接池并锁定，之后需重新打开保险库
+#[tauri::command]
+pub async fn close_vault(app: tauri::AppHandle, state: State<'_, AppState>) -> Result<bool, AppError> {
+    let _guard = state.vault_switch.lock().await;
+    state.session.lock();
+    let Some(pool) = state.replace_pool(None) else {
+        return Ok(false);
+    };
+    pool.close().await;
+    report_database_status(&app, DatabaseStatusEvent::new(DatabaseStatus::Closed, None));
+    emit_vault_changed(&app, None);
+    Ok(true)
+}
@@ -0,0 +1,102 @@
+use tauri::State;
+use crate::database::api_key::{get_all_api_keys, get_api_key_by_id, get_api_keys_by_ids};
+use crate::database::error::DatabaseError;
+use crate::database::settings::{delete_setting, set_setting};
+use crate::error::AppError;
+use crate::verifier::{self, verify_and_record, VerificationResult, BASE_URL_SETTING_PREFIX};
+use crate::AppState;
+
+// 通过平台接口验证指定Key是否仍然可用
+#[tauri::command]
+pub async fn verify_api_key(
+    state: State<'_, AppState>,
+    key_id: String,
+) -> Result<VerificationResult, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+
+    let api_key = get_api_key_by_id(pool, &key_id)
+        .await?
+        .ok_or(DatabaseError::KeyNotFound)?;
+    let client = reqwest::Client::new();
+    verify_and_record(pool, &client, &api_key).await?.ok_or_else(|| {
+        AppError::Validation(format!(
+            "Online verification is not supported for platform {:?}",
+            api_key.platform
+        ))
+    })
+}
+
+// 批量验证Key；未指定 key_ids 时验证所有支持的Key，跳过不支持的平台
+#[tauri::command]
+pub async fn verify_api_keys(
+    state: State<'_, AppState>,
+    key_ids: Option<Vec<String>>,
+) -> Result<Vec<VerificationResult>, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+
+    let keys = match key_ids {
+        Some(ids) => get_api_keys_by_ids(pool, &ids).await?,
+        None => get_all_api_keys(pool).await?,
+    };
+    let client = reqwest::Client::new();
+    let mut results = Vec::new();
+    for api_key in &keys {
+        if let Some(result) = verify_and_record(pool, &client, api_key).await? {
+            results.push(result);
+        }
+    }
+    Ok(results)
+}
+
+// 列出支持在线验证的平台
+#[tauri::command]
+pub async fn list_verifier_providers() -> Result<Vec<String>, AppError> {
+    Ok(verifier::supported_providers().into_iter().map(str::to_string).collect())
+}
+
+// 验证接口会收到Key的值：只允许 https，http 仅限本机回环地址（如本地模拟服务）
+fn check_base_url(url: &str) -> Result<(), AppError> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| AppError::Validation(format!("Invalid base URL {}: {}", url, e)))?;
+    let host = parsed.host_str().unwrap_or_default();
+    let loopback = host.eq_ignore_ascii_case("localhost")
+        || host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback());
+    match parsed.scheme() {
+        "https" if !host.is_empty() => Ok(()),
+        "http" if loopback => Ok(()),
+        "http" => Err(AppError::Validation(format!(
+            "Plain http is only allowed for localhost, use https for {}",
+            url
+        ))),
+        _ => Err(AppError::Validation(format!("Invalid base URL: {}", url))),
+    }
+}
+
+// 覆盖平台的验证接口地址（需要解锁），base_url 为空时恢复默认
+#[tauri::command]
+pub async fn set_verifier_base_url(
+    state: State<'_, AppState>,
+    provider: String,
+    base_url: Option<String>,
+) -> Result<bool, AppError> {
+    let pool = &state.pool()?;
+    state.session.ensure_unlocked(pool).await?;
+    if verifier::verifier_for(&provider, None).is_none() {
+        return Err(AppError::Validation(format!("No verifier for provider {}", provider)));
+    }
+    let provider = crate::providers::canonicalize_platform(&provider);
+    let key = format!("{}{}", BASE_URL_SETTING_PREFIX, provider);
+    match base_url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty()) {
+        Some(url) => {
+            check_base_url(&url)?;
+            set_setting(pool, &key, &url).await?
+        }
+        None => delete_setting(pool, &key).await?,
+    }
+    Ok(true)
+}
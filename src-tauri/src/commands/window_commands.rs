@@ -1,6 +1,7 @@
 use tauri::{Manager, WebviewWindowBuilder, WebviewUrl, Emitter};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
+use crate::error::AppError;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WindowPosition {
@@ -16,28 +17,28 @@ pub struct WindowSize {
 
 // 显示浮动工具条窗口
 #[tauri::command]
-pub async fn show_floating_toolbar(app: tauri::AppHandle) -> Result<(), String> {
+pub async fn show_floating_toolbar(app: tauri::AppHandle) -> Result<(), AppError> {
     // 获取浮动工具条窗口
     if let Some(window) = app.get_webview_window("floating-toolbar") {
-        window.show().map_err(|e| e.to_string())?;
-        window.set_focus().map_err(|e| e.to_string())?;
+        window.show()?;
+        window.set_focus()?;
     }
     Ok(())
 }
 
 // 隐藏浮动工具条窗口
 #[tauri::command]
-pub async fn hide_floating_toolbar(app: tauri::AppHandle) -> Result<(), String> {
+pub async fn hide_floating_toolbar(app: tauri::AppHandle) -> Result<(), AppError> {
     // 获取浮动工具条窗口
     if let Some(window) = app.get_webview_window("floating-toolbar") {
-        window.hide().map_err(|e| e.to_string())?;
+        window.hide()?;
     }
     Ok(())
 }
 
 // 退出应用程序
 #[tauri::command]
-pub async fn exit_application(app: tauri::AppHandle) -> Result<(), String> {
+pub async fn exit_application(app: tauri::AppHandle) -> Result<(), AppError> {
     println!("Exit application command called");
     app.exit(0);
     Ok(())
@@ -49,13 +50,12 @@ pub async fn set_window_position(
     app: tauri::AppHandle,
     window_label: &str,
     position: WindowPosition,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     if let Some(window) = app.get_webview_window(window_label) {
         window
             .set_position(tauri::Position::Logical(tauri::LogicalPosition::new(
                 position.x, position.y,
-            )))
-            .map_err(|e| e.to_string())?;
+            )))?;
     }
     Ok(())
 }
@@ -66,13 +66,12 @@ pub async fn set_window_size(
     app: tauri::AppHandle,
     window_label: &str,
     size: WindowSize,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     if let Some(window) = app.get_webview_window(window_label) {
         window
             .set_size(tauri::Size::Logical(tauri::LogicalSize::new(
                 size.width, size.height,
-            )))
-            .map_err(|e| e.to_string())?;
+            )))?;
     }
     Ok(())
 }
@@ -98,11 +97,10 @@ pub async fn set_click_through(
     app: tauri::AppHandle,
     window_label: &str,
     enabled: bool,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     if let Some(window) = app.get_webview_window(window_label) {
         window
-            .set_ignore_cursor_events(enabled)
-            .map_err(|e| e.to_string())?;
+            .set_ignore_cursor_events(enabled)?;
     }
     Ok(())
 }
@@ -114,12 +112,12 @@ pub async fn create_preview_window(
     preview_data: String,
     theme: Option<String>,
     language: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     println!("Creating preview window with language: {:?}", language);
     // 检查预览窗口是否已存在
     if let Some(existing_window) = app.get_webview_window("preview") {
         // 如果存在，先关闭它
-        existing_window.close().map_err(|e| e.to_string())?;
+        existing_window.close()?;
     }
 
     // 创建新的预览窗口
@@ -186,8 +184,7 @@ pub async fn create_preview_window(
         theme_json = serde_json::to_string(&theme.unwrap_or_else(|| "system".to_string())).unwrap_or_else(|_| "\"system\"".to_string()),
         language_json = serde_json::to_string(&language.unwrap_or_else(|| "zh-CN".to_string())).unwrap_or_else(|_| "\"zh-CN\"".to_string())
     ))
-    .build()
-    .map_err(|e| e.to_string())?;
+    .build()?;
 
     // 确保预览窗口置顶
     if let Err(e) = window.set_always_on_top(true) {
@@ -224,16 +221,16 @@ pub async fn create_preview_window(
 
 // 关闭预览窗口
 #[tauri::command]
-pub async fn close_preview_window(app: tauri::AppHandle) -> Result<(), String> {
+pub async fn close_preview_window(app: tauri::AppHandle) -> Result<(), AppError> {
     if let Some(window) = app.get_webview_window("preview") {
-        window.close().map_err(|e| e.to_string())?;
+        window.close()?;
     }
     Ok(())
 }
 
 // 预览窗口确认导入：由预览窗口调用，后端转发事件给主窗口并关闭预览
 #[tauri::command]
-pub async fn confirm_import_preview(app: tauri::AppHandle, data: String) -> Result<(), String> {
+pub async fn confirm_import_preview(app: tauri::AppHandle, data: String) -> Result<(), AppError> {
     // 将事件广播给所有窗口，避免窗口 label 不一致导致接收不到
     if let Err(e) = app.emit("confirm-import", &data) {
         eprintln!("Failed to emit confirm-import: {}", e);
@@ -2,10 +2,13 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use sqlx::SqlitePool;
 use crate::database::error::DatabaseError;
+use crate::database::key_version;
 
 #[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiKey {
+    // id 与时间戳由后端分配，客户端新增时可省略
+    #[serde(default)]
     pub id: String,
     pub name: String,
     #[serde(rename = "keyValue")]
@@ -15,12 +18,27 @@ pub struct ApiKey {
     #[serde(rename = "groupId")]
     pub group_id: Option<String>,
     pub tags: Option<String>,
-    #[serde(rename = "createdAt")]
+    #[serde(rename = "createdAt", default)]
     pub created_at: i64,
-    #[serde(rename = "updatedAt")]
+    #[serde(rename = "updatedAt", default)]
     pub updated_at: i64,
     #[serde(rename = "lastUsedAt")]
     pub last_used_at: Option<i64>,
+    // 移入回收站的时间，为空表示未删除
+    #[serde(rename = "deletedAt", default)]
+    pub deleted_at: Option<i64>,
+    // 过期时间与建议轮换周期，均为可选
+    #[serde(rename = "expiresAt", default)]
+    pub expires_at: Option<i64>,
+    #[serde(rename = "rotateEveryDays", default)]
+    pub rotate_every_days: Option<i64>,
+    // 最近一次在线验证的结果，由后端写入
+    #[serde(rename = "verificationStatus", default)]
+    pub verification_status: Option<String>,
+    #[serde(rename = "verificationMessage", default)]
+    pub verification_message: Option<String>,
+    #[serde(rename = "verifiedAt", default)]
+    pub verified_at: Option<i64>,
 }
 
 impl ApiKey {
@@ -46,6 +64,76 @@ impl ApiKey {
             created_at: now,
             updated_at: now,
             last_used_at: None,
+            deleted_at: None,
+            expires_at: None,
+            rotate_every_days: None,
+            verification_status: None,
+            verification_message: None,
+            verified_at: None,
+        }
+    }
+}
+
+// 列表与搜索返回的Key摘要：不含 key_value，只带脱敏后的预览，取值需通过 reveal/copy 命令
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeySummary {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "keyPreview")]
+    pub key_preview: String,
+    pub platform: Option<String>,
+    pub description: Option<String>,
+    #[serde(rename = "groupId")]
+    pub group_id: Option<String>,
+    pub tags: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: i64,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: i64,
+    #[serde(rename = "lastUsedAt")]
+    pub last_used_at: Option<i64>,
+    #[serde(rename = "expiresAt")]
+    pub expires_at: Option<i64>,
+    #[serde(rename = "rotateEveryDays")]
+    pub rotate_every_days: Option<i64>,
+    #[serde(rename = "verificationStatus")]
+    pub verification_status: Option<String>,
+    #[serde(rename = "verificationMessage")]
+    pub verification_message: Option<String>,
+    #[serde(rename = "verifiedAt")]
+    pub verified_at: Option<i64>,
+}
+
+// 预览只保留首尾各 4 个字符，较短的Key完全隐藏
+pub(crate) fn key_preview(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() < 16 {
+        return "••••••••".to_string();
+    }
+    let prefix: String = chars[..4].iter().collect();
+    let suffix: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}…{}", prefix, suffix)
+}
+
+impl From<ApiKey> for ApiKeySummary {
+    fn from(key: ApiKey) -> Self {
+        Self {
+            key_preview: key_preview(&key.key_value),
+            id: key.id,
+            name: key.name,
+            platform: key.platform,
+            description: key.description,
+            group_id: key.group_id,
+            tags: key.tags,
+            created_at: key.created_at,
+            updated_at: key.updated_at,
+            last_used_at: key.last_used_at,
+            expires_at: key.expires_at,
+            rotate_every_days: key.rotate_every_days,
+            verification_status: key.verification_status,
+            verification_message: key.verification_message,
+            verified_at: key.verified_at,
         }
     }
 }
@@ -55,9 +143,10 @@ pub async fn insert_api_key(pool: &SqlitePool, api_key: &ApiKey) -> Result<(), D
     sqlx::query(
         r#"
         INSERT INTO api_keys (
-            id, name, key_value, platform, description, group_id, tags, created_at, updated_at, last_used_at
+            id, name, key_value, platform, description, group_id, tags, created_at, updated_at, last_used_at,
+            expires_at, rotate_every_days
         )
-        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
         "#
     )
     .bind(&api_key.id)
@@ -70,19 +159,48 @@ pub async fn insert_api_key(pool: &SqlitePool, api_key: &ApiKey) -> Result<(), D
     .bind(api_key.created_at)
     .bind(api_key.updated_at)
     .bind(api_key.last_used_at)
+    .bind(api_key.expires_at)
+    .bind(api_key.rotate_every_days)
     .execute(pool)
     .await
     .map(|_| ())
-    .map_err(|e| DatabaseError::SqlxError(e.to_string()))
+    .map_err(DatabaseError::from)
 }
 
-// 更新API Key
-pub async fn update_api_key(pool: &SqlitePool, api_key: &ApiKey) -> Result<(), DatabaseError> {
-    sqlx::query(
+// 更新API Key；expected_updated_at 为客户端读取到的 updated_at，记录已被修改时返回 Conflict。
+// 内容有变化时，修改前的记录会写入 api_key_versions
+pub async fn update_api_key(
+    pool: &SqlitePool,
+    api_key: &ApiKey,
+    expected_updated_at: Option<i64>,
+    reason: Option<&str>,
+) -> Result<(), DatabaseError> {
+    let mut tx = pool.begin().await?;
+
+    let current = sqlx::query_as::<_, ApiKey>("SELECT * FROM api_keys WHERE id = ?1 AND deleted_at IS NULL")
+        .bind(&api_key.id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(DatabaseError::KeyNotFound)?;
+    let conflict = || DatabaseError::Conflict(format!("API key {} was modified since it was loaded", api_key.id));
+    if expected_updated_at.is_some_and(|expected| expected != current.updated_at) {
+        return Err(conflict());
+    }
+
+    let changed = key_version::changed_fields(&current, api_key);
+    if !changed.is_empty() {
+        key_version::record_version(&mut tx, &current, &changed, reason).await?;
+    }
+
+    // updated_at 至少递增 1，保证同一秒内的两次修改也能被区分；
+    // 未提交的使用时间、过期时间与轮换周期保留原值，清除需通过 expiry::set_key_schedule
+    let result = sqlx::query(
         r#"
         UPDATE api_keys
-        SET name = ?1, key_value = ?2, platform = ?3, description = ?4, group_id = ?5, tags = ?6, updated_at = ?7, last_used_at = ?8
-        WHERE id = ?9
+        SET name = ?1, key_value = ?2, platform = ?3, description = ?4, group_id = ?5, tags = ?6,
+            updated_at = MAX(?7, updated_at + 1), last_used_at = COALESCE(?8, last_used_at),
+            expires_at = COALESCE(?11, expires_at), rotate_every_days = COALESCE(?12, rotate_every_days)
+        WHERE id = ?9 AND deleted_at IS NULL AND updated_at = ?10
         "#
     )
     .bind(&api_key.name)
@@ -94,25 +212,38 @@ pub async fn update_api_key(pool: &SqlitePool, api_key: &ApiKey) -> Result<(), D
     .bind(api_key.updated_at)
     .bind(api_key.last_used_at)
     .bind(&api_key.id)
-    .execute(pool)
+    .bind(current.updated_at)
+    .bind(api_key.expires_at)
+    .bind(api_key.rotate_every_days)
+    .execute(&mut *tx)
     .await
-    .map(|_| ())
-    .map_err(|e| DatabaseError::SqlxError(e.to_string()))
+    .map_err(|e| DatabaseError::SqlxError(e.to_string()))?;
+
+    if result.rows_affected() == 0 {
+        return Err(conflict());
+    }
+    tx.commit().await?;
+    Ok(())
 }
 
-// 删除API Key
+// 删除API Key：移入回收站，由 trash 模块负责恢复与彻底删除
 pub async fn delete_api_key(pool: &SqlitePool, id: &str) -> Result<(), DatabaseError> {
-    sqlx::query("DELETE FROM api_keys WHERE id = ?1")
+    let result = sqlx::query("UPDATE api_keys SET deleted_at = ?1 WHERE id = ?2 AND deleted_at IS NULL")
+        .bind(chrono::Utc::now().timestamp())
         .bind(id)
         .execute(pool)
         .await
-        .map(|_| ())
-        .map_err(|e| DatabaseError::SqlxError(e.to_string()))
+        .map_err(|e| DatabaseError::SqlxError(e.to_string()))?;
+
+    if result.rows_affected() == 0 {
+        return Err(DatabaseError::KeyNotFound);
+    }
+    Ok(())
 }
 
 // 获取所有API Keys
 pub async fn get_all_api_keys(pool: &SqlitePool) -> Result<Vec<ApiKey>, DatabaseError> {
-    let keys = sqlx::query_as::<_, ApiKey>("SELECT * FROM api_keys")
+    let keys = sqlx::query_as::<_, ApiKey>("SELECT * FROM api_keys WHERE deleted_at IS NULL")
         .fetch_all(pool)
         .await
         .map_err(|e| DatabaseError::SqlxError(e.to_string()))?;
@@ -121,9 +252,8 @@ pub async fn get_all_api_keys(pool: &SqlitePool) -> Result<Vec<ApiKey>, Database
 }
 
 // 根据ID获取API Key
-#[allow(dead_code)]
 pub async fn get_api_key_by_id(pool: &SqlitePool, id: &str) -> Result<Option<ApiKey>, DatabaseError> {
-    let key = sqlx::query_as::<_, ApiKey>("SELECT * FROM api_keys WHERE id = ?1")
+    let key = sqlx::query_as::<_, ApiKey>("SELECT * FROM api_keys WHERE id = ?1 AND deleted_at IS NULL")
         .bind(id)
         .fetch_optional(pool)
         .await
@@ -132,13 +262,37 @@ pub async fn get_api_key_by_id(pool: &SqlitePool, id: &str) -> Result<Option<Api
     Ok(key)
 }
 
+// 按ID或名称（忽略大小写）查找API Key，供命令行与代理使用；同名Key有多个时返回 Conflict
+pub async fn find_api_key(pool: &SqlitePool, id_or_name: &str) -> Result<ApiKey, DatabaseError> {
+    if let Some(key) = get_api_key_by_id(pool, id_or_name).await? {
+        return Ok(key);
+    }
+
+    let mut keys = sqlx::query_as::<_, ApiKey>(
+        "SELECT * FROM api_keys WHERE deleted_at IS NULL AND name = ?1 COLLATE NOCASE ORDER BY created_at"
+    )
+    .bind(id_or_name.trim())
+    .fetch_all(pool)
+    .await?;
+
+    match keys.len() {
+        0 => Err(DatabaseError::KeyNotFound),
+        1 => Ok(keys.remove(0)),
+        _ => Err(DatabaseError::Conflict(format!(
+            "Multiple API keys are named {}, use one of these ids instead: {}",
+            id_or_name,
+            keys.iter().map(|k| k.id.as_str()).collect::<Vec<_>>().join(", ")
+        ))),
+    }
+}
+
 // 搜索API Keys
 pub async fn search_api_keys(pool: &SqlitePool, keyword: &str) -> Result<Vec<ApiKey>, DatabaseError> {
     let search_term = format!("%{}%", keyword);
     let keys = sqlx::query_as::<_, ApiKey>(
         r#"
         SELECT * FROM api_keys
-        WHERE name LIKE ?1 OR platform LIKE ?2 OR description LIKE ?3
+        WHERE deleted_at IS NULL AND (name LIKE ?1 OR platform LIKE ?2 OR description LIKE ?3)
         "#
     )
     .bind(&search_term)
@@ -153,7 +307,7 @@ pub async fn search_api_keys(pool: &SqlitePool, keyword: &str) -> Result<Vec<Api
 
 // 获取所有唯一的platform值
 pub async fn get_all_platforms(pool: &SqlitePool) -> Result<Vec<String>, DatabaseError> {
-    let platforms = sqlx::query_scalar::<_, String>("SELECT DISTINCT platform FROM api_keys WHERE platform IS NOT NULL")
+    let platforms = sqlx::query_scalar::<_, String>("SELECT DISTINCT platform FROM api_keys WHERE platform IS NOT NULL AND deleted_at IS NULL")
         .fetch_all(pool)
         .await
         .map_err(|e| DatabaseError::SqlxError(e.to_string()))?;
@@ -173,7 +327,7 @@ pub async fn get_existing_key_values(
     // 构造动态 IN 子句
     let placeholders = vec!["?"; keys.len()].join(",");
     let query = format!(
-        "SELECT key_value FROM api_keys WHERE key_value IN ({})",
+        "SELECT key_value FROM api_keys WHERE deleted_at IS NULL AND key_value IN ({})",
         placeholders
     );
 
@@ -189,3 +343,108 @@ pub async fn get_existing_key_values(
 
     Ok(existing)
 }
+
+// 根据ID列表批量获取API Key
+pub async fn get_api_keys_by_ids(pool: &SqlitePool, ids: &[String]) -> Result<Vec<ApiKey>, DatabaseError> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let placeholders = vec!["?"; ids.len()].join(",");
+    let query = format!("SELECT * FROM api_keys WHERE deleted_at IS NULL AND id IN ({})", placeholders);
+
+    let mut q = sqlx::query_as::<_, ApiKey>(&query);
+    for id in ids {
+        q = q.bind(id);
+    }
+
+    q.fetch_all(pool)
+        .await
+        .map_err(|e| DatabaseError::SqlxError(e.to_string()))
+}
+
+// 记录在线验证结果；不修改 updated_at，避免与正在进行的编辑冲突
+pub async fn record_verification(
+    pool: &SqlitePool,
+    id: &str,
+    status: &str,
+    message: Option<&str>,
+    verified_at: i64,
+) -> Result<(), DatabaseError> {
+    let result = sqlx::query(
+        "UPDATE api_keys SET verification_status = ?1, verification_message = ?2, verified_at = ?3 WHERE id = ?4 AND deleted_at IS NULL"
+    )
+    .bind(status)
+    .bind(message)
+    .bind(verified_at)
+    .bind(id)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(DatabaseError::KeyNotFound);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::key_version::list_key_versions;
+    use crate::database::TestDatabase;
+
+    async fn insert_key(pool: &SqlitePool) -> ApiKey {
+        let key = ApiKey::new("openai".into(), "value-1".into(), None, None, None, None);
+        insert_api_key(pool, &key).await.unwrap();
+        key
+    }
+
+    // 不存在或已移入回收站的Key返回 KeyNotFound
+    #[tokio::test]
+    async fn test_update_missing_key() {
+        let db = TestDatabase::open().await;
+        let missing = ApiKey::new("missing".into(), "value".into(), None, None, None, None);
+        assert!(matches!(update_api_key(&db.pool, &missing, None, None).await, Err(DatabaseError::KeyNotFound)));
+
+        let key = insert_key(&db.pool).await;
+        delete_api_key(&db.pool, &key.id).await.unwrap();
+        assert!(matches!(update_api_key(&db.pool, &key, None, None).await, Err(DatabaseError::KeyNotFound)));
+    }
+
+    // expected_updated_at 与当前记录不一致时返回 Conflict，且不写入任何修改
+    #[tokio::test]
+    async fn test_update_stale_version_conflicts() {
+        let db = TestDatabase::open().await;
+        let key = insert_key(&db.pool).await;
+
+        let mut edited = key.clone();
+        edited.name = "renamed".into();
+        let result = update_api_key(&db.pool, &edited, Some(key.updated_at - 1), None).await;
+
+        assert!(matches!(result, Err(DatabaseError::Conflict(_))));
+        let stored = get_api_key_by_id(&db.pool, &key.id).await.unwrap().unwrap();
+        assert_eq!(stored.name, "openai");
+        assert!(list_key_versions(&db.pool, &key.id).await.unwrap().is_empty());
+    }
+
+    // 版本一致时更新成功，updated_at 递增并记录历史版本；第二次使用旧版本号提交时冲突
+    #[tokio::test]
+    async fn test_update_with_expected_version() {
+        let db = TestDatabase::open().await;
+        let key = insert_key(&db.pool).await;
+
+        let mut edited = key.clone();
+        edited.key_value = "value-2".into();
+        update_api_key(&db.pool, &edited, Some(key.updated_at), Some("rotated")).await.unwrap();
+
+        let stored = get_api_key_by_id(&db.pool, &key.id).await.unwrap().unwrap();
+        assert_eq!(stored.key_value, "value-2");
+        assert!(stored.updated_at > key.updated_at);
+        let versions = list_key_versions(&db.pool, &key.id).await.unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].key_value, "value-1");
+
+        let result = update_api_key(&db.pool, &edited, Some(key.updated_at), None).await;
+        assert!(matches!(result, Err(DatabaseError::Conflict(_))));
+    }
+}
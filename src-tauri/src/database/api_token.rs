@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use crate::database::api_key::ApiKey;
+use crate::database::error::DatabaseError;
+
+// 本地 HTTP API 的访问令牌。令牌明文只在创建时返回一次，数据库中仅保存哈希；
+// group_ids 与 tags 为逗号分隔的访问范围，均为空时可访问全部Key
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiToken {
+    pub id: String,
+    pub name: String,
+    #[serde(skip)]
+    pub token_hash: String,
+    #[serde(rename = "groupIds")]
+    pub group_ids: Option<String>,
+    pub tags: Option<String>,
+    // 是否允许读取Key的值，否则只能查询元数据
+    #[serde(rename = "allowSecrets")]
+    pub allow_secrets: bool,
+    #[serde(rename = "createdAt")]
+    pub created_at: i64,
+    #[serde(rename = "lastUsedAt")]
+    pub last_used_at: Option<i64>,
+}
+
+fn split_list(value: &Option<String>) -> impl Iterator<Item = &str> {
+    value
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+impl ApiToken {
+    // Key属于令牌的任一分组，或带有令牌的任一标签时可访问
+    pub fn allows(&self, key: &ApiKey) -> bool {
+        let mut groups = split_list(&self.group_ids).peekable();
+        let mut tags = split_list(&self.tags).peekable();
+        if groups.peek().is_none() && tags.peek().is_none() {
+            return true;
+        }
+        if groups.any(|g| key.group_id.as_deref() == Some(g)) {
+            return true;
+        }
+        let key_tags: Vec<&str> = split_list(&key.tags).collect();
+        tags.any(|t| key_tags.iter().any(|k| k.eq_ignore_ascii_case(t)))
+    }
+}
+
+// 保存新令牌
+pub async fn insert_api_token(pool: &SqlitePool, token: &ApiToken) -> Result<(), DatabaseError> {
+    sqlx::query(
+        r#"
+        INSERT INTO api_tokens (id, name, token_hash, group_ids, tags, allow_secrets, created_at, last_used_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+        "#
+    )
+    .bind(&token.id)
+    .bind(&token.name)
+    .bind(&token.token_hash)
+    .bind(&token.group_ids)
+    .bind(&token.tags)
+    .bind(token.allow_secrets)
+    .bind(token.created_at)
+    .bind(token.last_used_at)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// 获取所有令牌，最新创建的在前
+pub async fn list_api_tokens(pool: &SqlitePool) -> Result<Vec<ApiToken>, DatabaseError> {
+    let tokens = sqlx::query_as::<_, ApiToken>("SELECT * FROM api_tokens ORDER BY created_at DESC")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(tokens)
+}
+
+pub async fn get_api_token(pool: &SqlitePool, id: &str) -> Result<Option<ApiToken>, DatabaseError> {
+    let token = sqlx::query_as::<_, ApiToken>("SELECT * FROM api_tokens WHERE id = ?1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(token)
+}
+
+// 吊销令牌，返回是否存在
+pub async fn delete_api_token(pool: &SqlitePool, id: &str) -> Result<bool, DatabaseError> {
+    let result = sqlx::query("DELETE FROM api_tokens WHERE id = ?1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+// 记录令牌最近一次使用的时间
+pub async fn touch_api_token(pool: &SqlitePool, id: &str) -> Result<(), DatabaseError> {
+    sqlx::query("UPDATE api_tokens SET last_used_at = ?1 WHERE id = ?2")
+        .bind(chrono::Utc::now().timestamp())
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(group_ids: Option<&str>, tags: Option<&str>) -> ApiToken {
+        ApiToken {
+            id: "token".to_string(),
+            name: "test".to_string(),
+            token_hash: String::new(),
+            group_ids: group_ids.map(str::to_string),
+            tags: tags.map(str::to_string),
+            allow_secrets: false,
+            created_at: 0,
+            last_used_at: None,
+        }
+    }
+
+    fn key(group_id: Option<&str>, tags: Option<&str>) -> ApiKey {
+        ApiKey::new(
+            "key".to_string(),
+            "value".to_string(),
+            None,
+            None,
+            group_id.map(str::to_string),
+            tags.map(str::to_string),
+        )
+    }
+
+    #[test]
+    fn test_allows_without_scope() {
+        // 未限定分组与标签时可访问全部Key，空项视为未设置
+        assert!(token(None, None).allows(&key(None, None)));
+        assert!(token(Some(" , "), Some("")).allows(&key(Some("g1"), Some("prod"))));
+    }
+
+    #[test]
+    fn test_allows_by_group() {
+        let scoped = token(Some("g1, g2"), None);
+        assert!(scoped.allows(&key(Some("g2"), None)));
+        assert!(!scoped.allows(&key(Some("g3"), None)));
+        assert!(!scoped.allows(&key(None, Some("g1"))));
+    }
+
+    #[test]
+    fn test_allows_by_tag() {
+        // 标签不区分大小写，需要完整匹配
+        let scoped = token(None, Some("prod"));
+        assert!(scoped.allows(&key(None, Some("dev, PROD"))));
+        assert!(!scoped.allows(&key(None, Some("production"))));
+        assert!(!scoped.allows(&key(None, None)));
+
+        // 同时限定分组与标签时满足其一即可
+        let both = token(Some("g1"), Some("prod"));
+        assert!(both.allows(&key(Some("g1"), None)));
+        assert!(both.allows(&key(Some("g2"), Some("prod"))));
+        assert!(!both.allows(&key(Some("g2"), Some("dev"))));
+    }
+}
@@ -0,0 +1,580 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::pool::PoolConnection;
+use sqlx::{FromRow, Sqlite, SqliteConnection, SqlitePool};
+use crate::database::error::DatabaseError;
+
+// 桌面端自身操作的审计来源；代理与 HTTP API 各自使用 agent、http，命令行为 cli
+pub const APP_SOURCE: &str = "app";
+pub const CLI_SOURCE: &str = "cli";
+
+// settings 表中保存链尾（序号:哈希）的键，用于发现末尾记录被删除；只属于本机，不随快照或备份恢复改变
+pub const CHAIN_HEAD_SETTING: &str = "audit_chain_head";
+// 第一条记录的 prev_hash
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+// 审计日志：记录解锁、查看、复制、导出、导入、删除、修改主密码以及外部程序对Key的访问。
+// key_id 不设外键，Key被彻底删除后记录仍然保留。
+// 每条记录按 seq 顺序以 SHA-256 串联前一条的哈希，修改、删除或插入记录都会使校验失败
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub id: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: i64,
+    // 操作来源：app、cli、agent 或 http
+    pub source: String,
+    // 操作，如 unlock、reveal、copy、export、get
+    pub action: String,
+    // 请求中指定的Key名称或ID
+    pub target: Option<String>,
+    #[serde(rename = "keyId")]
+    pub key_id: Option<String>,
+    // allowed、denied 或 failed
+    pub outcome: String,
+    // 客户端信息、操作细节或失败原因
+    pub detail: Option<String>,
+    // 写入时分配，从 1 开始连续递增
+    pub seq: Option<i64>,
+    #[serde(rename = "prevHash")]
+    pub prev_hash: Option<String>,
+    #[serde(rename = "entryHash")]
+    pub entry_hash: Option<String>,
+}
+
+impl AuditEntry {
+    pub fn new(source: &str, action: &str, outcome: &str) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            created_at: chrono::Utc::now().timestamp(),
+            source: source.to_string(),
+            action: action.to_string(),
+            target: None,
+            key_id: None,
+            outcome: outcome.to_string(),
+            detail: None,
+            seq: None,
+            prev_hash: None,
+            entry_hash: None,
+        }
+    }
+
+    pub fn with_key(mut self, key_id: &str) -> Self {
+        self.key_id = Some(key_id.to_string());
+        self
+    }
+
+    pub fn with_target(mut self, target: &str) -> Self {
+        self.target = Some(target.to_string());
+        self
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    // 记录内容与前一条哈希的 SHA-256；以 JSON 数组编码，避免字段拼接产生歧义
+    fn compute_hash(&self, prev_hash: &str, seq: i64) -> String {
+        let payload = serde_json::json!([
+            prev_hash,
+            seq,
+            self.id,
+            self.created_at,
+            self.source,
+            self.action,
+            self.target,
+            self.key_id,
+            self.outcome,
+            self.detail,
+        ]);
+        format!("{:x}", Sha256::digest(payload.to_string().as_bytes()))
+    }
+}
+
+// 查询条件，均为可选
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditFilter {
+    pub source: Option<String>,
+    pub action: Option<String>,
+    #[serde(rename = "keyId")]
+    pub key_id: Option<String>,
+    pub outcome: Option<String>,
+    // 时间范围（秒级时间戳，包含两端）
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+// 哈希链校验结果；valid 为 false 时给出第一处异常
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditChainReport {
+    pub valid: bool,
+    #[serde(rename = "entriesChecked")]
+    pub entries_checked: i64,
+    #[serde(rename = "brokenSeq")]
+    pub broken_seq: Option<i64>,
+    #[serde(rename = "brokenEntryId")]
+    pub broken_entry_id: Option<String>,
+    pub reason: Option<String>,
+}
+
+impl AuditChainReport {
+    fn broken(entries_checked: i64, seq: Option<i64>, entry_id: Option<&str>, reason: String) -> Self {
+        Self {
+            valid: false,
+            entries_checked,
+            broken_seq: seq,
+            broken_entry_id: entry_id.map(str::to_string),
+            reason: Some(reason),
+        }
+    }
+}
+
+fn parse_chain_head(value: &str) -> Option<(i64, String)> {
+    let (seq, hash) = value.split_once(':')?;
+    Some((seq.parse().ok()?, hash.to_string()))
+}
+
+// 当前链尾；优先使用 settings 中的记录，使删除末尾记录后新记录无法接续。
+// 保存的链尾落后于已有记录（settings 被覆盖或回退）时报错，不在错误的位置继续写入
+async fn chain_head(conn: &mut SqliteConnection) -> Result<(i64, String), DatabaseError> {
+    let saved: Option<String> = sqlx::query_scalar("SELECT value FROM settings WHERE key = ?1")
+        .bind(CHAIN_HEAD_SETTING)
+        .fetch_optional(&mut *conn)
+        .await?;
+    let last: Option<(i64, String)> = sqlx::query_as(
+        "SELECT seq, entry_hash FROM audit_log WHERE seq IS NOT NULL ORDER BY seq DESC LIMIT 1"
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    match (saved.as_deref().map(parse_chain_head), last) {
+        (Some(None), _) => Err(DatabaseError::Corrupted(format!(
+            "Audit chain head is unreadable: {}",
+            saved.unwrap_or_default()
+        ))),
+        (Some(Some((seq, hash))), Some((last_seq, last_hash)))
+            if seq < last_seq || (seq == last_seq && hash != last_hash) =>
+        {
+            Err(DatabaseError::Corrupted(format!(
+                "Audit chain head (entry {}) does not match the last audit entry {}",
+                seq, last_seq
+            )))
+        }
+        (Some(Some(head)), _) => Ok(head),
+        (None, last) => Ok(last.unwrap_or((0, GENESIS_HASH.to_string()))),
+    }
+}
+
+async fn save_chain_head(conn: &mut SqliteConnection, seq: i64, hash: &str) -> Result<(), DatabaseError> {
+    sqlx::query(
+        r#"
+        INSERT INTO settings (key, value) VALUES (?1, ?2)
+        ON CONFLICT(key) DO UPDATE SET value = excluded.value
+        "#
+    )
+    .bind(CHAIN_HEAD_SETTING)
+    .bind(format!("{}:{}", seq, hash))
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+async fn append_entry(conn: &mut SqliteConnection, entry: &AuditEntry) -> Result<(), DatabaseError> {
+    let (last_seq, prev_hash) = chain_head(conn).await?;
+    let seq = last_seq + 1;
+    let hash = entry.compute_hash(&prev_hash, seq);
+    sqlx::query(
+        r#"
+        INSERT INTO audit_log (id, created_at, source, action, target, key_id, outcome, detail, seq, prev_hash, entry_hash)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+        "#
+    )
+    .bind(&entry.id)
+    .bind(entry.created_at)
+    .bind(&entry.source)
+    .bind(&entry.action)
+    .bind(&entry.target)
+    .bind(&entry.key_id)
+    .bind(&entry.outcome)
+    .bind(&entry.detail)
+    .bind(seq)
+    .bind(&prev_hash)
+    .bind(&hash)
+    .execute(&mut *conn)
+    .await?;
+    save_chain_head(conn, seq, &hash).await
+}
+
+// 以 IMMEDIATE 开始的写事务：其他连接（包括命令行进程）的写入会等待，链不会分叉。
+// 未提交就被丢弃时（出错或调用方的 future 被取消）不把连接放回连接池，关闭连接由 SQLite 回滚
+struct ImmediateTransaction {
+    conn: Option<PoolConnection<Sqlite>>,
+}
+
+impl ImmediateTransaction {
+    async fn begin(pool: &SqlitePool) -> Result<Self, sqlx::Error> {
+        let mut tx = Self {
+            conn: Some(pool.acquire().await?),
+        };
+        sqlx::query("BEGIN IMMEDIATE").execute(tx.conn()).await?;
+        Ok(tx)
+    }
+
+    fn conn(&mut self) -> &mut SqliteConnection {
+        self.conn.as_mut().expect("audit transaction is already finished")
+    }
+
+    async fn commit(mut self) -> Result<(), sqlx::Error> {
+        sqlx::query("COMMIT").execute(self.conn()).await?;
+        // 提交成功后连接可以正常放回连接池
+        self.conn.take();
+        Ok(())
+    }
+}
+
+impl Drop for ImmediateTransaction {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            drop(conn.detach());
+        }
+    }
+}
+
+// 写入一条审计记录，接在链尾
+pub async fn record_audit(pool: &SqlitePool, entry: &AuditEntry) -> Result<(), DatabaseError> {
+    let mut tx = ImmediateTransaction::begin(pool).await?;
+    append_entry(tx.conn(), entry).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+// 写入审计记录；失败时只打印日志，不影响被审计的操作
+pub async fn audit(pool: &SqlitePool, entry: AuditEntry) {
+    if let Err(e) = record_audit(pool, &entry).await {
+        eprintln!("Failed to write audit log ({} {}): {}", entry.source, entry.action, e);
+    }
+}
+
+async fn chain_entries(conn: &mut SqliteConnection, entries: &[AuditEntry]) -> Result<(), DatabaseError> {
+    let (mut seq, mut prev_hash) = chain_head(conn).await?;
+    for entry in entries {
+        seq += 1;
+        let hash = entry.compute_hash(&prev_hash, seq);
+        sqlx::query("UPDATE audit_log SET seq = ?1, prev_hash = ?2, entry_hash = ?3 WHERE id = ?4")
+            .bind(seq)
+            .bind(&prev_hash)
+            .bind(&hash)
+            .bind(&entry.id)
+            .execute(&mut *conn)
+            .await?;
+        prev_hash = hash;
+    }
+    save_chain_head(conn, seq, &prev_hash).await
+}
+
+// 为旧版本中没有哈希的记录按时间顺序补上序号与哈希，接在当前链尾之后
+pub(crate) async fn backfill_audit_chain(pool: &SqlitePool) -> Result<(), DatabaseError> {
+    let legacy = sqlx::query_as::<_, AuditEntry>(
+        "SELECT * FROM audit_log WHERE entry_hash IS NULL ORDER BY created_at, rowid"
+    )
+    .fetch_all(pool)
+    .await?;
+    if legacy.is_empty() {
+        return Ok(());
+    }
+
+    let mut tx = ImmediateTransaction::begin(pool).await?;
+    chain_entries(tx.conn(), &legacy).await?;
+    tx.commit().await?;
+    eprintln!("Added {} existing audit log entries to the hash chain", legacy.len());
+    Ok(())
+}
+
+// 按条件查询审计记录，最新的在前
+pub async fn list_audit_events(pool: &SqlitePool, filter: &AuditFilter) -> Result<Vec<AuditEntry>, DatabaseError> {
+    let entries = sqlx::query_as::<_, AuditEntry>(
+        r#"
+        SELECT * FROM audit_log
+        WHERE (?1 IS NULL OR source = ?1)
+          AND (?2 IS NULL OR action = ?2)
+          AND (?3 IS NULL OR key_id = ?3)
+          AND (?4 IS NULL OR outcome = ?4)
+          AND (?5 IS NULL OR created_at >= ?5)
+          AND (?6 IS NULL OR created_at <= ?6)
+        ORDER BY seq DESC, created_at DESC
+        LIMIT ?7 OFFSET ?8
+        "#
+    )
+    .bind(&filter.source)
+    .bind(&filter.action)
+    .bind(&filter.key_id)
+    .bind(&filter.outcome)
+    .bind(filter.since)
+    .bind(filter.until)
+    .bind(filter.limit.unwrap_or(-1))
+    .bind(filter.offset.unwrap_or(0))
+    .fetch_all(pool)
+    .await?;
+
+    Ok(entries)
+}
+
+// 从第一条开始重新计算哈希链：检查序号连续、prev_hash 与前一条一致、记录内容未被修改，
+// 最后与 settings 中保存的链尾比对
+pub async fn verify_audit_chain(pool: &SqlitePool) -> Result<AuditChainReport, DatabaseError> {
+    let entries = sqlx::query_as::<_, AuditEntry>("SELECT * FROM audit_log ORDER BY seq IS NULL, seq, rowid")
+        .fetch_all(pool)
+        .await?;
+
+    let mut prev_hash = GENESIS_HASH.to_string();
+    let mut checked = 0;
+    for entry in &entries {
+        let expected_seq = checked + 1;
+        let Some(seq) = entry.seq else {
+            return Ok(AuditChainReport::broken(
+                checked,
+                None,
+                Some(&entry.id),
+                "Entry is not part of the hash chain".to_string(),
+            ));
+        };
+        if seq != expected_seq {
+            return Ok(AuditChainReport::broken(
+                checked,
+                Some(seq),
+                Some(&entry.id),
+                format!("Expected entry {} but found {}, entries were removed or reordered", expected_seq, seq),
+            ));
+        }
+        if entry.prev_hash.as_deref() != Some(prev_hash.as_str()) {
+            return Ok(AuditChainReport::broken(
+                checked,
+                Some(seq),
+                Some(&entry.id),
+                "Previous hash does not match the preceding entry".to_string(),
+            ));
+        }
+        let hash = entry.compute_hash(&prev_hash, seq);
+        if entry.entry_hash.as_deref() != Some(hash.as_str()) {
+            return Ok(AuditChainReport::broken(
+                checked,
+                Some(seq),
+                Some(&entry.id),
+                "Entry content does not match its hash".to_string(),
+            ));
+        }
+        prev_hash = hash;
+        checked += 1;
+    }
+
+    let head: Option<String> = sqlx::query_scalar("SELECT value FROM settings WHERE key = ?1")
+        .bind(CHAIN_HEAD_SETTING)
+        .fetch_optional(pool)
+        .await?;
+    if let Some(head) = head {
+        match parse_chain_head(&head) {
+            Some((seq, hash)) if seq == checked && hash == prev_hash => {}
+            Some((seq, _)) if seq > checked => {
+                return Ok(AuditChainReport::broken(
+                    checked,
+                    Some(checked + 1),
+                    None,
+                    format!("Entries {} to {} were removed", checked + 1, seq),
+                ));
+            }
+            _ => {
+                return Ok(AuditChainReport::broken(
+                    checked,
+                    None,
+                    None,
+                    "Last entry does not match the recorded chain head".to_string(),
+                ));
+            }
+        }
+    } else if checked > 0 {
+        return Ok(AuditChainReport::broken(
+            checked,
+            None,
+            None,
+            "The recorded chain head is missing".to_string(),
+        ));
+    }
+
+    Ok(AuditChainReport {
+        valid: true,
+        entries_checked: checked,
+        broken_seq: None,
+        broken_entry_id: None,
+        reason: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::TestDatabase;
+
+    async fn record_entries(pool: &SqlitePool, count: usize) {
+        for i in 0..count {
+            let entry = AuditEntry::new(APP_SOURCE, "reveal", "allowed")
+                .with_key("key-1")
+                .with_detail(format!("entry {}", i + 1));
+            record_audit(pool, &entry).await.unwrap();
+        }
+    }
+
+    #[test]
+    fn test_compute_hash() {
+        // 相同内容得到相同哈希，前一条哈希、序号或内容不同时哈希不同
+        let entry = AuditEntry::new(APP_SOURCE, "copy", "allowed").with_key("key-1");
+        let hash = entry.compute_hash(GENESIS_HASH, 1);
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, entry.compute_hash(GENESIS_HASH, 1));
+        assert_ne!(hash, entry.compute_hash(GENESIS_HASH, 2));
+        assert_ne!(hash, entry.compute_hash(&hash, 1));
+        assert_ne!(hash, entry.clone().with_detail("changed").compute_hash(GENESIS_HASH, 1));
+    }
+
+    #[test]
+    fn test_compute_hash_field_boundaries() {
+        // 字段之间的边界参与哈希，移动字符不会得到相同结果
+        let mut first = AuditEntry::new("ab", "c", "allowed");
+        let mut second = AuditEntry::new("a", "bc", "allowed");
+        second.id = first.id.clone();
+        second.created_at = first.created_at;
+        assert_ne!(first.compute_hash(GENESIS_HASH, 1), second.compute_hash(GENESIS_HASH, 1));
+
+        // target 为空与空字符串也要区分
+        first.target = Some(String::new());
+        let mut without_target = first.clone();
+        without_target.target = None;
+        assert_ne!(first.compute_hash(GENESIS_HASH, 1), without_target.compute_hash(GENESIS_HASH, 1));
+    }
+
+    #[tokio::test]
+    async fn test_verify_valid_chain() {
+        let db = TestDatabase::open().await;
+        record_entries(&db.pool, 3).await;
+
+        let report = verify_audit_chain(&db.pool).await.unwrap();
+        assert!(report.valid, "{:?}", report.reason);
+        assert_eq!(report.entries_checked, 3);
+    }
+
+    #[tokio::test]
+    async fn test_verify_tampered_entry() {
+        // 修改记录内容后，该记录的哈希不再匹配
+        let db = TestDatabase::open().await;
+        record_entries(&db.pool, 3).await;
+        sqlx::query("UPDATE audit_log SET outcome = 'denied' WHERE seq = 2")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+
+        let report = verify_audit_chain(&db.pool).await.unwrap();
+        assert!(!report.valid);
+        assert_eq!(report.entries_checked, 1);
+        assert_eq!(report.broken_seq, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_verify_deleted_entry() {
+        // 删除中间的记录后序号不再连续
+        let db = TestDatabase::open().await;
+        record_entries(&db.pool, 3).await;
+        sqlx::query("DELETE FROM audit_log WHERE seq = 2")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+
+        let report = verify_audit_chain(&db.pool).await.unwrap();
+        assert!(!report.valid);
+        assert_eq!(report.broken_seq, Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_verify_deleted_last_entry() {
+        // 删除末尾的记录后与保存的链尾不一致
+        let db = TestDatabase::open().await;
+        record_entries(&db.pool, 3).await;
+        sqlx::query("DELETE FROM audit_log WHERE seq = 3")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+
+        let report = verify_audit_chain(&db.pool).await.unwrap();
+        assert!(!report.valid);
+        assert_eq!(report.entries_checked, 2);
+        assert_eq!(report.broken_seq, Some(3));
+
+        // 链尾落后于保存的记录时新记录接在原链尾之后，不会覆盖被删除的序号
+        record_entries(&db.pool, 1).await;
+        let seq: i64 = sqlx::query_scalar("SELECT MAX(seq) FROM audit_log")
+            .fetch_one(&db.pool)
+            .await
+            .unwrap();
+        assert_eq!(seq, 4);
+    }
+
+    #[tokio::test]
+    async fn test_verify_inserted_entry() {
+        // 绕过 record_audit 直接插入、且哈希计算正确的记录，与保存的链尾不一致
+        let db = TestDatabase::open().await;
+        record_entries(&db.pool, 2).await;
+        let last_hash: String = sqlx::query_scalar("SELECT entry_hash FROM audit_log WHERE seq = 2")
+            .fetch_one(&db.pool)
+            .await
+            .unwrap();
+        let forged = AuditEntry::new(APP_SOURCE, "export", "allowed");
+        sqlx::query(
+            r#"
+            INSERT INTO audit_log (id, created_at, source, action, outcome, seq, prev_hash, entry_hash)
+            VALUES (?1, ?2, ?3, ?4, ?5, 3, ?6, ?7)
+            "#
+        )
+        .bind(&forged.id)
+        .bind(forged.created_at)
+        .bind(&forged.source)
+        .bind(&forged.action)
+        .bind(&forged.outcome)
+        .bind(&last_hash)
+        .bind(forged.compute_hash(&last_hash, 3))
+        .execute(&db.pool)
+        .await
+        .unwrap();
+
+        let report = verify_audit_chain(&db.pool).await.unwrap();
+        assert!(!report.valid);
+        assert_eq!(report.entries_checked, 3);
+
+        // 没有序号的记录不在链中
+        sqlx::query("INSERT INTO audit_log (id, created_at, source, action, outcome) VALUES ('x', 0, 'app', 'copy', 'allowed')")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        let report = verify_audit_chain(&db.pool).await.unwrap();
+        assert!(!report.valid);
+        assert_eq!(report.broken_entry_id.as_deref(), Some("x"));
+    }
+
+    #[tokio::test]
+    async fn test_append_refuses_stale_chain_head() {
+        // settings 中的链尾被旧值覆盖时拒绝写入，而不是复用已有的序号
+        let db = TestDatabase::open().await;
+        record_entries(&db.pool, 2).await;
+        sqlx::query("UPDATE settings SET value = ?1 WHERE key = ?2")
+            .bind(format!("1:{}", GENESIS_HASH))
+            .bind(CHAIN_HEAD_SETTING)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+
+        let entry = AuditEntry::new(APP_SOURCE, "copy", "allowed");
+        assert!(matches!(record_audit(&db.pool, &entry).await, Err(DatabaseError::Corrupted(_))));
+    }
+}
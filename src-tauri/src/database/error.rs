@@ -8,10 +8,25 @@ pub enum DatabaseError {
     KeyNotFound,
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+    #[error("Duplicate record: {0}")]
+    Duplicate(String),
+    #[error("Conflict: {0}")]
+    Conflict(String),
+    #[error("Database is corrupted: {0}")]
+    Corrupted(String),
+    #[error("IO error: {0}")]
+    Io(String),
+    #[error("Database is not ready")]
+    NotReady,
 }
 
 impl From<sqlx::Error> for DatabaseError {
     fn from(error: sqlx::Error) -> Self {
-        DatabaseError::SqlxError(error.to_string())
+        match &error {
+            sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+                DatabaseError::Duplicate(db_error.message().to_string())
+            }
+            _ => DatabaseError::SqlxError(error.to_string()),
+        }
     }
 }
\ No newline at end of file
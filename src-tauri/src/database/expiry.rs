@@ -0,0 +1,214 @@
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use crate::database::error::DatabaseError;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+// 提醒类型
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ExpiryKind {
+    // 已过期
+    Expired,
+    // 即将过期
+    ExpiringSoon,
+    // 已超过轮换周期
+    RotationOverdue,
+    // 即将到达轮换周期
+    RotationDue,
+}
+
+// 即将过期或需要轮换的Key，不包含 key_value，可直接随事件发送给前端
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpiringKey {
+    #[serde(rename = "keyId")]
+    pub key_id: String,
+    pub name: String,
+    pub platform: Option<String>,
+    pub kind: ExpiryKind,
+    // 过期时间或应轮换的时间
+    #[serde(rename = "dueAt")]
+    pub due_at: i64,
+    // 距到期的天数，已到期时为负数
+    #[serde(rename = "daysLeft")]
+    pub days_left: i64,
+}
+
+type ExpiryRow = (String, String, Option<String>, Option<i64>, Option<i64>, i64);
+
+// 查询 within_days 天内过期或需要轮换的Key（含已过期/已逾期），按到期时间排序。
+// 上次轮换时间取最近一次修改 key_value 的历史版本时间，没有则取创建时间
+pub async fn expiring_keys(pool: &SqlitePool, within_days: i64) -> Result<Vec<ExpiringKey>, DatabaseError> {
+    let rows = sqlx::query_as::<_, ExpiryRow>(
+        r#"
+        SELECT k.id, k.name, k.platform, k.expires_at, k.rotate_every_days,
+            COALESCE(
+                (SELECT MAX(v.replaced_at) FROM api_key_versions v
+                 WHERE v.key_id = k.id AND ',' || v.changed_fields || ',' LIKE '%,keyValue,%'),
+                k.created_at,
+                0
+            ) AS rotated_at
+        FROM api_keys k
+        WHERE k.deleted_at IS NULL AND (k.expires_at IS NOT NULL OR k.rotate_every_days IS NOT NULL)
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let now = chrono::Utc::now().timestamp();
+    // 导入或旧数据中可能有极大的天数，按饱和运算处理，不会溢出
+    let horizon = now.saturating_add(within_days.max(0).saturating_mul(SECONDS_PER_DAY));
+    let mut result = Vec::new();
+    for (key_id, name, platform, expires_at, rotate_every_days, rotated_at) in rows {
+        let mut push = |kind: ExpiryKind, due_at: i64| {
+            result.push(ExpiringKey {
+                key_id: key_id.clone(),
+                name: name.clone(),
+                platform: platform.clone(),
+                kind,
+                due_at,
+                days_left: due_at.saturating_sub(now).div_euclid(SECONDS_PER_DAY),
+            });
+        };
+
+        if let Some(expires_at) = expires_at.filter(|t| *t <= horizon) {
+            let kind = if expires_at <= now { ExpiryKind::Expired } else { ExpiryKind::ExpiringSoon };
+            push(kind, expires_at);
+        }
+        if let Some(days) = rotate_every_days.filter(|d| *d > 0) {
+            let due_at = rotated_at.saturating_add(days.saturating_mul(SECONDS_PER_DAY));
+            if due_at <= horizon {
+                let kind = if due_at <= now { ExpiryKind::RotationOverdue } else { ExpiryKind::RotationDue };
+                push(kind, due_at);
+            }
+        }
+    }
+
+    result.sort_by_key(|k| k.due_at);
+    Ok(result)
+}
+
+// 设置或清除Key的过期时间与轮换周期，None 表示不设置
+pub async fn set_key_schedule(
+    pool: &SqlitePool,
+    key_id: &str,
+    expires_at: Option<i64>,
+    rotate_every_days: Option<i64>,
+) -> Result<(), DatabaseError> {
+    let result = sqlx::query(
+        r#"
+        UPDATE api_keys SET expires_at = ?1, rotate_every_days = ?2, updated_at = MAX(?3, updated_at + 1)
+        WHERE id = ?4 AND deleted_at IS NULL
+        "#
+    )
+    .bind(expires_at)
+    .bind(rotate_every_days)
+    .bind(chrono::Utc::now().timestamp())
+    .bind(key_id)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(DatabaseError::KeyNotFound);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::api_key::{delete_api_key, get_api_key_by_id, insert_api_key, update_api_key, ApiKey};
+    use crate::database::TestDatabase;
+
+    async fn insert_key(pool: &SqlitePool, name: &str, expires_at: Option<i64>, rotate_every_days: Option<i64>) -> ApiKey {
+        let key = ApiKey {
+            expires_at,
+            rotate_every_days,
+            ..ApiKey::new(name.into(), format!("{}-value", name), None, None, None, None)
+        };
+        insert_api_key(pool, &key).await.unwrap();
+        key
+    }
+
+    // 按到期时间排序返回过期、即将过期与需要轮换的Key，超出提醒范围与已删除的Key不返回
+    #[tokio::test]
+    async fn test_expiring_keys() {
+        let db = TestDatabase::open().await;
+        let now = chrono::Utc::now().timestamp();
+        insert_key(&db.pool, "expired", Some(now - SECONDS_PER_DAY), None).await;
+        insert_key(&db.pool, "soon", Some(now + 3 * SECONDS_PER_DAY + 60), None).await;
+        insert_key(&db.pool, "later", Some(now + 30 * SECONDS_PER_DAY), None).await;
+        insert_key(&db.pool, "rotate", None, Some(5)).await;
+        insert_key(&db.pool, "plain", None, None).await;
+        let trashed = insert_key(&db.pool, "trashed", Some(now - SECONDS_PER_DAY), None).await;
+        delete_api_key(&db.pool, &trashed.id).await.unwrap();
+
+        let keys = expiring_keys(&db.pool, 7).await.unwrap();
+        let found: Vec<(&str, ExpiryKind)> = keys.iter().map(|k| (k.name.as_str(), k.kind)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("expired", ExpiryKind::Expired),
+                ("soon", ExpiryKind::ExpiringSoon),
+                ("rotate", ExpiryKind::RotationDue),
+            ]
+        );
+        assert_eq!(keys[0].days_left, -1);
+        assert_eq!(keys[1].days_left, 3);
+
+        // 天数极大时不会溢出
+        assert_eq!(expiring_keys(&db.pool, i64::MAX).await.unwrap().len(), 4);
+    }
+
+    // 轮换时间从最近一次修改 key_value 算起，只改名称不算轮换
+    #[tokio::test]
+    async fn test_rotation_counts_from_last_value_change() {
+        let db = TestDatabase::open().await;
+        let key = insert_key(&db.pool, "main", None, Some(1)).await;
+        sqlx::query("UPDATE api_keys SET created_at = ?1 WHERE id = ?2")
+            .bind(chrono::Utc::now().timestamp() - 2 * SECONDS_PER_DAY)
+            .bind(&key.id)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        let stored = get_api_key_by_id(&db.pool, &key.id).await.unwrap().unwrap();
+
+        let mut renamed = stored.clone();
+        renamed.name = "renamed".into();
+        update_api_key(&db.pool, &renamed, None, None).await.unwrap();
+        let keys = expiring_keys(&db.pool, 0).await.unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].kind, ExpiryKind::RotationOverdue);
+
+        let mut rotated = renamed.clone();
+        rotated.key_value = "new-value".into();
+        update_api_key(&db.pool, &rotated, None, None).await.unwrap();
+        assert!(expiring_keys(&db.pool, 0).await.unwrap().is_empty());
+    }
+
+    // 编辑Key时未提交的计划保留原值，清除需通过 set_key_schedule
+    #[tokio::test]
+    async fn test_schedule_kept_on_edit_and_cleared_by_setter() {
+        let db = TestDatabase::open().await;
+        let now = chrono::Utc::now().timestamp();
+        let key = insert_key(&db.pool, "main", Some(now + SECONDS_PER_DAY), Some(30)).await;
+
+        let mut edited = key.clone();
+        edited.expires_at = None;
+        edited.rotate_every_days = None;
+        edited.description = Some("edited".into());
+        update_api_key(&db.pool, &edited, None, None).await.unwrap();
+        let stored = get_api_key_by_id(&db.pool, &key.id).await.unwrap().unwrap();
+        assert_eq!(stored.expires_at, key.expires_at);
+        assert_eq!(stored.rotate_every_days, Some(30));
+
+        set_key_schedule(&db.pool, &key.id, None, Some(90)).await.unwrap();
+        let stored = get_api_key_by_id(&db.pool, &key.id).await.unwrap().unwrap();
+        assert_eq!(stored.expires_at, None);
+        assert_eq!(stored.rotate_every_days, Some(90));
+        assert!(stored.updated_at > key.updated_at);
+
+        assert!(matches!(set_key_schedule(&db.pool, "missing", None, None).await, Err(DatabaseError::KeyNotFound)));
+    }
+}
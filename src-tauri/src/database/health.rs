@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use crate::database::error::DatabaseError;
+
+// 通过 database-status 事件通知前端的数据库状态
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DatabaseStatus {
+    Initializing,
+    Ready,
+    Corrupted,
+    Failed,
+    // 保险库已关闭，尚未打开其他保险库
+    Closed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseStatusEvent {
+    pub status: DatabaseStatus,
+    pub message: Option<String>,
+    // 可用于恢复的最新备份文件名
+    pub latest_backup: Option<String>,
+}
+
+impl DatabaseStatusEvent {
+    pub fn new(status: DatabaseStatus, message: Option<String>) -> Self {
+        Self {
+            status,
+            message,
+            latest_backup: None,
+        }
+    }
+}
+
+// SQLITE_CORRUPT(11) 与 SQLITE_NOTADB(26) 表示数据库文件损坏
+fn is_corruption_error(error: &sqlx::Error) -> bool {
+    match error {
+        sqlx::Error::Database(db_error) => db_error
+            .code()
+            .and_then(|code| code.parse::<i32>().ok())
+            .map(|code| matches!(code & 0xff, 11 | 26))
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+// 区分损坏与其他错误，便于启动时决定是否提供修复选项
+pub fn classify_error(context: &str, error: sqlx::Error) -> DatabaseError {
+    if is_corruption_error(&error) {
+        DatabaseError::Corrupted(format!("{}: {}", context, error))
+    } else {
+        DatabaseError::SqlxError(format!("{}: {}", context, error))
+    }
+}
+
+// 执行完整性检查：quick 为 true 时使用更快的 quick_check
+async fn run_check(pool: &SqlitePool, quick: bool) -> Result<(), DatabaseError> {
+    let pragma = if quick { "PRAGMA quick_check" } else { "PRAGMA integrity_check" };
+    let results: Vec<String> = sqlx::query_scalar(pragma)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| classify_error("Integrity check failed", e))?;
+
+    if results.len() == 1 && results[0] == "ok" {
+        Ok(())
+    } else {
+        Err(DatabaseError::Corrupted(results.join("; ")))
+    }
+}
+
+// 启动时使用的快速检查
+pub async fn quick_check(pool: &SqlitePool) -> Result<(), DatabaseError> {
+    run_check(pool, true).await
+}
+
+// 完整检查，耗时较长
+pub async fn integrity_check(pool: &SqlitePool) -> Result<(), DatabaseError> {
+    run_check(pool, false).await
+}
@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqliteConnection, SqlitePool};
+use crate::database::api_key::{key_preview, ApiKey};
+use crate::database::error::DatabaseError;
+
+// API Key 的历史版本：保存被修改前的完整记录，key_value 与 api_keys 表中的存储形式一致
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyVersion {
+    pub id: String,
+    #[serde(rename = "keyId")]
+    pub key_id: String,
+    pub name: String,
+    #[serde(rename = "keyValue")]
+    pub key_value: String,
+    pub platform: Option<String>,
+    pub description: Option<String>,
+    #[serde(rename = "groupId")]
+    pub group_id: Option<String>,
+    pub tags: Option<String>,
+    // 该版本开始生效的时间（被替换前记录的 updated_at）
+    #[serde(rename = "validFrom")]
+    pub valid_from: Option<i64>,
+    // 该版本被替换的时间
+    #[serde(rename = "replacedAt")]
+    pub replaced_at: i64,
+    pub reason: Option<String>,
+    // 本次修改涉及的字段，逗号分隔
+    #[serde(rename = "changedFields")]
+    pub changed_fields: Option<String>,
+}
+
+// 返回给前端的历史版本：不含 key_value，查看旧值需通过 reveal_key_version
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyVersionSummary {
+    pub id: String,
+    #[serde(rename = "keyId")]
+    pub key_id: String,
+    pub name: String,
+    #[serde(rename = "keyPreview")]
+    pub key_preview: String,
+    pub platform: Option<String>,
+    pub description: Option<String>,
+    #[serde(rename = "groupId")]
+    pub group_id: Option<String>,
+    pub tags: Option<String>,
+    #[serde(rename = "validFrom")]
+    pub valid_from: Option<i64>,
+    #[serde(rename = "replacedAt")]
+    pub replaced_at: i64,
+    pub reason: Option<String>,
+    #[serde(rename = "changedFields")]
+    pub changed_fields: Option<String>,
+}
+
+impl From<KeyVersion> for KeyVersionSummary {
+    fn from(version: KeyVersion) -> Self {
+        Self {
+            key_preview: key_preview(&version.key_value),
+            id: version.id,
+            key_id: version.key_id,
+            name: version.name,
+            platform: version.platform,
+            description: version.description,
+            group_id: version.group_id,
+            tags: version.tags,
+            valid_from: version.valid_from,
+            replaced_at: version.replaced_at,
+            reason: version.reason,
+            changed_fields: version.changed_fields,
+        }
+    }
+}
+
+// 比较修改前后的记录，返回发生变化的字段名（camelCase）
+pub fn changed_fields(previous: &ApiKey, next: &ApiKey) -> Vec<&'static str> {
+    let mut changed = Vec::new();
+    if previous.name != next.name {
+        changed.push("name");
+    }
+    if previous.key_value != next.key_value {
+        changed.push("keyValue");
+    }
+    if previous.platform != next.platform {
+        changed.push("platform");
+    }
+    if previous.description != next.description {
+        changed.push("description");
+    }
+    if previous.group_id != next.group_id {
+        changed.push("groupId");
+    }
+    if previous.tags != next.tags {
+        changed.push("tags");
+    }
+    changed
+}
+
+// 在修改前写入旧版本，需与修改处于同一事务
+pub async fn record_version(
+    conn: &mut SqliteConnection,
+    previous: &ApiKey,
+    changed: &[&str],
+    reason: Option<&str>,
+) -> Result<(), DatabaseError> {
+    sqlx::query(
+        r#"
+        INSERT INTO api_key_versions (
+            id, key_id, name, key_value, platform, description, group_id, tags,
+            valid_from, replaced_at, reason, changed_fields
+        )
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+        "#
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(&previous.id)
+    .bind(&previous.name)
+    .bind(&previous.key_value)
+    .bind(&previous.platform)
+    .bind(&previous.description)
+    .bind(&previous.group_id)
+    .bind(&previous.tags)
+    .bind(previous.updated_at)
+    .bind(chrono::Utc::now().timestamp())
+    .bind(reason)
+    .bind(changed.join(","))
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+// 获取某个Key的全部历史版本，最近的在前
+pub async fn list_key_versions(pool: &SqlitePool, key_id: &str) -> Result<Vec<KeyVersion>, DatabaseError> {
+    let versions = sqlx::query_as::<_, KeyVersion>(
+        "SELECT * FROM api_key_versions WHERE key_id = ?1 ORDER BY replaced_at DESC, rowid DESC"
+    )
+    .bind(key_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(versions)
+}
+
+// 根据ID获取历史版本
+pub async fn get_key_version(pool: &SqlitePool, id: &str) -> Result<Option<KeyVersion>, DatabaseError> {
+    let version = sqlx::query_as::<_, KeyVersion>("SELECT * FROM api_key_versions WHERE id = ?1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::api_key::{insert_api_key, update_api_key};
+    use crate::database::TestDatabase;
+
+    // 只列出实际变化的字段
+    #[test]
+    fn test_changed_fields() {
+        let previous = ApiKey::new("name".into(), "value".into(), None, None, None, None);
+        let mut next = previous.clone();
+        next.updated_at += 10;
+        next.last_used_at = Some(1);
+        assert!(changed_fields(&previous, &next).is_empty());
+
+        next.key_value = "rotated".into();
+        next.platform = Some("openai".into());
+        next.tags = Some("work".into());
+        assert_eq!(changed_fields(&previous, &next), vec!["keyValue", "platform", "tags"]);
+    }
+
+    // 每次有效修改保存一个旧版本，最近的在前；未修改内容时不产生版本
+    #[tokio::test]
+    async fn test_versions_recorded_on_update() {
+        let db = TestDatabase::open().await;
+        let key = ApiKey::new("main".into(), "value-1".into(), None, None, None, None);
+        insert_api_key(&db.pool, &key).await.unwrap();
+
+        let mut edited = key.clone();
+        edited.key_value = "value-2".into();
+        update_api_key(&db.pool, &edited, None, Some("rotated")).await.unwrap();
+        edited.name = "renamed".into();
+        update_api_key(&db.pool, &edited, None, None).await.unwrap();
+        update_api_key(&db.pool, &edited, None, None).await.unwrap();
+
+        let versions = list_key_versions(&db.pool, &key.id).await.unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].name, "main");
+        assert_eq!(versions[0].key_value, "value-2");
+        assert_eq!(versions[0].changed_fields.as_deref(), Some("name"));
+        assert_eq!(versions[1].key_value, "value-1");
+        assert_eq!(versions[1].reason.as_deref(), Some("rotated"));
+        assert_eq!(versions[1].changed_fields.as_deref(), Some("keyValue"));
+        assert_eq!(versions[1].valid_from, Some(key.updated_at));
+
+        let found = get_key_version(&db.pool, &versions[1].id).await.unwrap().unwrap();
+        assert_eq!(found.key_value, "value-1");
+        assert!(get_key_version(&db.pool, "missing").await.unwrap().is_none());
+    }
+
+    // 返回给前端的摘要不包含旧的Key值
+    #[test]
+    fn test_summary_hides_value() {
+        let version = KeyVersion {
+            id: "v1".into(),
+            key_id: "k1".into(),
+            name: "main".into(),
+            key_value: "sk-old-secret-value".into(),
+            platform: None,
+            description: None,
+            group_id: None,
+            tags: None,
+            valid_from: None,
+            replaced_at: 0,
+            reason: None,
+            changed_fields: None,
+        };
+        let summary = KeyVersionSummary::from(version);
+        assert_eq!(summary.key_preview, key_preview("sk-old-secret-value"));
+
+        let json = serde_json::to_string(&summary).unwrap();
+        assert!(!json.contains("sk-old-secret-value"));
+        assert!(!json.contains("keyValue"));
+    }
+}
@@ -1,59 +1,100 @@
 pub mod api_key;
+pub mod api_token;
+pub mod audit;
 pub mod error;
+pub mod expiry;
 pub mod group;
+pub mod health;
+pub mod key_version;
+pub mod project_binding;
+pub mod repair;
 pub mod usage_history;
 pub mod settings;
 pub mod batch_import;
+pub mod snapshot;
+pub mod trash;
 
 use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
 use tauri::Manager;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use error::DatabaseError;
+use crate::providers;
+use crate::vaults;
 
-// 初始化数据库连接池
-pub async fn init_database(app_handle: &tauri::AppHandle) -> Result<SqlitePool, Box<dyn std::error::Error>> {
+// 数据库文件名
+pub const DATABASE_FILE: &str = "api_keys.db";
+
+// 获取应用数据目录，必要时创建
+pub fn app_data_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, DatabaseError> {
     // 获取应用数据目录
     let app_dir = app_handle.path().app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+        .map_err(|e| DatabaseError::Io(format!("Failed to get app data directory: {}", e)))?;
     
     println!("App data directory: {:?}", app_dir);
     
     // 创建数据库目录
     std::fs::create_dir_all(&app_dir)
-        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
-    
-    // 数据库文件路径
-    let db_path = app_dir.join("api_keys.db");
-    println!("Database file path: {:?}", db_path);
+        .map_err(|e| DatabaseError::Io(format!("Failed to create app data directory: {}", e)))?;
     
     // 检查目录是否创建成功
     if !Path::new(&app_dir).exists() {
-        return Err(format!("App data directory was not created: {:?}", app_dir).into());
+        return Err(DatabaseError::Io(format!("App data directory was not created: {:?}", app_dir)));
     }
-    
+
+    Ok(app_dir)
+}
+
+// 当前保险库的数据目录（数据库与备份都存放在此）
+pub fn active_vault_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, DatabaseError> {
+    let app_dir = app_data_dir(app_handle)?;
+    let vault = vaults::load_registry(&app_dir)?.active_vault();
+    vaults::vault_dir(&app_dir, &vault.id)
+}
+
+// 获取当前保险库的数据库文件路径
+pub fn database_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, DatabaseError> {
+    Ok(active_vault_dir(app_handle)?.join(DATABASE_FILE))
+}
+
+// 初始化数据库连接池
+pub async fn init_database(app_handle: &tauri::AppHandle) -> Result<SqlitePool, DatabaseError> {
+    let db_path = database_path(app_handle)?;
+    println!("Database file path: {:?}", db_path);
+    let pool = open_database(&db_path).await?;
+    println!("Database migrations completed successfully");
+    Ok(pool)
+}
+
+// 打开指定路径的数据库：建立连接池、检查完整性并运行迁移。
+// 成功时不向 stdout 输出，命令行工具的输出可直接用于脚本
+pub async fn open_database(db_path: &Path) -> Result<SqlitePool, DatabaseError> {
     // 创建数据库连接池
     let pool = SqlitePoolOptions::new()
         .max_connections(5)
         .connect_with(
             sqlx::sqlite::SqliteConnectOptions::new()
-                .filename(&db_path)
+                .filename(db_path)
                 .create_if_missing(true)
+                .foreign_keys(true)
         )
         .await
-        .map_err(|e| format!("Failed to create database connection pool: {}", e))?;
-    
-    println!("Database connection pool created successfully");
+        .map_err(|e| health::classify_error("Failed to create database connection pool", e))?;
+
+    // 启动时快速检查数据库完整性
+    if let Err(e) = health::quick_check(&pool).await {
+        pool.close().await;
+        return Err(e);
+    }
     
     // 运行数据库迁移
-    run_migrations(&pool).await
-        .map_err(|e| format!("Failed to run database migrations: {}", e))?;
-    
-    println!("Database migrations completed successfully");
+    if let Err(e) = run_migrations(&pool).await {
+        pool.close().await;
+        return Err(health::classify_error("Failed to run database migrations", e));
+    }
     
     // 验证数据库文件是否存在
-    if Path::new(&db_path).exists() {
-        println!("Database file created successfully at: {:?}", db_path);
-    } else {
-        println!("Warning: Database file was not created at: {:?}", db_path);
+    if !Path::new(db_path).exists() {
+        eprintln!("Warning: Database file was not created at: {:?}", db_path);
     }
     
     Ok(pool)
@@ -103,12 +144,51 @@ async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::Error> {
             id TEXT PRIMARY KEY,
             key_id TEXT NOT NULL,
             used_at INTEGER,
+            action TEXT,
             FOREIGN KEY (key_id) REFERENCES api_keys(id)
         )
         "#
     )
     .execute(pool)
     .await?;
+    add_column_if_missing(pool, "usage_history", "action", "TEXT").await?;
+    add_column_if_missing(pool, "api_keys", "deleted_at", "INTEGER").await?;
+    add_column_if_missing(pool, "api_keys", "expires_at", "INTEGER").await?;
+    add_column_if_missing(pool, "api_keys", "rotate_every_days", "INTEGER").await?;
+    add_column_if_missing(pool, "api_keys", "verification_status", "TEXT").await?;
+    add_column_if_missing(pool, "api_keys", "verification_message", "TEXT").await?;
+    add_column_if_missing(pool, "api_keys", "verified_at", "INTEGER").await?;
+
+    // 创建API Key历史版本表
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS api_key_versions (
+            id TEXT PRIMARY KEY,
+            key_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            key_value TEXT NOT NULL,
+            platform TEXT,
+            description TEXT,
+            group_id TEXT,
+            tags TEXT,
+            valid_from INTEGER,
+            replaced_at INTEGER NOT NULL,
+            reason TEXT,
+            changed_fields TEXT,
+            FOREIGN KEY (key_id) REFERENCES api_keys(id)
+        )
+        "#
+    )
+    .execute(pool)
+    .await?;
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_api_key_versions_key_id ON api_key_versions (key_id)")
+        .execute(pool)
+        .await?;
+
+    // 旧版本未启用外键约束，硬删除会留下孤立的使用记录
+    sqlx::query("DELETE FROM usage_history WHERE key_id NOT IN (SELECT id FROM api_keys)")
+        .execute(pool)
+        .await?;
 
     // 创建配置表
     sqlx::query(
@@ -139,5 +219,143 @@ async fn run_migrations(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    // 创建审计日志表
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS audit_log (
+            id TEXT PRIMARY KEY,
+            created_at INTEGER NOT NULL,
+            source TEXT NOT NULL,
+            action TEXT NOT NULL,
+            target TEXT,
+            key_id TEXT,
+            outcome TEXT NOT NULL,
+            detail TEXT,
+            seq INTEGER,
+            prev_hash TEXT,
+            entry_hash TEXT
+        )
+        "#
+    )
+    .execute(pool)
+    .await?;
+    add_column_if_missing(pool, "audit_log", "seq", "INTEGER").await?;
+    add_column_if_missing(pool, "audit_log", "prev_hash", "TEXT").await?;
+    add_column_if_missing(pool, "audit_log", "entry_hash", "TEXT").await?;
+    // 链尾与记录不一致时仍然打开数据库，由校验命令报告问题
+    if let Err(e) = audit::backfill_audit_chain(pool).await {
+        eprintln!("Failed to add audit log entries to the hash chain: {}", e);
+    }
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_audit_log_created_at ON audit_log(created_at)")
+        .execute(pool)
+        .await?;
+    // 序号唯一，并发写入时不会产生分叉的链
+    sqlx::query("CREATE UNIQUE INDEX IF NOT EXISTS idx_audit_log_seq ON audit_log(seq)")
+        .execute(pool)
+        .await?;
+
+    // 创建本地 HTTP API 访问令牌表
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS api_tokens (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            token_hash TEXT NOT NULL,
+            group_ids TEXT,
+            tags TEXT,
+            allow_secrets INTEGER NOT NULL DEFAULT 0,
+            created_at INTEGER NOT NULL,
+            last_used_at INTEGER
+        )
+        "#
+    )
+    .execute(pool)
+    .await?;
+
+    // 创建项目绑定表：项目目录中的变量与Key的对应关系
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS project_bindings (
+            id TEXT PRIMARY KEY,
+            project_path TEXT NOT NULL,
+            env_var TEXT NOT NULL,
+            key_id TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            synced_at INTEGER,
+            UNIQUE (project_path, env_var)
+        )
+        "#
+    )
+    .execute(pool)
+    .await?;
+
+    canonicalize_platforms(pool).await?;
+
+    Ok(())
+}
+
+// 将旧数据中的平台别名（如 Anthropic、gpt）统一为注册表中的平台 id
+async fn canonicalize_platforms(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    for provider in providers::PROVIDERS {
+        let mut names: Vec<String> = provider.aliases.iter().map(|a| a.to_string()).collect();
+        names.push(provider.label.to_lowercase());
+        let sql = format!(
+            "UPDATE api_keys SET platform = ?1 WHERE platform != ?1 AND LOWER(TRIM(platform)) IN ({})",
+            vec!["?"; names.len()].join(",")
+        );
+        let mut query = sqlx::query(&sql).bind(provider.id);
+        for name in &names {
+            query = query.bind(name);
+        }
+        let updated = query.execute(pool).await?.rows_affected();
+        if updated > 0 {
+            eprintln!("Canonicalized platform of {} API keys to {}", updated, provider.id);
+        }
+    }
+
+    Ok(())
+}
+
+// 为旧版本数据库中已存在的表补充新增列
+async fn add_column_if_missing(
+    pool: &SqlitePool,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<(), sqlx::Error> {
+    let columns: Vec<String> = sqlx::query_scalar(&format!("SELECT name FROM pragma_table_info('{}')", table))
+        .fetch_all(pool)
+        .await?;
+
+    if !columns.iter().any(|c| c == column) {
+        sqlx::query(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))
+            .execute(pool)
+            .await?;
+    }
+
     Ok(())
-}
\ No newline at end of file
+}
+
+// 测试用的临时数据库，离开作用域时删除文件
+#[cfg(test)]
+pub(crate) struct TestDatabase {
+    pub pool: SqlitePool,
+    path: PathBuf,
+}
+
+#[cfg(test)]
+impl TestDatabase {
+    pub(crate) async fn open() -> Self {
+        let path = std::env::temp_dir().join(format!("api-keys-test-{}.db", uuid::Uuid::new_v4()));
+        let pool = open_database(&path).await.expect("failed to open test database");
+        Self { pool, path }
+    }
+}
+
+#[cfg(test)]
+impl Drop for TestDatabase {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
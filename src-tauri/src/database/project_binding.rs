@@ -0,0 +1,200 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use crate::database::error::DatabaseError;
+
+// 项目目录与Key的绑定：project_path 下的 .env 中变量 env_var 取自 key_id。
+// synced_at 为最近一次写入 .env 的时间，之后Key的值被修改（轮换）即视为过期
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectBinding {
+    pub id: String,
+    #[serde(rename = "projectPath")]
+    pub project_path: String,
+    #[serde(rename = "envVar")]
+    pub env_var: String,
+    #[serde(rename = "keyId")]
+    pub key_id: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: i64,
+    #[serde(rename = "syncedAt")]
+    pub synced_at: Option<i64>,
+}
+
+// 绑定及其状态：Key已删除、或同步后Key的值被修改
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct BindingStatus {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub binding: ProjectBinding,
+    #[serde(rename = "keyName")]
+    pub key_name: Option<String>,
+    pub missing: bool,
+    pub stale: bool,
+}
+
+// 绑定状态查询：Key不存在或在回收站中为 missing；从未同步或同步后 keyValue 有过修改为 stale。
+// 时间戳只精确到秒，与同步同一秒内的修改也视为过期，宁可多提示一次重新同步
+const STATUS_SELECT: &str = r#"
+    SELECT b.*, k.name AS key_name,
+        (k.id IS NULL OR k.deleted_at IS NOT NULL) AS missing,
+        (b.synced_at IS NULL OR EXISTS (
+            SELECT 1 FROM api_key_versions v
+            WHERE v.key_id = b.key_id
+              AND v.replaced_at >= b.synced_at
+              AND (',' || COALESCE(v.changed_fields, '') || ',') LIKE '%,keyValue,%'
+        )) AS stale
+    FROM project_bindings b
+    LEFT JOIN api_keys k ON k.id = b.key_id
+"#;
+
+// 设置项目中变量对应的Key，已存在时替换并标记为未同步
+pub async fn upsert_binding(
+    pool: &SqlitePool,
+    project_path: &str,
+    env_var: &str,
+    key_id: &str,
+) -> Result<(), DatabaseError> {
+    sqlx::query(
+        r#"
+        INSERT INTO project_bindings (id, project_path, env_var, key_id, created_at, synced_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, NULL)
+        ON CONFLICT(project_path, env_var) DO UPDATE SET key_id = excluded.key_id, synced_at = NULL
+        "#
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(project_path)
+    .bind(env_var)
+    .bind(key_id)
+    .bind(chrono::Utc::now().timestamp())
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// 删除项目中的绑定；未指定变量时删除整个项目，返回删除的条数
+pub async fn delete_bindings(
+    pool: &SqlitePool,
+    project_path: &str,
+    env_var: Option<&str>,
+) -> Result<u64, DatabaseError> {
+    let result = match env_var {
+        Some(env_var) => {
+            sqlx::query("DELETE FROM project_bindings WHERE project_path = ?1 AND env_var = ?2")
+                .bind(project_path)
+                .bind(env_var)
+                .execute(pool)
+                .await?
+        }
+        None => {
+            sqlx::query("DELETE FROM project_bindings WHERE project_path = ?1")
+                .bind(project_path)
+                .execute(pool)
+                .await?
+        }
+    };
+
+    Ok(result.rows_affected())
+}
+
+// 获取所有绑定及状态，按项目与变量名排序
+pub async fn list_binding_statuses(pool: &SqlitePool) -> Result<Vec<BindingStatus>, DatabaseError> {
+    let sql = format!("{} ORDER BY b.project_path, b.env_var", STATUS_SELECT);
+    let statuses = sqlx::query_as::<_, BindingStatus>(&sql).fetch_all(pool).await?;
+
+    Ok(statuses)
+}
+
+// 获取项目的绑定及状态
+pub async fn get_project_statuses(pool: &SqlitePool, project_path: &str) -> Result<Vec<BindingStatus>, DatabaseError> {
+    let sql = format!("{} WHERE b.project_path = ?1 ORDER BY b.env_var", STATUS_SELECT);
+    let statuses = sqlx::query_as::<_, BindingStatus>(&sql)
+        .bind(project_path)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(statuses)
+}
+
+// 绑定了指定Key的项目路径
+pub async fn projects_using_key(pool: &SqlitePool, key_id: &str) -> Result<Vec<String>, DatabaseError> {
+    let paths = sqlx::query_scalar::<_, String>(
+        "SELECT DISTINCT project_path FROM project_bindings WHERE key_id = ?1 ORDER BY project_path"
+    )
+    .bind(key_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(paths)
+}
+
+// 记录项目的 .env 已按当前的Key写入
+pub async fn mark_synced(pool: &SqlitePool, project_path: &str, synced_at: i64) -> Result<(), DatabaseError> {
+    sqlx::query("UPDATE project_bindings SET synced_at = ?1 WHERE project_path = ?2")
+        .bind(synced_at)
+        .bind(project_path)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::api_key::{delete_api_key, insert_api_key, update_api_key, ApiKey};
+    use crate::database::TestDatabase;
+
+    async fn status(pool: &SqlitePool, project_path: &str) -> BindingStatus {
+        get_project_statuses(pool, project_path).await.unwrap().remove(0)
+    }
+
+    // 从未同步为过期；同步后只有修改 key_value 才过期，同一秒内的修改也算；Key删除后为 missing
+    #[tokio::test]
+    async fn test_binding_status() {
+        let db = TestDatabase::open().await;
+        let key = ApiKey::new("main".into(), "value-1".into(), None, None, None, None);
+        insert_api_key(&db.pool, &key).await.unwrap();
+        upsert_binding(&db.pool, "/tmp/project", "API_KEY", &key.id).await.unwrap();
+        assert!(status(&db.pool, "/tmp/project").await.stale);
+
+        mark_synced(&db.pool, "/tmp/project", chrono::Utc::now().timestamp()).await.unwrap();
+        let synced = status(&db.pool, "/tmp/project").await;
+        assert!(!synced.stale && !synced.missing);
+        assert_eq!(synced.key_name.as_deref(), Some("main"));
+
+        let mut edited = key.clone();
+        edited.name = "renamed".into();
+        update_api_key(&db.pool, &edited, None, None).await.unwrap();
+        assert!(!status(&db.pool, "/tmp/project").await.stale);
+
+        edited.key_value = "value-2".into();
+        update_api_key(&db.pool, &edited, None, None).await.unwrap();
+        assert!(status(&db.pool, "/tmp/project").await.stale);
+
+        delete_api_key(&db.pool, &key.id).await.unwrap();
+        assert!(status(&db.pool, "/tmp/project").await.missing);
+    }
+
+    // 重新绑定同一变量时替换Key并重置同步时间；可按变量或整个项目解除绑定
+    #[tokio::test]
+    async fn test_upsert_and_delete_bindings() {
+        let db = TestDatabase::open().await;
+        upsert_binding(&db.pool, "/tmp/a", "ONE", "k1").await.unwrap();
+        upsert_binding(&db.pool, "/tmp/a", "TWO", "k1").await.unwrap();
+        upsert_binding(&db.pool, "/tmp/b", "ONE", "k1").await.unwrap();
+        mark_synced(&db.pool, "/tmp/a", 100).await.unwrap();
+
+        upsert_binding(&db.pool, "/tmp/a", "ONE", "k2").await.unwrap();
+        let statuses = get_project_statuses(&db.pool, "/tmp/a").await.unwrap();
+        assert_eq!(statuses[0].binding.key_id, "k2");
+        assert_eq!(statuses[0].binding.synced_at, None);
+        assert_eq!(statuses[1].binding.synced_at, Some(100));
+        assert_eq!(projects_using_key(&db.pool, "k1").await.unwrap(), vec!["/tmp/a", "/tmp/b"]);
+
+        assert_eq!(delete_bindings(&db.pool, "/tmp/a", Some("TWO")).await.unwrap(), 1);
+        assert_eq!(delete_bindings(&db.pool, "/tmp/a", None).await.unwrap(), 1);
+        assert_eq!(list_binding_statuses(&db.pool).await.unwrap().len(), 1);
+    }
+}
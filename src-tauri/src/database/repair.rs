@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use crate::backup::{list_backups, validate_backup, BackupInfo};
+use crate::database::error::DatabaseError;
+use crate::database::open_database;
+
+// 抢救数据时尝试读取的表，顺序满足外键依赖
+const SALVAGE_TABLES: &[&str] = &["groups", "api_keys", "api_key_versions", "usage_history", "settings", "batch_imports", "audit_log", "api_tokens", "project_bindings"];
+
+// 数据库损坏时的修复方式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RepairStrategy {
+    // 用最新的有效备份替换损坏的数据库
+    RestoreBackup,
+    // 新建数据库并尽可能复制仍可读取的数据
+    Salvage,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairResult {
+    pub strategy: RepairStrategy,
+    // 损坏的数据库被移动到的位置
+    pub quarantined_path: String,
+    pub restored_backup: Option<String>,
+    pub salvaged_rows: u64,
+    pub unreadable_rows: u64,
+}
+
+// 查找最新的、通过完整性检查的备份
+pub async fn latest_valid_backup(backups_dir: &Path) -> Option<BackupInfo> {
+    for backup in list_backups(backups_dir).ok()? {
+        if validate_backup(Path::new(&backup.path)).await.is_ok() {
+            return Some(backup);
+        }
+    }
+    None
+}
+
+// 将损坏的数据库及其 WAL/SHM 文件移到一旁，保留原始数据以便人工处理
+pub fn quarantine(db_path: &Path) -> Result<PathBuf, DatabaseError> {
+    let suffix = format!("corrupt-{}", chrono::Utc::now().format("%Y%m%d-%H%M%S"));
+    let target = db_path.with_extension(format!("db.{}", suffix));
+
+    if db_path.exists() {
+        std::fs::rename(db_path, &target)
+            .map_err(|e| DatabaseError::Io(format!("Failed to move corrupted database: {}", e)))?;
+    }
+    for sidecar in ["db-wal", "db-shm"] {
+        let path = db_path.with_extension(sidecar);
+        if path.exists() {
+            let _ = std::fs::rename(&path, target.with_extension(format!("{}.{}", sidecar, suffix)));
+        }
+    }
+
+    println!("Corrupted database moved to: {:?}", target);
+    Ok(target)
+}
+
+// 用最新的有效备份替换损坏的数据库
+pub async fn repair_with_backup(db_path: &Path, backups_dir: &Path) -> Result<RepairResult, DatabaseError> {
+    let backup = latest_valid_backup(backups_dir)
+        .await
+        .ok_or_else(|| DatabaseError::InvalidInput("No valid backup available".to_string()))?;
+
+    let quarantined = quarantine(db_path)?;
+    std::fs::copy(&backup.path, db_path)
+        .map_err(|e| DatabaseError::Io(format!("Failed to copy backup: {}", e)))?;
+
+    Ok(RepairResult {
+        strategy: RepairStrategy::RestoreBackup,
+        quarantined_path: quarantined.to_string_lossy().into_owned(),
+        restored_backup: Some(backup.file_name),
+        salvaged_rows: 0,
+        unreadable_rows: 0,
+    })
+}
+
+// 新建数据库，并逐行复制损坏文件中仍能读取的数据
+pub async fn repair_with_salvage(db_path: &Path) -> Result<RepairResult, DatabaseError> {
+    let quarantined = quarantine(db_path)?;
+    let pool = open_database(db_path).await?;
+
+    let (salvaged_rows, unreadable_rows) = salvage_rows(&pool, &quarantined).await;
+    pool.close().await;
+    println!(
+        "Salvaged {} rows from corrupted database ({} unreadable)",
+        salvaged_rows, unreadable_rows
+    );
+
+    Ok(RepairResult {
+        strategy: RepairStrategy::Salvage,
+        quarantined_path: quarantined.to_string_lossy().into_owned(),
+        restored_backup: None,
+        salvaged_rows,
+        unreadable_rows,
+    })
+}
+
+async fn salvage_rows(pool: &SqlitePool, source: &Path) -> (u64, u64) {
+    let Ok(mut conn) = pool.acquire().await else {
+        return (0, 0);
+    };
+    let attached = sqlx::query("ATTACH DATABASE ?1 AS salvage_source")
+        .bind(source.to_string_lossy().into_owned())
+        .execute(&mut *conn)
+        .await;
+    if let Err(e) = attached {
+        eprintln!("Failed to attach corrupted database: {}", e);
+        return (0, 0);
+    }
+
+    let mut salvaged = 0;
+    let mut unreadable = 0;
+    for table in SALVAGE_TABLES {
+        let target: Vec<String> = sqlx::query_scalar(&format!("SELECT name FROM pragma_table_info('{}', 'main')", table))
+            .fetch_all(&mut *conn)
+            .await
+            .unwrap_or_default();
+        let source_columns: Vec<String> = sqlx::query_scalar(&format!("SELECT name FROM pragma_table_info('{}', 'salvage_source')", table))
+            .fetch_all(&mut *conn)
+            .await
+            .unwrap_or_default();
+        let columns = target
+            .iter()
+            .filter(|c| source_columns.contains(c))
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ");
+        if columns.is_empty() {
+            eprintln!("Table {} could not be read from corrupted database", table);
+            continue;
+        }
+
+        let rowids: Vec<i64> = match sqlx::query_scalar(&format!("SELECT rowid FROM salvage_source.{}", table))
+            .fetch_all(&mut *conn)
+            .await
+        {
+            Ok(rowids) => rowids,
+            Err(e) => {
+                eprintln!("Failed to list rows of {}: {}", table, e);
+                continue;
+            }
+        };
+
+        // 逐行复制，跳过无法读取或违反约束的行
+        let insert = format!(
+            "INSERT OR IGNORE INTO main.{table} ({cols}) SELECT {cols} FROM salvage_source.{table} WHERE rowid = ?1",
+            table = table,
+            cols = columns
+        );
+        for rowid in rowids {
+            match sqlx::query(&insert).bind(rowid).execute(&mut *conn).await {
+                Ok(result) => salvaged += result.rows_affected(),
+                Err(_) => unreadable += 1,
+            }
+        }
+    }
+
+    let _ = sqlx::query("DETACH DATABASE salvage_source")
+        .execute(&mut *conn)
+        .await;
+    (salvaged, unreadable)
+}
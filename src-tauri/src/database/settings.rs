@@ -1,8 +1,64 @@
 use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use crate::agent::CONFIRM_SETTING;
+use crate::database::audit::CHAIN_HEAD_SETTING;
+use crate::database::error::DatabaseError;
+use crate::security::password::MASTER_PASSWORD_SETTING;
+use crate::verifier::BASE_URL_SETTING_PREFIX;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[allow(dead_code)]
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
 pub struct Setting {
     pub key: String,
     pub value: String,
-}
\ No newline at end of file
+}
+
+// 获取所有配置项
+pub async fn get_all_settings(pool: &SqlitePool) -> Result<Vec<Setting>, DatabaseError> {
+    sqlx::query_as::<_, Setting>("SELECT key, value FROM settings ORDER BY key")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| DatabaseError::SqlxError(e.to_string()))
+}
+
+// 获取单个配置项
+pub async fn get_setting(pool: &SqlitePool, key: &str) -> Result<Option<String>, DatabaseError> {
+    sqlx::query_scalar::<_, String>("SELECT value FROM settings WHERE key = ?1")
+        .bind(key)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| DatabaseError::SqlxError(e.to_string()))
+}
+
+// 删除配置项
+pub async fn delete_setting(pool: &SqlitePool, key: &str) -> Result<(), DatabaseError> {
+    sqlx::query("DELETE FROM settings WHERE key = ?1")
+        .bind(key)
+        .execute(pool)
+        .await
+        .map(|_| ())
+        .map_err(|e| DatabaseError::SqlxError(e.to_string()))
+}
+
+// 写入配置项（存在则覆盖）
+pub async fn set_setting(pool: &SqlitePool, key: &str, value: &str) -> Result<(), DatabaseError> {
+    sqlx::query(
+        r#"
+        INSERT INTO settings (key, value) VALUES (?1, ?2)
+        ON CONFLICT(key) DO UPDATE SET value = excluded.value
+        "#
+    )
+    .bind(key)
+    .bind(value)
+    .execute(pool)
+    .await
+    .map(|_| ())
+    .map_err(|e| DatabaseError::SqlxError(e.to_string()))
+}
+
+// 只属于本机的安全配置：不写入导出的快照，导入快照或从备份恢复时保持本机原有的值
+const LOCAL_ONLY_SETTINGS: &[&str] = &[MASTER_PASSWORD_SETTING, CONFIRM_SETTING, CHAIN_HEAD_SETTING];
+const LOCAL_ONLY_PREFIXES: &[&str] = &["http_api_", "unlock_", BASE_URL_SETTING_PREFIX];
+
+pub fn is_local_only_setting(key: &str) -> bool {
+    LOCAL_ONLY_SETTINGS.contains(&key) || LOCAL_ONLY_PREFIXES.iter().any(|prefix| key.starts_with(prefix))
+}
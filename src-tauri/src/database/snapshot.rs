@@ -0,0 +1,325 @@
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use crate::database::api_key::{get_all_api_keys, ApiKey};
+use crate::database::error::DatabaseError;
+use crate::database::group::{get_all_groups, Group};
+use crate::database::settings::{get_all_settings, is_local_only_setting, Setting};
+use crate::database::usage_history::{get_all_usage_history, UsageHistory};
+use crate::validation::normalize_api_key;
+
+// 整个保险库的数据快照，用于导出与恢复
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultSnapshot {
+    #[serde(rename = "apiKeys")]
+    pub api_keys: Vec<ApiKey>,
+    pub groups: Vec<Group>,
+    pub tags: Vec<String>,
+    pub settings: Vec<Setting>,
+    #[serde(rename = "usageHistory")]
+    pub usage_history: Option<Vec<UsageHistory>>,
+}
+
+// 快照导入模式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportMode {
+    // 合并：保留现有数据，同ID条目以较新的为准
+    Merge,
+    // 替换：清空现有数据后写入快照
+    Replace,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotImportResult {
+    #[serde(rename = "keysImported")]
+    pub keys_imported: usize,
+    #[serde(rename = "keysSkipped")]
+    pub keys_skipped: usize,
+    #[serde(rename = "groupsImported")]
+    pub groups_imported: usize,
+    #[serde(rename = "settingsImported")]
+    pub settings_imported: usize,
+    #[serde(rename = "usageImported")]
+    pub usage_imported: usize,
+}
+
+impl VaultSnapshot {
+    // 写入数据库前检查快照内容是否完整
+    pub fn validate(&self) -> Result<(), DatabaseError> {
+        let mut ids = std::collections::HashSet::new();
+        for key in &self.api_keys {
+            if key.id.trim().is_empty() || key.name.trim().is_empty() || key.key_value.is_empty() {
+                return Err(DatabaseError::InvalidInput(format!(
+                    "API key '{}' is missing required fields",
+                    key.id
+                )));
+            }
+            if !ids.insert(key.id.as_str()) {
+                return Err(DatabaseError::InvalidInput(format!("Duplicate API key id: {}", key.id)));
+            }
+        }
+
+        let mut group_ids = std::collections::HashSet::new();
+        for group in &self.groups {
+            if group.id.trim().is_empty() || !group_ids.insert(group.id.as_str()) {
+                return Err(DatabaseError::InvalidInput(format!("Invalid group id: '{}'", group.id)));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// 从数据库读取完整快照
+pub async fn load_snapshot(
+    pool: &SqlitePool,
+    include_usage_history: bool,
+) -> Result<VaultSnapshot, DatabaseError> {
+    let api_keys = get_all_api_keys(pool).await?;
+    let groups = get_all_groups(pool).await?;
+    let settings = get_all_settings(pool)
+        .await?
+        .into_iter()
+        .filter(|s| !is_local_only_setting(&s.key))
+        .collect();
+    let usage_history = if include_usage_history {
+        Some(get_all_usage_history(pool).await?)
+    } else {
+        None
+    };
+
+    let mut tags: Vec<String> = api_keys
+        .iter()
+        .filter_map(|k| k.tags.as_deref())
+        .flat_map(|t| t.split(','))
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+    tags.sort();
+    tags.dedup();
+
+    Ok(VaultSnapshot {
+        api_keys,
+        groups,
+        tags,
+        settings,
+        usage_history,
+    })
+}
+
+// 在单个事务中将快照写入数据库
+pub async fn apply_snapshot(
+    pool: &SqlitePool,
+    snapshot: &VaultSnapshot,
+    mode: ImportMode,
+) -> Result<SnapshotImportResult, DatabaseError> {
+    snapshot.validate()?;
+
+    // 与手动添加的Key一样规范化名称、平台与标签
+    let mut api_keys = snapshot.api_keys.clone();
+    for key in &mut api_keys {
+        normalize_api_key(key).map_err(|errors| {
+            let fields: Vec<String> = errors.iter().map(ToString::to_string).collect();
+            DatabaseError::InvalidInput(format!("API key '{}' is invalid ({})", key.id, fields.join("; ")))
+        })?;
+    }
+
+    let mut tx = pool.begin().await?;
+    let mut result = SnapshotImportResult::default();
+
+    if mode == ImportMode::Replace {
+        for table in ["usage_history", "api_key_versions", "api_keys", "groups"] {
+            sqlx::query(&format!("DELETE FROM {}", table))
+                .execute(&mut *tx)
+                .await?;
+        }
+        // 本机的安全配置不随快照替换
+        let keys: Vec<String> = sqlx::query_scalar("SELECT key FROM settings")
+            .fetch_all(&mut *tx)
+            .await?;
+        for key in keys.iter().filter(|key| !is_local_only_setting(key)) {
+            sqlx::query("DELETE FROM settings WHERE key = ?1")
+                .bind(key)
+                .execute(&mut *tx)
+                .await?;
+        }
+    }
+
+    for group in &snapshot.groups {
+        let affected = sqlx::query(
+            r#"
+            INSERT INTO groups (id, name, description, created_at, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                description = excluded.description,
+                updated_at = excluded.updated_at
+            WHERE excluded.updated_at > groups.updated_at
+            "#
+        )
+        .bind(&group.id)
+        .bind(&group.name)
+        .bind(&group.description)
+        .bind(group.created_at)
+        .bind(group.updated_at)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        result.groups_imported += affected as usize;
+    }
+
+    for key in &api_keys {
+        let affected = sqlx::query(
+            r#"
+            INSERT INTO api_keys (
+                id, name, key_value, platform, description, group_id, tags, created_at, updated_at, last_used_at,
+                expires_at, rotate_every_days
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+            ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                key_value = excluded.key_value,
+                platform = excluded.platform,
+                description = excluded.description,
+                group_id = excluded.group_id,
+                tags = excluded.tags,
+                updated_at = excluded.updated_at,
+                last_used_at = excluded.last_used_at,
+                expires_at = excluded.expires_at,
+                rotate_every_days = excluded.rotate_every_days
+            WHERE excluded.updated_at > api_keys.updated_at
+            "#
+        )
+        .bind(&key.id)
+        .bind(&key.name)
+        .bind(&key.key_value)
+        .bind(&key.platform)
+        .bind(&key.description)
+        .bind(&key.group_id)
+        .bind(&key.tags)
+        .bind(key.created_at)
+        .bind(key.updated_at)
+        .bind(key.last_used_at)
+        .bind(key.expires_at)
+        .bind(key.rotate_every_days)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        if affected > 0 {
+            result.keys_imported += 1;
+        } else {
+            result.keys_skipped += 1;
+        }
+    }
+
+    // 替换模式下删除指向已不存在Key的项目绑定，快照中保留了同ID的Key时绑定继续有效
+    if mode == ImportMode::Replace {
+        sqlx::query("DELETE FROM project_bindings WHERE key_id NOT IN (SELECT id FROM api_keys)")
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    for setting in snapshot.settings.iter().filter(|s| !is_local_only_setting(&s.key)) {
+        // 合并模式下不覆盖本机已有配置
+        let sql = match mode {
+            ImportMode::Merge => "INSERT OR IGNORE INTO settings (key, value) VALUES (?1, ?2)",
+            ImportMode::Replace => "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+        };
+        let affected = sqlx::query(sql)
+            .bind(&setting.key)
+            .bind(&setting.value)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        result.settings_imported += affected as usize;
+    }
+
+    for usage in snapshot.usage_history.iter().flatten() {
+        let affected = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO usage_history (id, key_id, used_at, action)
+            SELECT ?1, ?2, ?3, ?4 WHERE EXISTS (SELECT 1 FROM api_keys WHERE id = ?2)
+            "#
+        )
+        .bind(&usage.id)
+        .bind(&usage.key_id)
+        .bind(usage.used_at)
+        .bind(&usage.action)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        result.usage_imported += affected as usize;
+    }
+
+    tx.commit().await?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::api_key::insert_api_key;
+    use crate::database::project_binding::{projects_using_key, upsert_binding};
+    use crate::database::TestDatabase;
+
+    fn snapshot_with(api_keys: Vec<ApiKey>) -> VaultSnapshot {
+        VaultSnapshot {
+            api_keys,
+            groups: Vec::new(),
+            tags: Vec::new(),
+            settings: Vec::new(),
+            usage_history: None,
+        }
+    }
+
+    // 替换导入后，指向已删除Key的项目绑定被清除，快照中仍存在的Key保留绑定
+    #[tokio::test]
+    async fn test_replace_import_drops_stale_bindings() {
+        let db = TestDatabase::open().await;
+        let kept = ApiKey::new("kept".into(), "value-1".into(), None, None, None, None);
+        let dropped = ApiKey::new("dropped".into(), "value-2".into(), None, None, None, None);
+        insert_api_key(&db.pool, &kept).await.unwrap();
+        insert_api_key(&db.pool, &dropped).await.unwrap();
+        upsert_binding(&db.pool, "/tmp/project", "KEPT", &kept.id).await.unwrap();
+        upsert_binding(&db.pool, "/tmp/project", "DROPPED", &dropped.id).await.unwrap();
+
+        apply_snapshot(&db.pool, &snapshot_with(vec![kept.clone()]), ImportMode::Replace)
+            .await
+            .unwrap();
+
+        assert_eq!(projects_using_key(&db.pool, &kept.id).await.unwrap(), vec!["/tmp/project"]);
+        assert!(projects_using_key(&db.pool, &dropped.id).await.unwrap().is_empty());
+    }
+
+    // 导入的Key与手动添加的Key一样规范化
+    #[tokio::test]
+    async fn test_import_normalizes_keys() {
+        let db = TestDatabase::open().await;
+        let mut key = ApiKey::new(" imported ".into(), " value ".into(), Some("Anthropic".into()), None, None, Some("b, a,,a".into()));
+        key.id = "imported-id".into();
+
+        apply_snapshot(&db.pool, &snapshot_with(vec![key]), ImportMode::Merge).await.unwrap();
+
+        let stored = get_all_api_keys(&db.pool).await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].name, "imported");
+        assert_eq!(stored[0].key_value, "value");
+        assert_eq!(stored[0].platform.as_deref(), Some("claude"));
+        assert_eq!(stored[0].tags.as_deref(), Some("b,a"));
+    }
+
+    // 规范化失败的Key使整个导入失败
+    #[tokio::test]
+    async fn test_import_rejects_invalid_key() {
+        let db = TestDatabase::open().await;
+        let key = ApiKey::new("bad".into(), "has space".into(), None, None, None, None);
+
+        let result = apply_snapshot(&db.pool, &snapshot_with(vec![key]), ImportMode::Merge).await;
+
+        assert!(matches!(result, Err(DatabaseError::InvalidInput(_))));
+        assert!(get_all_api_keys(&db.pool).await.unwrap().is_empty());
+    }
+}
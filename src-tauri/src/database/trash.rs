@@ -0,0 +1,213 @@
+use sqlx::SqlitePool;
+use crate::database::api_key::ApiKey;
+use crate::database::error::DatabaseError;
+use crate::database::settings::{get_setting, set_setting};
+use crate::validation::MAX_PERIOD_DAYS;
+
+// settings 表中保存回收站保留天数的键
+pub const TRASH_RETENTION_SETTING: &str = "trash_retention_days";
+pub const DEFAULT_TRASH_RETENTION_DAYS: i64 = 30;
+
+// 读取回收站保留天数，0 表示不自动清理
+pub async fn get_retention_days(pool: &SqlitePool) -> Result<i64, DatabaseError> {
+    Ok(get_setting(pool, TRASH_RETENTION_SETTING)
+        .await?
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_TRASH_RETENTION_DAYS))
+}
+
+// 保存回收站保留天数
+pub async fn set_retention_days(pool: &SqlitePool, days: i64) -> Result<(), DatabaseError> {
+    if !(0..=MAX_PERIOD_DAYS).contains(&days) {
+        return Err(DatabaseError::InvalidInput(format!(
+            "Retention days must be between 0 and {}",
+            MAX_PERIOD_DAYS
+        )));
+    }
+    set_setting(pool, TRASH_RETENTION_SETTING, &days.to_string()).await
+}
+
+// 获取回收站中的API Key，最近删除的在前
+pub async fn list_trashed_api_keys(pool: &SqlitePool) -> Result<Vec<ApiKey>, DatabaseError> {
+    let keys = sqlx::query_as::<_, ApiKey>(
+        "SELECT * FROM api_keys WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(keys)
+}
+
+// 从回收站恢复API Key
+pub async fn restore_api_key(pool: &SqlitePool, id: &str) -> Result<(), DatabaseError> {
+    let result = sqlx::query(
+        "UPDATE api_keys SET deleted_at = NULL, updated_at = MAX(?1, updated_at + 1) WHERE id = ?2 AND deleted_at IS NOT NULL"
+    )
+    .bind(chrono::Utc::now().timestamp())
+    .bind(id)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(DatabaseError::KeyNotFound);
+    }
+    Ok(())
+}
+
+// 彻底删除回收站中的API Key及其使用记录、历史版本；ids 为空时清空整个回收站
+pub async fn purge_trash(pool: &SqlitePool, ids: Option<&[String]>) -> Result<u64, DatabaseError> {
+    let condition = match ids {
+        Some([]) => return Ok(0),
+        Some(ids) => format!("deleted_at IS NOT NULL AND id IN ({})", vec!["?"; ids.len()].join(",")),
+        None => "deleted_at IS NOT NULL".to_string(),
+    };
+    let ids = ids.unwrap_or_default();
+
+    let mut tx = pool.begin().await?;
+
+    // 先删除使用记录与历史版本，避免违反外键约束
+    for table in ["usage_history", "api_key_versions"] {
+        let sql = format!(
+            "DELETE FROM {} WHERE key_id IN (SELECT id FROM api_keys WHERE {})",
+            table, condition
+        );
+        let mut query = sqlx::query(&sql);
+        for id in ids {
+            query = query.bind(id);
+        }
+        query.execute(&mut *tx).await?;
+    }
+
+    let keys_sql = format!("DELETE FROM api_keys WHERE {}", condition);
+    let mut query = sqlx::query(&keys_sql);
+    for id in ids {
+        query = query.bind(id);
+    }
+    let purged = query.execute(&mut *tx).await?.rows_affected();
+
+    tx.commit().await?;
+    Ok(purged)
+}
+
+// 清理超过保留期限的已删除API Key
+pub async fn purge_expired(pool: &SqlitePool) -> Result<u64, DatabaseError> {
+    let days = get_retention_days(pool).await?;
+    if days <= 0 {
+        return Ok(0);
+    }
+    // 设置值可能来自旧版本或被直接修改，计算时不能溢出
+    let cutoff = chrono::Utc::now()
+        .timestamp()
+        .saturating_sub(days.saturating_mul(24 * 60 * 60));
+    let expired: Vec<String> = sqlx::query_scalar(
+        "SELECT id FROM api_keys WHERE deleted_at IS NOT NULL AND deleted_at < ?1"
+    )
+    .bind(cutoff)
+    .fetch_all(pool)
+    .await?;
+
+    purge_trash(pool, Some(&expired)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::api_key::{delete_api_key, get_all_api_keys, insert_api_key};
+    use crate::database::usage_history::record_usage;
+    use crate::database::TestDatabase;
+
+    async fn insert_key(pool: &SqlitePool, name: &str) -> ApiKey {
+        let key = ApiKey::new(name.into(), format!("{}-value", name), None, None, None, None);
+        insert_api_key(pool, &key).await.unwrap();
+        key
+    }
+
+    async fn set_deleted_at(pool: &SqlitePool, id: &str, deleted_at: i64) {
+        sqlx::query("UPDATE api_keys SET deleted_at = ?1 WHERE id = ?2")
+            .bind(deleted_at)
+            .bind(id)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    // 保留天数默认 30 天，超出范围的值被拒绝
+    #[tokio::test]
+    async fn test_retention_days() {
+        let db = TestDatabase::open().await;
+        assert_eq!(get_retention_days(&db.pool).await.unwrap(), DEFAULT_TRASH_RETENTION_DAYS);
+
+        set_retention_days(&db.pool, 7).await.unwrap();
+        assert_eq!(get_retention_days(&db.pool).await.unwrap(), 7);
+
+        for days in [-1, MAX_PERIOD_DAYS + 1] {
+            assert!(matches!(set_retention_days(&db.pool, days).await, Err(DatabaseError::InvalidInput(_))));
+        }
+        assert_eq!(get_retention_days(&db.pool).await.unwrap(), 7);
+    }
+
+    // 删除的Key进入回收站，恢复后重新出现在列表中
+    #[tokio::test]
+    async fn test_delete_and_restore() {
+        let db = TestDatabase::open().await;
+        let key = insert_key(&db.pool, "main").await;
+
+        delete_api_key(&db.pool, &key.id).await.unwrap();
+        assert!(get_all_api_keys(&db.pool).await.unwrap().is_empty());
+        let trashed = list_trashed_api_keys(&db.pool).await.unwrap();
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].id, key.id);
+
+        restore_api_key(&db.pool, &key.id).await.unwrap();
+        assert_eq!(get_all_api_keys(&db.pool).await.unwrap().len(), 1);
+        assert!(list_trashed_api_keys(&db.pool).await.unwrap().is_empty());
+
+        // 不在回收站中的Key不能恢复
+        assert!(matches!(restore_api_key(&db.pool, &key.id).await, Err(DatabaseError::KeyNotFound)));
+    }
+
+    // 彻底删除时一并删除使用记录，未删除的Key不受影响
+    #[tokio::test]
+    async fn test_purge_trash() {
+        let db = TestDatabase::open().await;
+        let kept = insert_key(&db.pool, "kept").await;
+        let trashed = insert_key(&db.pool, "trashed").await;
+        record_usage(&db.pool, &trashed.id, "copy").await.unwrap();
+        delete_api_key(&db.pool, &trashed.id).await.unwrap();
+
+        assert_eq!(purge_trash(&db.pool, Some(&[])).await.unwrap(), 0);
+        assert_eq!(purge_trash(&db.pool, Some(std::slice::from_ref(&kept.id))).await.unwrap(), 0);
+        assert_eq!(purge_trash(&db.pool, None).await.unwrap(), 1);
+
+        assert!(list_trashed_api_keys(&db.pool).await.unwrap().is_empty());
+        assert_eq!(get_all_api_keys(&db.pool).await.unwrap().len(), 1);
+        let usage: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM usage_history")
+            .fetch_one(&db.pool)
+            .await
+            .unwrap();
+        assert_eq!(usage, 0);
+    }
+
+    // 只清理超过保留期限的Key；保留天数为 0 或异常大时不清理
+    #[tokio::test]
+    async fn test_purge_expired() {
+        let db = TestDatabase::open().await;
+        let now = chrono::Utc::now().timestamp();
+        let old = insert_key(&db.pool, "old").await;
+        let recent = insert_key(&db.pool, "recent").await;
+        set_deleted_at(&db.pool, &old.id, now - 31 * 24 * 60 * 60).await;
+        set_deleted_at(&db.pool, &recent.id, now - 60).await;
+
+        set_retention_days(&db.pool, 0).await.unwrap();
+        assert_eq!(purge_expired(&db.pool).await.unwrap(), 0);
+
+        set_setting(&db.pool, TRASH_RETENTION_SETTING, &i64::MAX.to_string()).await.unwrap();
+        assert_eq!(purge_expired(&db.pool).await.unwrap(), 0);
+
+        set_retention_days(&db.pool, 30).await.unwrap();
+        assert_eq!(purge_expired(&db.pool).await.unwrap(), 1);
+        let remaining = list_trashed_api_keys(&db.pool).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, recent.id);
+    }
+}
@@ -1,9 +1,35 @@
 use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use crate::database::error::DatabaseError;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-#[allow(dead_code)]
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[serde(rename_all = "camelCase")]
 pub struct UsageHistory {
     pub id: String,
+    #[serde(rename = "keyId")]
     pub key_id: String,
+    #[serde(rename = "usedAt")]
     pub used_at: i64,
-}
\ No newline at end of file
+    pub action: Option<String>,
+}
+
+// 记录一次使用事件，action 标识使用方式（如 copy、export）
+pub async fn record_usage(pool: &SqlitePool, key_id: &str, action: &str) -> Result<(), DatabaseError> {
+    sqlx::query("INSERT INTO usage_history (id, key_id, used_at, action) VALUES (?1, ?2, ?3, ?4)")
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(key_id)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(action)
+        .execute(pool)
+        .await
+        .map(|_| ())
+        .map_err(|e| DatabaseError::SqlxError(e.to_string()))
+}
+
+// 获取所有使用记录
+pub async fn get_all_usage_history(pool: &SqlitePool) -> Result<Vec<UsageHistory>, DatabaseError> {
+    sqlx::query_as::<_, UsageHistory>("SELECT id, key_id, COALESCE(used_at, 0) AS used_at, action FROM usage_history ORDER BY used_at")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| DatabaseError::SqlxError(e.to_string()))
+}
@@ -0,0 +1,146 @@
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use crate::database::error::DatabaseError;
+use crate::export::error::ExportError;
+use crate::importers::error::ImportError;
+use crate::validation::FieldError;
+
+// 所有 Tauri 命令统一返回的错误类型，序列化为 { code, message, fields? } 供前端按 code 区分
+#[derive(Debug, thiserror::Error)]
+pub enum AppError {
+    #[error("Database error: {0}")]
+    Database(String),
+    #[error("Database is not ready")]
+    DatabaseNotReady,
+    #[error("Database is corrupted: {0}")]
+    DatabaseCorrupted(String),
+    #[error("Too many failed attempts, try again in {0} seconds")]
+    TooManyAttempts(i64),
+    #[error("Vault is locked")]
+    VaultLocked,
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Validation failed: {0}")]
+    Validation(String),
+    #[error("Validation failed: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    InvalidFields(Vec<FieldError>),
+    #[error("Conflict: {0}")]
+    Conflict(String),
+    #[error("Encryption error: {0}")]
+    Encryption(String),
+    #[error("Decryption failed: {0}")]
+    Decryption(String),
+    #[error("Clipboard error: {0}")]
+    Clipboard(String),
+    #[error("IO error: {0}")]
+    Io(String),
+    #[error("Window error: {0}")]
+    Window(String),
+}
+
+impl AppError {
+    // 稳定的机器可读错误码，与前端 services/errors.ts 的 ErrorCode 保持一致
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Database(_) => "DATABASE_ERROR",
+            AppError::DatabaseNotReady => "DATABASE_NOT_READY",
+            AppError::DatabaseCorrupted(_) => "DATABASE_CORRUPTED",
+            AppError::VaultLocked => "VAULT_LOCKED",
+            AppError::TooManyAttempts(_) => "TOO_MANY_ATTEMPTS",
+            AppError::Unauthorized(_) => "UNAUTHORIZED",
+            AppError::Forbidden(_) => "FORBIDDEN",
+            AppError::NotFound(_) => "NOT_FOUND",
+            AppError::Validation(_) | AppError::InvalidFields(_) => "VALIDATION_FAILED",
+            AppError::Conflict(_) => "CONFLICT",
+            AppError::Encryption(_) => "ENCRYPTION_ERROR",
+            AppError::Decryption(_) => "DECRYPTION_ERROR",
+            AppError::Clipboard(_) => "CLIPBOARD_ERROR",
+            AppError::Io(_) => "IO_ERROR",
+            AppError::Window(_) => "WINDOW_ERROR",
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 3)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        match self {
+            AppError::InvalidFields(fields) => state.serialize_field("fields", fields)?,
+            _ => state.skip_field("fields")?,
+        }
+        // 尝试次数过多时告知前端需要等待的秒数
+        if let AppError::TooManyAttempts(retry_after) = self {
+            state.serialize_field("retryAfter", retry_after)?;
+        }
+        state.end()
+    }
+}
+
+impl From<DatabaseError> for AppError {
+    fn from(error: DatabaseError) -> Self {
+        match error {
+            DatabaseError::SqlxError(message) => AppError::Database(message),
+            DatabaseError::KeyNotFound => AppError::NotFound("API key".to_string()),
+            DatabaseError::InvalidInput(message) => AppError::Validation(message),
+            DatabaseError::Duplicate(message) | DatabaseError::Conflict(message) => {
+                AppError::Conflict(message)
+            }
+            DatabaseError::Corrupted(message) => AppError::DatabaseCorrupted(message),
+            DatabaseError::Io(message) => AppError::Io(message),
+            DatabaseError::NotReady => AppError::DatabaseNotReady,
+        }
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(error: sqlx::Error) -> Self {
+        DatabaseError::from(error).into()
+    }
+}
+
+impl From<ExportError> for AppError {
+    fn from(error: ExportError) -> Self {
+        match error {
+            ExportError::Io(message) => AppError::Io(message),
+            ExportError::Decryption => AppError::Decryption(ExportError::Decryption.to_string()),
+            ExportError::Encryption(message) => AppError::Encryption(message),
+            error @ (ExportError::InvalidFormat(_)
+            | ExportError::Serialization(_)
+            | ExportError::UnsupportedVersion(_)
+            | ExportError::InvalidInput(_)) => AppError::Validation(error.to_string()),
+        }
+    }
+}
+
+impl From<ImportError> for AppError {
+    fn from(error: ImportError) -> Self {
+        match error {
+            ImportError::Io(message) => AppError::Io(message),
+            error => AppError::Validation(error.to_string()),
+        }
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(error: std::io::Error) -> Self {
+        AppError::Io(error.to_string())
+    }
+}
+
+impl From<tauri::Error> for AppError {
+    fn from(error: tauri::Error) -> Self {
+        AppError::Window(error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(error: serde_json::Error) -> Self {
+        AppError::Validation(error.to_string())
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, thiserror::Error)]
+pub enum ExportError {
+    #[error("IO error: {0}")]
+    Io(String),
+    #[error("Invalid vault file: {0}")]
+    InvalidFormat(String),
+    #[error("Unsupported vault file version: {0}")]
+    UnsupportedVersion(u32),
+    #[error("Failed to decrypt vault file: wrong passphrase or corrupted data")]
+    Decryption,
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+    #[error("Encryption error: {0}")]
+    Encryption(String),
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+}
+
+impl From<std::io::Error> for ExportError {
+    fn from(error: std::io::Error) -> Self {
+        ExportError::Io(error.to_string())
+    }
+}
@@ -0,0 +1,339 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use crate::database::api_key::ApiKey;
+use crate::export::error::ExportError;
+use crate::providers::find_provider;
+
+// 明文导出格式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    // OPENAI_API_KEY=...
+    Env,
+    // export OPENAI_API_KEY='...'
+    Shell,
+    Json,
+    Yaml,
+    Csv,
+}
+
+// 未指定ID时用于筛选导出范围的条件
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportFilter {
+    pub keyword: Option<String>,
+    pub platform: Option<String>,
+    #[serde(rename = "groupId")]
+    pub group_id: Option<String>,
+    pub tag: Option<String>,
+}
+
+impl ExportFilter {
+    pub fn matches(&self, key: &ApiKey) -> bool {
+        let contains = |field: &Option<String>, needle: &str| {
+            field
+                .as_deref()
+                .map(|v| v.to_lowercase().contains(needle))
+                .unwrap_or(false)
+        };
+
+        if let Some(keyword) = self.keyword.as_deref().map(str::to_lowercase) {
+            if !key.name.to_lowercase().contains(&keyword)
+                && !contains(&key.platform, &keyword)
+                && !contains(&key.description, &keyword)
+            {
+                return false;
+            }
+        }
+        if let Some(platform) = &self.platform {
+            if !key.platform.as_deref().is_some_and(|p| p.eq_ignore_ascii_case(platform)) {
+                return false;
+            }
+        }
+        if let Some(group_id) = &self.group_id {
+            if key.group_id.as_deref() != Some(group_id.as_str()) {
+                return false;
+            }
+        }
+        if let Some(tag) = &self.tag {
+            let has_tag = key
+                .tags
+                .as_deref()
+                .unwrap_or_default()
+                .split(',')
+                .any(|t| t.trim().eq_ignore_ascii_case(tag));
+            if !has_tag {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+// 带有环境变量名的导出条目
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedKey<'a> {
+    pub name: &'a str,
+    pub env_var: String,
+    pub key_value: &'a str,
+    pub platform: Option<&'a str>,
+    pub description: Option<&'a str>,
+    pub tags: Option<&'a str>,
+}
+
+// 将任意文本转换为合法的环境变量名片段
+fn sanitize_env_name(raw: &str) -> String {
+    let mut name = String::new();
+    for c in raw.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            name.push(c.to_ascii_uppercase());
+        } else if !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+    let name = name.trim_matches('_').to_string();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+// 已经是环境变量形式的名称（如从 .env 导入的 OPENAI_API_KEY）直接沿用
+fn is_env_style(name: &str) -> bool {
+    !name.is_empty()
+        && name.starts_with(|c: char| c.is_ascii_uppercase() || c == '_')
+        && name.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+// 根据平台与名称生成环境变量名，发生冲突时依次追加名称与序号
+pub fn assign_env_var_names(keys: &[ApiKey]) -> Vec<String> {
+    let mut used: HashSet<String> = HashSet::new();
+    let mut names = Vec::with_capacity(keys.len());
+
+    for key in keys {
+        let name_part = sanitize_env_name(&key.name);
+        let platform_part = key.platform.as_deref().map(sanitize_env_name).unwrap_or_default();
+
+        let mut candidates = Vec::new();
+        if is_env_style(key.name.trim()) {
+            candidates.push(key.name.trim().to_string());
+        }
+        // 已知平台优先使用约定俗成的变量名，如 ANTHROPIC_API_KEY
+        if let Some(provider) = key.platform.as_deref().and_then(find_provider) {
+            candidates.push(provider.env_var.to_string());
+            if !name_part.is_empty() {
+                candidates.push(format!("{}_{}", provider.env_var, name_part));
+            }
+        } else if !platform_part.is_empty() {
+            candidates.push(format!("{}_API_KEY", platform_part));
+            if !name_part.is_empty() {
+                candidates.push(format!("{}_API_KEY_{}", platform_part, name_part));
+            }
+        }
+        if platform_part.is_empty() && !name_part.is_empty() {
+            candidates.push(name_part);
+        }
+        if candidates.is_empty() {
+            candidates.push("API_KEY".to_string());
+        }
+
+        let chosen = match candidates.iter().find(|c| !used.contains(*c)) {
+            Some(candidate) => candidate.clone(),
+            None => {
+                let base = candidates.last().cloned().unwrap_or_default();
+                (2..)
+                    .map(|n| format!("{}_{}", base, n))
+                    .find(|c| !used.contains(c))
+                    .unwrap_or(base)
+            }
+        };
+        used.insert(chosen.clone());
+        names.push(chosen);
+    }
+
+    names
+}
+
+// .env 取值：必要时加双引号并转义
+pub(crate) fn quote_env_value(value: &str) -> String {
+    let is_plain = value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "-_./+=:@,".contains(c));
+    if is_plain {
+        return value.to_string();
+    }
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('$', "\\$")
+        .replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
+
+// shell 取值：使用单引号，内部单引号按 '\'' 转义
+fn quote_shell_value(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+// YAML 标量：JSON 字符串同时也是合法的 YAML 双引号标量
+fn yaml_scalar(value: Option<&str>) -> String {
+    match value {
+        Some(v) => serde_json::to_string(v).unwrap_or_else(|_| "null".to_string()),
+        None => "null".to_string(),
+    }
+}
+
+// 按指定格式渲染导出内容
+pub fn render(keys: &[ApiKey], format: ExportFormat) -> Result<String, ExportError> {
+    let env_names = assign_env_var_names(keys);
+    let entries: Vec<ExportedKey> = keys
+        .iter()
+        .zip(env_names)
+        .map(|(key, env_var)| ExportedKey {
+            name: &key.name,
+            env_var,
+            key_value: &key.key_value,
+            platform: key.platform.as_deref(),
+            description: key.description.as_deref(),
+            tags: key.tags.as_deref(),
+        })
+        .collect();
+
+    let content = match format {
+        ExportFormat::Env => entries
+            .iter()
+            .map(|e| format!("{}={}\n", e.env_var, quote_env_value(e.key_value)))
+            .collect(),
+        ExportFormat::Shell => entries
+            .iter()
+            .map(|e| format!("export {}={}\n", e.env_var, quote_shell_value(e.key_value)))
+            .collect(),
+        ExportFormat::Json => serde_json::to_string_pretty(&entries)
+            .map_err(|e| ExportError::Serialization(e.to_string()))?,
+        ExportFormat::Yaml => {
+            let mut out = String::from("keys:\n");
+            for e in &entries {
+                out.push_str(&format!("  - name: {}\n", yaml_scalar(Some(e.name))));
+                out.push_str(&format!("    envVar: {}\n", yaml_scalar(Some(&e.env_var))));
+                out.push_str(&format!("    keyValue: {}\n", yaml_scalar(Some(e.key_value))));
+                out.push_str(&format!("    platform: {}\n", yaml_scalar(e.platform)));
+                out.push_str(&format!("    description: {}\n", yaml_scalar(e.description)));
+                out.push_str(&format!("    tags: {}\n", yaml_scalar(e.tags)));
+            }
+            if entries.is_empty() {
+                out = "keys: []\n".to_string();
+            }
+            out
+        }
+        ExportFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            writer
+                .write_record(["name", "env_var", "key_value", "platform", "description", "tags"])
+                .map_err(|e| ExportError::Io(e.to_string()))?;
+            for e in &entries {
+                writer
+                    .write_record([
+                        e.name,
+                        &e.env_var,
+                        e.key_value,
+                        e.platform.unwrap_or_default(),
+                        e.description.unwrap_or_default(),
+                        e.tags.unwrap_or_default(),
+                    ])
+                    .map_err(|e| ExportError::Io(e.to_string()))?;
+            }
+            let bytes = writer.into_inner().map_err(|e| ExportError::Io(e.to_string()))?;
+            String::from_utf8(bytes).map_err(|e| ExportError::Io(e.to_string()))?
+        }
+    };
+
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(name: &str, value: &str, platform: Option<&str>) -> ApiKey {
+        ApiKey::new(name.into(), value.into(), platform.map(str::to_string), None, None, None)
+    }
+
+    // 变量名片段只保留大写字母、数字与下划线，不以数字开头
+    #[test]
+    fn test_sanitize_env_name() {
+        assert_eq!(sanitize_env_name(" my key! "), "MY_KEY");
+        assert_eq!(sanitize_env_name("1st--key"), "_1ST_KEY");
+        assert_eq!(sanitize_env_name("***"), "");
+    }
+
+    // 优先沿用环境变量形式的名称与平台约定变量名，冲突时追加名称与序号
+    #[test]
+    fn test_assign_env_var_names() {
+        let keys = vec![
+            key("OPENAI_API_KEY", "v1", None),
+            key("prod", "v2", Some("openai")),
+            key("prod", "v3", Some("openai")),
+            key("main", "v4", Some("My Service")),
+            key("my key!", "v5", None),
+            key("", "v6", None),
+        ];
+        assert_eq!(
+            assign_env_var_names(&keys),
+            vec![
+                "OPENAI_API_KEY",
+                "OPENAI_API_KEY_PROD",
+                "OPENAI_API_KEY_PROD_2",
+                "MY_SERVICE_API_KEY",
+                "MY_KEY",
+                "API_KEY",
+            ]
+        );
+    }
+
+    // .env 与 shell 取值按需加引号并转义
+    #[test]
+    fn test_quote_values() {
+        assert_eq!(quote_env_value("sk-abc_123"), "sk-abc_123");
+        assert_eq!(quote_env_value("a b\"$\\\n"), r#""a b\"\$\\\n""#);
+        assert_eq!(quote_shell_value("it's"), r"'it'\''s'");
+    }
+
+    // 各格式的渲染结果
+    #[test]
+    fn test_render_formats() {
+        let keys = vec![key("main", "it's secret", Some("anthropic"))];
+
+        assert_eq!(render(&keys, ExportFormat::Env).unwrap(), "ANTHROPIC_API_KEY=\"it's secret\"\n");
+        assert_eq!(render(&keys, ExportFormat::Shell).unwrap(), "export ANTHROPIC_API_KEY='it'\\''s secret'\n");
+
+        let json: serde_json::Value = serde_json::from_str(&render(&keys, ExportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json[0]["envVar"], "ANTHROPIC_API_KEY");
+        assert_eq!(json[0]["keyValue"], "it's secret");
+
+        let yaml = render(&keys, ExportFormat::Yaml).unwrap();
+        assert!(yaml.starts_with("keys:\n  - name: \"main\"\n"));
+        assert!(yaml.contains("    description: null\n"));
+        assert_eq!(render(&[], ExportFormat::Yaml).unwrap(), "keys: []\n");
+
+        let csv = render(&keys, ExportFormat::Csv).unwrap();
+        assert_eq!(
+            csv,
+            "name,env_var,key_value,platform,description,tags\nmain,ANTHROPIC_API_KEY,it's secret,anthropic,,\n"
+        );
+    }
+
+    // 筛选条件全部满足时才导出
+    #[test]
+    fn test_export_filter() {
+        let mut api_key = key("Prod Key", "v", Some("openai"));
+        api_key.tags = Some("work, billing".into());
+
+        assert!(ExportFilter::default().matches(&api_key));
+        assert!(ExportFilter { keyword: Some("prod".into()), ..Default::default() }.matches(&api_key));
+        assert!(ExportFilter { platform: Some("OpenAI".into()), tag: Some("Billing".into()), ..Default::default() }.matches(&api_key));
+        assert!(!ExportFilter { tag: Some("bill".into()), ..Default::default() }.matches(&api_key));
+        assert!(!ExportFilter { group_id: Some("g1".into()), ..Default::default() }.matches(&api_key));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod error;
+pub mod formats;
+pub mod vault_file;
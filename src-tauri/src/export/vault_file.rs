@@ -0,0 +1,204 @@
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use crate::database::snapshot::VaultSnapshot;
+use crate::export::error::ExportError;
+use crate::security;
+
+// 文件格式标识与当前版本
+pub const VAULT_FILE_FORMAT: &str = "api-key-manager-vault";
+pub const VAULT_FILE_VERSION: u32 = 1;
+const KDF_ALGORITHM: &str = "argon2id";
+const CIPHER: &str = "aes-256-gcm";
+
+// 导出时使用的 Argon2id 参数（与 argon2 crate 默认值一致）
+const DEFAULT_MEMORY_KIB: u32 = 19 * 1024;
+const DEFAULT_ITERATIONS: u32 = 2;
+const DEFAULT_PARALLELISM: u32 = 1;
+// 导入时允许的参数上限，防止构造的文件耗尽内存或CPU
+const MAX_MEMORY_KIB: u32 = 1024 * 1024;
+const MAX_ITERATIONS: u32 = 64;
+const MAX_PARALLELISM: u32 = 16;
+
+const MIN_PASSPHRASE_LEN: usize = 8;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct KdfParams {
+    pub algorithm: String,
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    // Base64 编码的随机盐值
+    pub salt: String,
+}
+
+// 明文文件头，描述如何解密文件正文
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultFileHeader {
+    pub format: String,
+    pub version: u32,
+    pub created_at: i64,
+    pub kdf: KdfParams,
+    pub cipher: String,
+    pub key_count: usize,
+    pub includes_usage_history: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct VaultFile {
+    header: VaultFileHeader,
+    // security::encrypt_data 输出：Base64(nonce || 密文 || GCM标签)
+    body: String,
+}
+
+// 加密正文，内含文件头关键字段的副本用于校验文件头未被替换
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VaultPayload {
+    format: String,
+    version: u32,
+    created_at: i64,
+    snapshot: VaultSnapshot,
+}
+
+// 将快照加密写入保险库文件
+pub fn write_vault_file(
+    path: &Path,
+    passphrase: &str,
+    snapshot: &VaultSnapshot,
+) -> Result<VaultFileHeader, ExportError> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(ExportError::InvalidInput(format!(
+            "Passphrase must be at least {} characters",
+            MIN_PASSPHRASE_LEN
+        )));
+    }
+
+    let salt = security::generate_salt();
+    let header = VaultFileHeader {
+        format: VAULT_FILE_FORMAT.to_string(),
+        version: VAULT_FILE_VERSION,
+        created_at: chrono::Utc::now().timestamp(),
+        kdf: KdfParams {
+            algorithm: KDF_ALGORITHM.to_string(),
+            memory_kib: DEFAULT_MEMORY_KIB,
+            iterations: DEFAULT_ITERATIONS,
+            parallelism: DEFAULT_PARALLELISM,
+            salt: general_purpose::STANDARD.encode(salt),
+        },
+        cipher: CIPHER.to_string(),
+        key_count: snapshot.api_keys.len(),
+        includes_usage_history: snapshot.usage_history.is_some(),
+    };
+
+    let payload = VaultPayload {
+        format: header.format.clone(),
+        version: header.version,
+        created_at: header.created_at,
+        snapshot: snapshot.clone(),
+    };
+    let plaintext = serde_json::to_string(&payload)
+        .map_err(|e| ExportError::Serialization(e.to_string()))?;
+
+    let key = security::derive_key(
+        passphrase,
+        &salt,
+        header.kdf.memory_kib,
+        header.kdf.iterations,
+        header.kdf.parallelism,
+    )
+    .map_err(ExportError::Encryption)?;
+    let body = security::encrypt_data(&plaintext, &key).map_err(ExportError::Encryption)?;
+
+    let file = VaultFile {
+        header: header.clone(),
+        body,
+    };
+    let content = serde_json::to_string_pretty(&file)
+        .map_err(|e| ExportError::Serialization(e.to_string()))?;
+
+    // 先写临时文件再重命名，避免中途失败留下不完整的文件；在完整文件名后追加 .tmp，目标本身是 .tmp 时也不会重名
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+    security::write_private_file(&tmp_path, &content)?;
+    std::fs::rename(&tmp_path, path)?;
+
+    Ok(header)
+}
+
+// 读取并校验文件头，不需要口令
+pub fn read_vault_header(path: &Path) -> Result<VaultFileHeader, ExportError> {
+    Ok(read_vault_envelope(path)?.header)
+}
+
+fn read_vault_envelope(path: &Path) -> Result<VaultFile, ExportError> {
+    let content = std::fs::read_to_string(path)?;
+    let file: VaultFile = serde_json::from_str(&content)
+        .map_err(|e| ExportError::InvalidFormat(e.to_string()))?;
+
+    let header = &file.header;
+    if header.format != VAULT_FILE_FORMAT {
+        return Err(ExportError::InvalidFormat(format!("Unknown format '{}'", header.format)));
+    }
+    if header.version > VAULT_FILE_VERSION {
+        return Err(ExportError::UnsupportedVersion(header.version));
+    }
+    if header.cipher != CIPHER || header.kdf.algorithm != KDF_ALGORITHM {
+        return Err(ExportError::InvalidFormat(format!(
+            "Unsupported cipher '{}' or KDF '{}'",
+            header.cipher, header.kdf.algorithm
+        )));
+    }
+    if header.kdf.memory_kib > MAX_MEMORY_KIB
+        || header.kdf.iterations > MAX_ITERATIONS
+        || header.kdf.parallelism > MAX_PARALLELISM
+    {
+        return Err(ExportError::InvalidFormat("KDF parameters out of range".to_string()));
+    }
+
+    Ok(file)
+}
+
+// 解密保险库文件并校验完整性，成功后才返回快照
+pub fn read_vault_file(
+    path: &Path,
+    passphrase: &str,
+) -> Result<(VaultFileHeader, VaultSnapshot), ExportError> {
+    let file = read_vault_envelope(path)?;
+    let header = file.header;
+
+    let salt = general_purpose::STANDARD
+        .decode(&header.kdf.salt)
+        .map_err(|e| ExportError::InvalidFormat(format!("Invalid salt: {}", e)))?;
+    let key = security::derive_key(
+        passphrase,
+        &salt,
+        header.kdf.memory_kib,
+        header.kdf.iterations,
+        header.kdf.parallelism,
+    )
+    .map_err(ExportError::InvalidFormat)?;
+
+    // AES-GCM 认证失败即说明口令错误或文件被篡改
+    let plaintext = security::decrypt_data(&file.body, &key).map_err(|_| ExportError::Decryption)?;
+    let payload: VaultPayload = serde_json::from_str(&plaintext)
+        .map_err(|e| ExportError::InvalidFormat(format!("Invalid payload: {}", e)))?;
+
+    if payload.format != header.format
+        || payload.version != header.version
+        || payload.created_at != header.created_at
+        || payload.snapshot.api_keys.len() != header.key_count
+    {
+        return Err(ExportError::InvalidFormat("Header does not match encrypted payload".to_string()));
+    }
+
+    payload
+        .snapshot
+        .validate()
+        .map_err(|e| ExportError::InvalidFormat(e.to_string()))?;
+
+    Ok((header, payload.snapshot))
+}
@@ -0,0 +1,249 @@
+mod server;
+
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::sync::Mutex;
+use tauri::Manager;
+use tokio::sync::oneshot;
+use crate::database::api_token::{insert_api_token, ApiToken};
+use crate::database::settings::{get_setting, set_setting};
+use crate::error::AppError;
+use crate::security::{generate_token, hash_password};
+use crate::AppState;
+
+// 令牌前缀，完整令牌为 akm_<令牌ID>_<随机串>
+pub const TOKEN_PREFIX: &str = "akm_";
+
+// 正在运行的服务：监听端口与停止信号
+struct RunningServer {
+    port: u16,
+    shutdown: oneshot::Sender<()>,
+}
+
+static SERVER: Mutex<Option<RunningServer>> = Mutex::new(None);
+
+// 本地 HTTP API 配置，保存在 settings 表中
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpApiConfig {
+    pub enabled: bool,
+    pub port: u16,
+    // 每个令牌每分钟允许的请求数
+    pub rate_limit_per_minute: u32,
+}
+
+impl Default for HttpApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 47615,
+            rate_limit_per_minute: 60,
+        }
+    }
+}
+
+// 返回给前端的服务状态
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpApiStatus {
+    pub config: HttpApiConfig,
+    // 正在监听的端口，未运行时为空
+    pub port: Option<u16>,
+}
+
+// 新建令牌的结果，secret 只返回这一次
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IssuedApiToken {
+    pub token: ApiToken,
+    pub secret: String,
+}
+
+// 读取服务配置，缺失的项使用默认值
+pub async fn load_config(pool: &SqlitePool) -> Result<HttpApiConfig, AppError> {
+    let defaults = HttpApiConfig::default();
+    Ok(HttpApiConfig {
+        enabled: get_setting(pool, "http_api_enabled")
+            .await?
+            .map(|v| v == "true")
+            .unwrap_or(defaults.enabled),
+        port: get_setting(pool, "http_api_port")
+            .await?
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(defaults.port),
+        rate_limit_per_minute: get_setting(pool, "http_api_rate_limit_per_minute")
+            .await?
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(defaults.rate_limit_per_minute),
+    })
+}
+
+// 保存服务配置
+pub async fn save_config(pool: &SqlitePool, config: &HttpApiConfig) -> Result<(), AppError> {
+    if config.port == 0 {
+        return Err(AppError::Validation("Port must be between 1 and 65535".to_string()));
+    }
+    let values = [
+        ("http_api_enabled", config.enabled.to_string()),
+        ("http_api_port", config.port.to_string()),
+        ("http_api_rate_limit_per_minute", config.rate_limit_per_minute.max(1).to_string()),
+    ];
+    for (key, value) in values {
+        set_setting(pool, key, &value).await?;
+    }
+    Ok(())
+}
+
+// 创建令牌：生成随机串并只保存其哈希
+pub async fn issue_token(
+    pool: &SqlitePool,
+    name: &str,
+    group_ids: Vec<String>,
+    tags: Vec<String>,
+    allow_secrets: bool,
+) -> Result<IssuedApiToken, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("Token name cannot be empty".to_string()));
+    }
+    let join = |values: Vec<String>| {
+        let values: Vec<String> = values
+            .iter()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .collect();
+        (!values.is_empty()).then(|| values.join(","))
+    };
+
+    let id = uuid::Uuid::new_v4().simple().to_string();
+    let random = generate_token();
+    let token_hash = hash_password(&random).map_err(AppError::Encryption)?;
+    let token = ApiToken {
+        id: id.clone(),
+        name: name.to_string(),
+        token_hash,
+        group_ids: join(group_ids),
+        tags: join(tags),
+        allow_secrets,
+        created_at: chrono::Utc::now().timestamp(),
+        last_used_at: None,
+    };
+    insert_api_token(pool, &token).await?;
+
+    Ok(IssuedApiToken {
+        token,
+        secret: format!("{}{}_{}", TOKEN_PREFIX, id, random),
+    })
+}
+
+// 拆分完整令牌为 (令牌ID, 随机串)
+pub(crate) fn parse_token(token: &str) -> Option<(&str, &str)> {
+    let (id, random) = token.strip_prefix(TOKEN_PREFIX)?.split_once('_')?;
+    (!id.is_empty() && !random.is_empty()).then_some((id, random))
+}
+
+// 正在监听的端口
+pub fn running_port() -> Option<u16> {
+    SERVER.lock().ok()?.as_ref().map(|s| s.port)
+}
+
+// 按配置启动服务，已在运行时先停止；只监听 127.0.0.1
+pub async fn start(app_handle: &tauri::AppHandle, config: &HttpApiConfig) -> Result<u16, AppError> {
+    stop();
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", config.port))
+        .await
+        .map_err(|e| AppError::Conflict(format!("Cannot listen on 127.0.0.1:{}: {}", config.port, e)))?;
+    let port = listener.local_addr()?.port();
+
+    let (shutdown, receiver) = oneshot::channel();
+    if let Ok(mut server) = SERVER.lock() {
+        *server = Some(RunningServer { port, shutdown });
+    }
+    let app = app_handle.clone();
+    let rate_limit = config.rate_limit_per_minute.max(1);
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = server::serve(app, listener, rate_limit, receiver).await {
+            eprintln!("HTTP API stopped: {}", e);
+        }
+    });
+
+    println!("HTTP API listening on 127.0.0.1:{}", port);
+    Ok(port)
+}
+
+// 停止服务，返回之前是否在运行
+pub fn stop() -> bool {
+    let running = SERVER.lock().ok().and_then(|mut server| server.take());
+    match running {
+        Some(server) => {
+            let _ = server.shutdown.send(());
+            println!("HTTP API on port {} stopped", server.port);
+            true
+        }
+        None => false,
+    }
+}
+
+// 数据库就绪后按配置决定是否启动服务
+pub fn spawn_server(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+        state.wait_for_status().await;
+        let Ok(pool) = state.pool() else {
+            return;
+        };
+        match load_config(&pool).await {
+            Ok(config) if config.enabled => {
+                if let Err(e) = start(&app_handle, &config).await {
+                    eprintln!("Failed to start HTTP API: {}", e);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("Failed to load HTTP API config: {}", e),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::TestDatabase;
+    use crate::security::verify_password;
+
+    #[test]
+    fn test_parse_token() {
+        assert_eq!(parse_token("akm_abc123_random"), Some(("abc123", "random")));
+        // 随机串中的下划线属于随机串
+        assert_eq!(parse_token("akm_abc123_ran_dom"), Some(("abc123", "ran_dom")));
+        assert_eq!(parse_token("abc123_random"), None);
+        assert_eq!(parse_token("akm_abc123"), None);
+        assert_eq!(parse_token("akm__random"), None);
+        assert_eq!(parse_token("akm_abc123_"), None);
+        assert_eq!(parse_token(""), None);
+    }
+
+    #[tokio::test]
+    async fn test_issue_token() {
+        // 返回的令牌可以拆分，只保存随机串的哈希，访问范围去掉空白与空项
+        let db = TestDatabase::open().await;
+        let issued = issue_token(
+            &db.pool,
+            " ci ",
+            vec![" group-1 ".to_string(), String::new()],
+            vec![],
+            false,
+        )
+        .await
+        .unwrap();
+
+        let (id, random) = parse_token(&issued.secret).unwrap();
+        assert_eq!(id, issued.token.id);
+        assert_eq!(issued.token.name, "ci");
+        assert_eq!(issued.token.group_ids.as_deref(), Some("group-1"));
+        assert_eq!(issued.token.tags, None);
+        assert!(!issued.token.token_hash.contains(random));
+        assert!(verify_password(random, &issued.token.token_hash).unwrap());
+
+        assert!(issue_token(&db.pool, "  ", vec![], vec![], false).await.is_err());
+    }
+}
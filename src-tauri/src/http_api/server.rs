@@ -0,0 +1,387 @@
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::Manager;
+use tokio::sync::oneshot;
+use crate::database::api_key::{find_api_key, get_all_api_keys, ApiKey};
+use crate::database::api_token::{get_api_token, touch_api_token, ApiToken};
+use crate::database::audit::{record_audit, AuditEntry};
+use crate::database::error::DatabaseError;
+use crate::database::usage_history::record_usage;
+use crate::error::AppError;
+use crate::export::formats::ExportFilter;
+use crate::http_api::parse_token;
+use crate::security::{constant_time_eq, verify_password};
+use crate::AppState;
+
+// 审计日志中的请求来源
+const AUDIT_SOURCE: &str = "http";
+// 限流的时间窗口
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+// 每个令牌每分钟允许的认证失败次数，超过后暂停对该令牌计算 Argon2（开销较大）
+const AUTH_FAILURE_LIMIT: u32 = 10;
+
+// 固定窗口限流
+struct RateLimiter {
+    limit: u32,
+    hits: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+    fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            hits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // 计入一次请求；超出限制时返回需要等待的秒数
+    fn hit(&self, bucket: &str) -> Result<(), u64> {
+        let Ok(mut hits) = self.hits.lock() else {
+            return Ok(());
+        };
+        let now = Instant::now();
+        hits.retain(|_, (start, _)| now.duration_since(*start) < RATE_WINDOW);
+        let (start, count) = hits.entry(bucket.to_string()).or_insert((now, 0));
+        if *count >= self.limit {
+            return Err((RATE_WINDOW - now.duration_since(*start)).as_secs().max(1));
+        }
+        *count += 1;
+        Ok(())
+    }
+
+    // 当前窗口是否已用完，不计入请求
+    fn blocked(&self, bucket: &str) -> Option<u64> {
+        let hits = self.hits.lock().ok()?;
+        let (start, count) = hits.get(bucket)?;
+        let elapsed = start.elapsed();
+        (elapsed < RATE_WINDOW && *count >= self.limit).then(|| (RATE_WINDOW - elapsed).as_secs().max(1))
+    }
+}
+
+#[derive(Clone)]
+struct ServerState {
+    app: tauri::AppHandle,
+    port: u16,
+    requests: Arc<RateLimiter>,
+    failures: Arc<RateLimiter>,
+    // 已校验过的令牌：哈希 -> 随机串的 SHA-256，避免每个请求都重新计算 Argon2
+    verified: Arc<Mutex<HashMap<String, String>>>,
+}
+
+// HTTP 错误：状态码按 AppError::code 映射，响应体与前端的 { code, message } 一致
+enum ApiError {
+    App(AppError),
+    RateLimited(u64),
+}
+
+impl From<AppError> for ApiError {
+    fn from(error: AppError) -> Self {
+        ApiError::App(error)
+    }
+}
+
+impl From<DatabaseError> for ApiError {
+    fn from(error: DatabaseError) -> Self {
+        ApiError::App(error.into())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let error = match self {
+            ApiError::RateLimited(retry_after) => {
+                let body = json!({ "code": "RATE_LIMITED", "message": "Too many requests" });
+                return (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    [(header::RETRY_AFTER, retry_after.to_string())],
+                    Json(body),
+                )
+                    .into_response();
+            }
+            ApiError::App(error) => error,
+        };
+        let status = match &error {
+            AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Validation(_) | AppError::InvalidFields(_) => StatusCode::BAD_REQUEST,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::VaultLocked => StatusCode::LOCKED,
+            AppError::DatabaseNotReady => StatusCode::SERVICE_UNAVAILABLE,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        let mut response = (status, Json(&error)).into_response();
+        if status == StatusCode::UNAUTHORIZED {
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, header::HeaderValue::from_static("Bearer"));
+        }
+        response
+    }
+}
+
+// 不含 key_value 的Key信息
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyMetadata<'a> {
+    id: &'a str,
+    name: &'a str,
+    platform: Option<&'a str>,
+    description: Option<&'a str>,
+    #[serde(rename = "groupId")]
+    group_id: Option<&'a str>,
+    tags: Option<&'a str>,
+    #[serde(rename = "createdAt")]
+    created_at: i64,
+    #[serde(rename = "updatedAt")]
+    updated_at: i64,
+    #[serde(rename = "lastUsedAt")]
+    last_used_at: Option<i64>,
+    #[serde(rename = "expiresAt")]
+    expires_at: Option<i64>,
+}
+
+impl<'a> From<&'a ApiKey> for KeyMetadata<'a> {
+    fn from(key: &'a ApiKey) -> Self {
+        Self {
+            id: &key.id,
+            name: &key.name,
+            platform: key.platform.as_deref(),
+            description: key.description.as_deref(),
+            group_id: key.group_id.as_deref(),
+            tags: key.tags.as_deref(),
+            created_at: key.created_at,
+            updated_at: key.updated_at,
+            last_used_at: key.last_used_at,
+            expires_at: key.expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchQuery {
+    q: Option<String>,
+    platform: Option<String>,
+    tag: Option<String>,
+}
+
+// 运行服务直到收到停止信号
+pub(super) async fn serve(
+    app: tauri::AppHandle,
+    listener: tokio::net::TcpListener,
+    rate_limit: u32,
+    shutdown: oneshot::Receiver<()>,
+) -> Result<(), AppError> {
+    let state = ServerState {
+        app,
+        port: listener.local_addr()?.port(),
+        requests: Arc::new(RateLimiter::new(rate_limit)),
+        failures: Arc::new(RateLimiter::new(AUTH_FAILURE_LIMIT)),
+        verified: Arc::new(Mutex::new(HashMap::new())),
+    };
+    let router = Router::new()
+        .route("/v1/status", get(status))
+        .route("/v1/keys", get(search_keys))
+        .route("/v1/keys/:key", get(key_metadata))
+        .route("/v1/keys/:key/secret", get(key_secret))
+        .layer(middleware::from_fn_with_state(state.clone(), check_host))
+        .with_state(state);
+
+    axum::serve(listener, router)
+        .with_graceful_shutdown(async {
+            let _ = shutdown.await;
+        })
+        .await?;
+    Ok(())
+}
+
+// 只接受 Host 为本机地址的请求，防止网页通过 DNS 重绑定访问
+async fn check_host(State(state): State<ServerState>, request: Request, next: Next) -> Response {
+    let host = request
+        .headers()
+        .get(header::HOST)
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or_default();
+    let allowed = [
+        format!("127.0.0.1:{}", state.port),
+        format!("localhost:{}", state.port),
+    ];
+    if !allowed.iter().any(|a| a.eq_ignore_ascii_case(host)) {
+        return ApiError::from(AppError::Forbidden(format!("Host {} is not allowed", host))).into_response();
+    }
+    next.run(request).await
+}
+
+// 校验 Bearer 令牌并计入限流，成功时返回连接池与令牌
+async fn authenticate(state: &ServerState, headers: &HeaderMap) -> Result<(SqlitePool, ApiToken), ApiError> {
+    let pool = state.app.state::<AppState>().pool()?;
+
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .map(str::trim);
+    let token = match bearer.and_then(parse_token) {
+        Some((id, random)) => match get_api_token(&pool, id).await? {
+            Some(token) => verify(state, &token, random).await?.then_some(token),
+            None => None,
+        },
+        None => None,
+    };
+    let Some(token) = token else {
+        return Err(AppError::Unauthorized("Missing or invalid access token".to_string()).into());
+    };
+
+    state.requests.hit(&token.id).map_err(ApiError::RateLimited)?;
+    if let Err(e) = touch_api_token(&pool, &token.id).await {
+        eprintln!("Failed to update token {}: {}", token.id, e);
+    }
+    state.app.state::<AppState>().session.ensure_unlocked(&pool).await?;
+    Ok((pool, token))
+}
+
+// 校验令牌的随机串：校验过的令牌只比对 SHA-256 摘要；未校验过的计算 Argon2，
+// 同一令牌失败过多时暂停计算，已校验过的正确令牌不受影响。不存在的令牌ID不会计算 Argon2
+async fn verify(state: &ServerState, token: &ApiToken, random: &str) -> Result<bool, ApiError> {
+    let digest = format!("{:x}", Sha256::digest(random.as_bytes()));
+    let cached = state
+        .verified
+        .lock()
+        .ok()
+        .and_then(|v| v.get(&token.token_hash).cloned());
+    if let Some(cached) = cached {
+        let valid = constant_time_eq(&cached, &digest);
+        if !valid {
+            let _ = state.failures.hit(&token.id);
+        }
+        return Ok(valid);
+    }
+    if let Some(retry_after) = state.failures.blocked(&token.id) {
+        return Err(ApiError::RateLimited(retry_after));
+    }
+
+    let (hash, candidate) = (token.token_hash.clone(), random.to_string());
+    let valid = tokio::task::spawn_blocking(move || verify_password(&candidate, &hash).unwrap_or(false))
+        .await
+        .unwrap_or(false);
+    if valid {
+        if let Ok(mut verified) = state.verified.lock() {
+            verified.insert(token.token_hash.clone(), digest);
+        }
+    } else {
+        let _ = state.failures.hit(&token.id);
+    }
+    Ok(valid)
+}
+
+// 查找令牌范围内的Key；范围外的Key与不存在的Key一样返回 404
+async fn find_scoped_key(pool: &SqlitePool, token: &ApiToken, key: &str) -> Result<ApiKey, ApiError> {
+    let not_found = || AppError::NotFound(format!("API key {}", key));
+    match find_api_key(pool, key).await {
+        Ok(api_key) if token.allows(&api_key) => Ok(api_key),
+        Ok(_) | Err(DatabaseError::KeyNotFound) => Err(not_found().into()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+// GET /v1/status：无需令牌，用于检测服务是否可用
+async fn status(State(state): State<ServerState>) -> Json<serde_json::Value> {
+    let app_state = state.app.state::<AppState>();
+    let pool = app_state.pool().ok();
+    let locked = match &pool {
+        Some(pool) => app_state.session.ensure_unlocked(pool).await.is_err(),
+        None => true,
+    };
+    Json(json!({ "version": 1, "ready": pool.is_some(), "locked": locked }))
+}
+
+// GET /v1/keys?q=&platform=&tag=：搜索令牌范围内的Key
+async fn search_keys(
+    State(state): State<ServerState>,
+    headers: HeaderMap,
+    Query(query): Query<SearchQuery>,
+) -> Result<Response, ApiError> {
+    let (pool, token) = authenticate(&state, &headers).await?;
+    let filter = ExportFilter {
+        keyword: query.q.filter(|q| !q.trim().is_empty()),
+        platform: query.platform,
+        tag: query.tag,
+        ..Default::default()
+    };
+    let keys = get_all_api_keys(&pool).await?;
+    let keys: Vec<KeyMetadata> = keys
+        .iter()
+        .filter(|k| token.allows(k) && filter.matches(k))
+        .map(KeyMetadata::from)
+        .collect();
+    Ok(Json(keys).into_response())
+}
+
+// GET /v1/keys/{id或名称}：Key的元数据
+async fn key_metadata(
+    State(state): State<ServerState>,
+    headers: HeaderMap,
+    Path(key): Path<String>,
+) -> Result<Response, ApiError> {
+    let (pool, token) = authenticate(&state, &headers).await?;
+    let api_key = find_scoped_key(&pool, &token, &key).await?;
+    Ok(Json(KeyMetadata::from(&api_key)).into_response())
+}
+
+// GET /v1/keys/{id或名称}/secret：Key的值，令牌需允许读取值
+async fn key_secret(
+    State(state): State<ServerState>,
+    headers: HeaderMap,
+    Path(key): Path<String>,
+) -> Result<Response, ApiError> {
+    let (pool, token) = authenticate(&state, &headers).await?;
+    let result = async {
+        if !token.allow_secrets {
+            return Err(ApiError::from(AppError::Forbidden(format!(
+                "Token {} may only read metadata",
+                token.name
+            ))));
+        }
+        find_scoped_key(&pool, &token, &key).await
+    }
+    .await;
+
+    let mut entry = AuditEntry::new(
+        AUDIT_SOURCE,
+        "get",
+        match &result {
+            Ok(_) => "allowed",
+            Err(ApiError::App(AppError::Forbidden(_))) => "denied",
+            Err(_) => "failed",
+        },
+    );
+    entry.target = Some(key.clone());
+    entry.key_id = result.as_ref().ok().map(|k| k.id.clone());
+    entry.detail = Some(format!("token {}", token.name));
+    if let Err(e) = record_audit(&pool, &entry).await {
+        eprintln!("Failed to write audit log: {}", e);
+    }
+
+    let api_key = result?;
+    if let Err(e) = record_usage(&pool, &api_key.id, "http").await {
+        eprintln!("Failed to record usage for {}: {}", api_key.id, e);
+    }
+    Ok(Json(json!({
+        "id": api_key.id,
+        "name": api_key.name,
+        "platform": api_key.platform,
+        "keyValue": api_key.key_value,
+    }))
+    .into_response())
+}
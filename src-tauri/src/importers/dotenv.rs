@@ -0,0 +1,111 @@
+use super::{error::ImportError, ImportedKey};
+
+// 从左到右一次处理双引号值中的转义，\\n 不会被当作换行
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('"') => result.push('"'),
+            Some('\\') => result.push('\\'),
+            // 其他转义原样保留
+            Some(other) => {
+                result.push('\\');
+                result.push(other);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+// 解析 .env 文件中的一行，返回变量名与值
+fn parse_line(line: &str) -> Option<(&str, String)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let line = line.strip_prefix("export ").unwrap_or(line).trim_start();
+    let (name, raw_value) = line.split_once('=')?;
+    let name = name.trim();
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+
+    let raw_value = raw_value.trim();
+    let value = if let Some(inner) = raw_value.strip_prefix('"') {
+        // 双引号：支持常见转义
+        let inner = inner.rsplit_once('"').map(|(v, _)| v).unwrap_or(inner);
+        unescape(inner)
+    } else if let Some(inner) = raw_value.strip_prefix('\'') {
+        // 单引号：原样保留
+        inner.rsplit_once('\'').map(|(v, _)| v).unwrap_or(inner).to_string()
+    } else {
+        // 未加引号：去掉行内注释
+        raw_value
+            .split_once(" #")
+            .map(|(v, _)| v)
+            .unwrap_or(raw_value)
+            .trim()
+            .to_string()
+    };
+
+    Some((name, value))
+}
+
+// 解析 .env 文件
+pub fn parse(content: &str) -> Result<Vec<ImportedKey>, ImportError> {
+    let results = content
+        .lines()
+        .filter_map(parse_line)
+        .filter(|(_, value)| !value.trim().is_empty())
+        .map(|(name, value)| ImportedKey::new(name, &value, None, &[]))
+        .collect();
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 双引号值中的转义从左到右处理，\\n 得到反斜杠加 n
+    #[test]
+    fn test_unescape() {
+        assert_eq!(unescape(r#"a\nb"#), "a\nb");
+        assert_eq!(unescape(r#"a\\nb"#), "a\\nb");
+        assert_eq!(unescape(r#"say \"hi\""#), "say \"hi\"");
+        assert_eq!(unescape(r#"keep\t"#), "keep\\t");
+        assert_eq!(unescape("end\\"), "end\\");
+    }
+
+    // 支持 export 前缀、引号、行内注释，跳过注释、空值与非法变量名
+    #[test]
+    fn test_parse() {
+        let content = r#"
+# comment
+export OPENAI_API_KEY=sk-plain # inline comment
+DOUBLE="with \"quotes\""
+SINGLE='raw \n value'
+EMPTY=
+BAD-NAME=value
+"#;
+        let keys = parse(content).unwrap();
+        let pairs: Vec<(&str, &str)> = keys.iter().map(|k| (k.name.as_str(), k.key_value.as_str())).collect();
+
+        assert_eq!(
+            pairs,
+            vec![
+                ("OPENAI_API_KEY", "sk-plain"),
+                ("DOUBLE", "with \"quotes\""),
+                ("SINGLE", "raw \\n value"),
+            ]
+        );
+        assert_eq!(keys[0].platform.as_deref(), Some("openai"));
+    }
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, thiserror::Error)]
+pub enum ImportError {
+    #[error("IO error: {0}")]
+    Io(String),
+    #[error("Unsupported import format: {0}")]
+    UnsupportedFormat(String),
+    #[error("Encrypted export is not supported: {0}")]
+    Encrypted(String),
+    #[error("Parse error: {0}")]
+    Parse(String),
+}
+
+impl From<std::io::Error> for ImportError {
+    fn from(error: std::io::Error) -> Self {
+        ImportError::Io(error.to_string())
+    }
+}
@@ -0,0 +1,46 @@
+use serde::Deserialize;
+use super::{error::ImportError, extract_keys_from_text, ImportedKey};
+
+// LastPass 导出的安全笔记使用该占位URL
+const SECURE_NOTE_URL: &str = "http://sn";
+
+// LastPass CSV 导出的一行：url,username,password,totp,extra,name,grouping,fav
+#[derive(Debug, Deserialize)]
+struct LastpassRow {
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    password: String,
+    #[serde(default)]
+    extra: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    grouping: String,
+}
+
+// 解析 LastPass CSV 导出
+pub fn parse(content: &str) -> Result<Vec<ImportedKey>, ImportError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(content.as_bytes());
+
+    let mut results = Vec::new();
+    for row in reader.deserialize::<LastpassRow>() {
+        let row = row.map_err(|e| ImportError::Parse(format!("Invalid LastPass CSV: {}", e)))?;
+        let tags: Vec<String> = if row.grouping.trim().is_empty() {
+            Vec::new()
+        } else {
+            vec![row.grouping.clone()]
+        };
+
+        if row.url == SECURE_NOTE_URL || row.password.trim().is_empty() {
+            results.extend(extract_keys_from_text(&row.name, &row.extra, &tags));
+            continue;
+        }
+
+        results.push(ImportedKey::new(&row.name, &row.password, Some(&row.extra), &tags));
+    }
+
+    Ok(results)
+}
@@ -0,0 +1,121 @@
+use serde_json::Value;
+use std::io::{Cursor, Read};
+use super::{error::ImportError, extract_keys_from_text, ImportedKey};
+
+// .1pux 压缩包中保存数据的文件
+const EXPORT_DATA_FILE: &str = "export.data";
+
+fn as_str<'a>(value: &'a Value, pointer: &str) -> &'a str {
+    value.pointer(pointer).and_then(Value::as_str).unwrap_or_default()
+}
+
+// 收集条目中的密码与隐藏字段，返回 (字段标题, 值)
+fn collect_secrets(details: &Value) -> Vec<(Option<String>, String)> {
+    let mut secrets = Vec::new();
+
+    for field in details
+        .get("loginFields")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        if as_str(field, "/designation") == "password" {
+            secrets.push((None, as_str(field, "/value").to_string()));
+        }
+    }
+
+    if let Some(password) = details.get("password").and_then(Value::as_str) {
+        secrets.push((None, password.to_string()));
+    }
+
+    // API Credential 等类别把 Key 存在 section 的 concealed 字段中
+    for section in details
+        .get("sections")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        for field in section
+            .get("fields")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            if let Some(concealed) = field.pointer("/value/concealed").and_then(Value::as_str) {
+                let title = as_str(field, "/title");
+                let title = if title.is_empty() { None } else { Some(title.to_string()) };
+                secrets.push((title, concealed.to_string()));
+            }
+        }
+    }
+
+    secrets.retain(|(_, value)| !value.trim().is_empty());
+    secrets.dedup_by(|a, b| a.1 == b.1);
+    secrets
+}
+
+// 解析 1Password .1pux 导出
+pub fn parse(bytes: &[u8]) -> Result<Vec<ImportedKey>, ImportError> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+        .map_err(|e| ImportError::Parse(format!("Invalid 1PUX archive: {}", e)))?;
+    let mut content = String::new();
+    archive
+        .by_name(EXPORT_DATA_FILE)
+        .map_err(|e| ImportError::Parse(format!("Missing {} in 1PUX archive: {}", EXPORT_DATA_FILE, e)))?
+        .read_to_string(&mut content)?;
+
+    let export: Value = serde_json::from_str(&content)
+        .map_err(|e| ImportError::Parse(format!("Invalid 1PUX export data: {}", e)))?;
+
+    let mut results = Vec::new();
+    let vaults = export
+        .get("accounts")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .flat_map(|account| account.get("vaults").and_then(Value::as_array).into_iter().flatten());
+
+    for vault in vaults {
+        let vault_name = as_str(vault, "/attrs/name");
+        for raw in vault.get("items").and_then(Value::as_array).into_iter().flatten() {
+            // 部分版本会把条目包在 item 字段中
+            let item = raw.get("item").unwrap_or(raw);
+            if item.get("trashed").and_then(Value::as_bool).unwrap_or(false)
+                || as_str(item, "/state") == "archived"
+            {
+                continue;
+            }
+
+            let title = as_str(item, "/overview/title");
+            let notes = as_str(item, "/details/notesPlain");
+            let mut tags: Vec<String> = item
+                .pointer("/overview/tags")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect();
+            if !vault_name.is_empty() {
+                tags.push(vault_name.to_string());
+            }
+
+            let secrets = item.get("details").map(collect_secrets).unwrap_or_default();
+            if secrets.is_empty() {
+                results.extend(extract_keys_from_text(title, notes, &tags));
+                continue;
+            }
+
+            let multiple = secrets.len() > 1;
+            for (field_title, value) in secrets {
+                let name = match field_title {
+                    Some(field_title) if multiple => format!("{} - {}", title, field_title),
+                    _ => title.to_string(),
+                };
+                results.push(ImportedKey::new(&name, &value, Some(notes), &tags));
+            }
+        }
+    }
+
+    Ok(results)
+}
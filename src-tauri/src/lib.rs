@@ -1,26 +1,168 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
+mod agent;
+mod backup;
+pub mod cli;
 mod clipboard;
 mod commands;
 mod database;
+mod error;
+mod export;
+mod http_api;
+mod importers;
+mod projects;
+mod providers;
+mod reminders;
+mod scanner;
 mod security;
+mod templates;
+mod validation;
+mod vaults;
+mod verifier;
 
 use commands::{
+    agent_commands::*,
     api_key_commands::*,
+    audit_commands::*,
+    backup_commands::*,
     batch_import_commands::*,
     clipboard_commands::*,
+    database_commands::*,
+    export_commands::*,
     group_commands::*,
+    http_api_commands::*,
+    import_commands::*,
+    project_commands::*,
+    provider_commands::*,
+    reminder_commands::*,
+    scan_commands::*,
     security_commands::*,
+    template_commands::*,
+    trash_commands::*,
+    vault_commands::*,
+    verify_commands::*,
     window_commands::*,
 };
+use database::error::DatabaseError;
+use error::AppError;
+use database::health::{DatabaseStatus, DatabaseStatusEvent};
 use database::init_database;
+use security::password::VaultSession;
 use sqlx::SqlitePool;
-use tauri::{Manager, State};
+use std::sync::RwLock;
+use tauri::{Emitter, Manager, State};
+use tokio::sync::watch;
 
+// 应用状态：启动时即注册，数据库就绪后再填入连接池
+pub struct AppState {
+    db: RwLock<Option<SqlitePool>>,
+    status: watch::Sender<DatabaseStatusEvent>,
+    pub session: VaultSession,
+    // 串行化保险库的打开与关闭，避免并发切换时连接池错乱
+    pub vault_switch: tokio::sync::Mutex<()>,
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        let (status, _) = watch::channel(DatabaseStatusEvent::new(DatabaseStatus::Initializing, None));
+        Self {
+            db: RwLock::new(None),
+            status,
+            session: VaultSession::default(),
+            vault_switch: tokio::sync::Mutex::new(()),
+        }
+    }
+}
 
+impl AppState {
+    // 获取数据库连接池，初始化未完成或失败时返回 NotReady
+    pub fn pool(&self) -> Result<SqlitePool, DatabaseError> {
+        self.db
+            .read()
+            .ok()
+            .and_then(|db| db.clone())
+            .ok_or(DatabaseError::NotReady)
+    }
 
-// 应用状态
-pub struct AppState {
-    pub db: SqlitePool,
+    // 替换连接池并返回旧的连接池，由调用方负责关闭
+    pub(crate) fn replace_pool(&self, pool: Option<SqlitePool>) -> Option<SqlitePool> {
+        match self.db.write() {
+            Ok(mut db) => std::mem::replace(&mut *db, pool),
+            Err(_) => None,
+        }
+    }
+
+    // 最近一次的数据库状态，供错过启动事件的窗口查询
+    pub fn status(&self) -> DatabaseStatusEvent {
+        self.status.borrow().clone()
+    }
+
+    // 等待数据库初始化结束（就绪、损坏或失败）
+    pub async fn wait_for_status(&self) -> DatabaseStatusEvent {
+        let mut receiver = self.status.subscribe();
+        loop {
+            let current = receiver.borrow_and_update().clone();
+            if current.status != DatabaseStatus::Initializing {
+                return current;
+            }
+            if receiver.changed().await.is_err() {
+                return current;
+            }
+        }
+    }
+}
+
+// 记录并广播数据库状态；就绪时额外发送 database-ready 事件
+pub(crate) fn report_database_status(app_handle: &tauri::AppHandle, event: DatabaseStatusEvent) {
+    app_handle.state::<AppState>().status.send_replace(event.clone());
+    if let Err(e) = app_handle.emit("database-status", &event) {
+        eprintln!("Failed to emit database-status: {}", e);
+    }
+    if event.status == DatabaseStatus::Ready {
+        if let Err(e) = app_handle.emit("database-ready", &event) {
+            eprintln!("Failed to emit database-ready: {}", e);
+        }
+    }
+}
+
+// 启用新打开的连接池：清理过期回收站、填入应用状态并广播就绪；返回被替换的旧连接池
+pub(crate) async fn activate_pool(app_handle: &tauri::AppHandle, pool: SqlitePool) -> Option<SqlitePool> {
+    match database::trash::purge_expired(&pool).await {
+        Ok(0) => {}
+        Ok(purged) => println!("Purged {} expired API keys from trash", purged),
+        Err(e) => eprintln!("Failed to purge expired trash: {}", e),
+    }
+    let previous = app_handle.state::<AppState>().replace_pool(Some(pool));
+    report_database_status(app_handle, DatabaseStatusEvent::new(DatabaseStatus::Ready, None));
+    previous
+}
+
+// 初始化数据库并填入应用状态；失败时通过 database-status 事件告知前端
+pub(crate) async fn setup_database(app_handle: &tauri::AppHandle) -> Result<(), DatabaseError> {
+    report_database_status(app_handle, DatabaseStatusEvent::new(DatabaseStatus::Initializing, None));
+    match init_database(app_handle).await {
+        Ok(pool) => {
+            if let Some(previous) = activate_pool(app_handle, pool).await {
+                previous.close().await;
+            }
+            println!("Database initialized successfully");
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("数据库初始化失败: {}", e);
+            app_handle.state::<AppState>().replace_pool(None);
+            let mut event = DatabaseStatusEvent::new(DatabaseStatus::Failed, Some(e.to_string()));
+            if matches!(e, DatabaseError::Corrupted(_)) {
+                event.status = DatabaseStatus::Corrupted;
+                if let Ok(dir) = backup::backups_dir(app_handle) {
+                    event.latest_backup = database::repair::latest_valid_backup(&dir)
+                        .await
+                        .map(|b| b.file_name);
+                }
+            }
+            report_database_status(app_handle, event);
+            Err(e)
+        }
+    }
 }
 
 // 启动 greet 命令用于测试
@@ -31,12 +173,11 @@ fn greet(name: &str) -> String {
 
 // 测试数据库连接
 #[tauri::command]
-async fn test_database(state: State<'_, AppState>) -> Result<String, String> {
-    let pool = &state.db;
+async fn test_database(state: State<'_, AppState>) -> Result<String, AppError> {
+    let pool = &state.pool()?;
     let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM api_keys")
         .fetch_one(pool)
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
     Ok(format!("Database connected! Found {} API keys", count))
 }
 
@@ -96,17 +237,14 @@ pub fn run() {
                 println!("Floating toolbar window initialized with full transparency");
             }
             
+            app.manage(AppState::default());
+            backup::spawn_scheduler(app.handle().clone());
+            reminders::spawn_scheduler(app.handle().clone());
+            agent::spawn_agent(app.handle().clone());
+            http_api::spawn_server(app.handle().clone());
             let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
-                match init_database(&app_handle).await {
-                    Ok(pool) => {
-                        app_handle.manage(AppState { db: pool });
-                        println!("Database initialized and managed successfully");
-                    }
-                    Err(e) => {
-                        eprintln!("数据库初始化失败: {}", e);
-                    }
-                }
+                let _ = setup_database(&app_handle).await;
             });
 
             // 全局快捷键已在插件初始化时注册
@@ -119,14 +257,23 @@ pub fn run() {
             test_database,
             add_api_key,
             edit_api_key,
+            list_key_versions,
+            restore_key_version,
+            reveal_key_version,
             delete_api_key,
+            reveal_api_key,
             list_api_keys,
             search_api_keys,
             get_all_platforms,
             import_api_keys_batch,
             copy_to_clipboard,
+            clear_clipboard,
             set_master_password,
             verify_master_password,
+            lock_vault,
+            get_unlock_attempt_status,
+            set_lockout_policy,
+            is_vault_locked,
             encrypt_key,
             decrypt_key,
             show_floating_toolbar,
@@ -141,6 +288,56 @@ pub fn run() {
             add_group,
             list_groups,
             check_api_keys_exists,
+            parse_import_file,
+            preview_import_file,
+            export_vault,
+            inspect_vault_file,
+            import_vault,
+            export_keys,
+            list_backups,
+            create_backup,
+            restore_backup,
+            get_backup_config,
+            set_backup_config,
+            get_database_status,
+            wait_for_database,
+            check_database_integrity,
+            repair_database,
+            list_trash,
+            restore_api_key,
+            purge_trash,
+            get_trash_retention_days,
+            set_trash_retention_days,
+            expiring_keys,
+            get_expiry_reminder_days,
+            set_expiry_reminder_days,
+            set_key_schedule,
+            verify_api_key,
+            verify_api_keys,
+            list_verifier_providers,
+            set_verifier_base_url,
+            list_providers,
+            list_vaults,
+            create_vault,
+            open_vault,
+            close_vault,
+            get_agent_status,
+            set_agent_confirm_requests,
+            list_audit_log,
+            list_audit_events,
+            verify_audit_chain,
+            get_http_api_status,
+            set_http_api_config,
+            list_api_tokens,
+            create_api_token,
+            revoke_api_token,
+            render_template,
+            list_projects,
+            bind_project_key,
+            unbind_project,
+            sync_project,
+            scan_directory,
+            scan_git_history,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,261 @@
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tauri::Emitter;
+use crate::database::api_key::get_api_key_by_id;
+use crate::database::audit::{audit, AuditEntry};
+use crate::database::project_binding::{
+    get_project_statuses, list_binding_statuses, mark_synced, projects_using_key, BindingStatus,
+};
+use crate::database::usage_history::record_usage;
+use crate::error::AppError;
+use crate::export::formats::quote_env_value;
+use crate::security::write_private_file;
+use crate::templates::assignment_regex;
+
+// 项目中写入Key的文件，位于项目根目录
+pub const ENV_FILE: &str = ".env";
+
+// 项目及其绑定；任一绑定过期或缺失时需要重新同步
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectInfo {
+    pub path: String,
+    pub bindings: Vec<BindingStatus>,
+    #[serde(rename = "syncedAt")]
+    pub synced_at: Option<i64>,
+    pub stale: bool,
+}
+
+impl ProjectInfo {
+    fn new(path: String, bindings: Vec<BindingStatus>) -> Self {
+        Self {
+            synced_at: bindings.iter().filter_map(|b| b.binding.synced_at).min(),
+            stale: bindings.iter().any(|b| b.stale || b.missing),
+            path,
+            bindings,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    #[serde(rename = "envFile")]
+    pub env_file: String,
+    // 写入的变量名
+    pub written: Vec<String>,
+    // Key已删除而未写入的变量名
+    pub missing: Vec<String>,
+}
+
+// 统一项目路径的形式：解析为绝对路径，且必须是已存在的目录
+pub fn normalize_project_path(path: &Path) -> Result<String, AppError> {
+    let path = std::fs::canonicalize(path)
+        .map_err(|e| AppError::Validation(format!("Invalid project directory {}: {}", path.display(), e)))?;
+    if !path.is_dir() {
+        return Err(AppError::Validation(format!("{} is not a directory", path.display())));
+    }
+    Ok(path.to_string_lossy().to_string())
+}
+
+// 按项目归类所有绑定
+pub async fn list_projects(pool: &SqlitePool) -> Result<Vec<ProjectInfo>, AppError> {
+    let mut projects: Vec<ProjectInfo> = Vec::new();
+    let mut current: Option<(String, Vec<BindingStatus>)> = None;
+    for status in list_binding_statuses(pool).await? {
+        match &mut current {
+            Some((path, bindings)) if *path == status.binding.project_path => bindings.push(status),
+            _ => {
+                if let Some((path, bindings)) = current.take() {
+                    projects.push(ProjectInfo::new(path, bindings));
+                }
+                current = Some((status.binding.project_path.clone(), vec![status]));
+            }
+        }
+    }
+    if let Some((path, bindings)) = current {
+        projects.push(ProjectInfo::new(path, bindings));
+    }
+    Ok(projects)
+}
+
+pub async fn get_project(pool: &SqlitePool, project_path: &str) -> Result<ProjectInfo, AppError> {
+    let bindings = get_project_statuses(pool, project_path).await?;
+    if bindings.is_empty() {
+        return Err(AppError::NotFound(format!("Project {}", project_path)));
+    }
+    Ok(ProjectInfo::new(project_path.to_string(), bindings))
+}
+
+// 查找包含指定目录的已绑定项目，有多个时取最内层的
+pub async fn find_project_for(pool: &SqlitePool, dir: &Path) -> Result<Option<String>, AppError> {
+    let dir = PathBuf::from(normalize_project_path(dir)?);
+    let projects = list_projects(pool).await?;
+    Ok(projects
+        .into_iter()
+        .map(|p| p.path)
+        .filter(|path| dir.starts_with(path))
+        .max_by_key(|path| path.len()))
+}
+
+// 写入或刷新项目的 .env：更新已绑定变量所在的行，追加缺少的变量，其余内容保持不变；
+// 写入的每个Key记录审计日志
+pub async fn sync_project(pool: &SqlitePool, source: &str, project_path: &str) -> Result<SyncReport, AppError> {
+    let project = get_project(pool, project_path).await?;
+    let mut values = Vec::new();
+    let mut missing = Vec::new();
+    for status in &project.bindings {
+        let binding = &status.binding;
+        match get_api_key_by_id(pool, &binding.key_id).await? {
+            Some(key) if key.deleted_at.is_none() => values.push((binding.env_var.as_str(), key)),
+            _ => missing.push(binding.env_var.clone()),
+        }
+    }
+
+    let env_file = Path::new(project_path).join(ENV_FILE);
+    let existing = match std::fs::read_to_string(&env_file) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(AppError::Io(format!("Failed to read {}: {}", env_file.display(), e))),
+    };
+
+    let mut seen = HashSet::new();
+    let mut lines: Vec<String> = Vec::new();
+    for line in existing.lines() {
+        let replaced = assignment_regex().captures(line).and_then(|caps| {
+            let (_, key) = values.iter().find(|(var, _)| *var == &caps[2])?;
+            seen.insert(caps[2].to_string());
+            Some(format!("{}{}{}{}", &caps[1], &caps[2], &caps[3], quote_env_value(&key.key_value)))
+        });
+        lines.push(replaced.unwrap_or_else(|| line.to_string()));
+    }
+    for (var, key) in &values {
+        if !seen.contains(*var) {
+            lines.push(format!("{}={}", var, quote_env_value(&key.key_value)));
+        }
+    }
+    let mut content = lines.join("\n");
+    content.push('\n');
+    write_private_file(&env_file, &content)?;
+
+    mark_synced(pool, project_path, chrono::Utc::now().timestamp()).await?;
+    for (var, key) in &values {
+        if let Err(e) = record_usage(pool, &key.id, "project").await {
+            eprintln!("Failed to record usage for {} ({}): {}", key.id, var, e);
+        }
+        audit(
+            pool,
+            AuditEntry::new(source, "sync", "allowed")
+                .with_key(&key.id)
+                .with_target(&env_file.to_string_lossy())
+                .with_detail(*var),
+        )
+        .await;
+    }
+
+    Ok(SyncReport {
+        env_file: env_file.to_string_lossy().to_string(),
+        written: values.iter().map(|(var, _)| var.to_string()).collect(),
+        missing,
+    })
+}
+
+// Key的值被修改后，通知前端哪些项目的 .env 已经过期
+pub async fn notify_stale_projects(app_handle: &tauri::AppHandle, pool: &SqlitePool, key_id: &str) {
+    let paths = match projects_using_key(pool, key_id).await {
+        Ok(paths) => paths,
+        Err(e) => {
+            eprintln!("Failed to look up projects using {}: {}", key_id, e);
+            return;
+        }
+    };
+    let mut stale = Vec::new();
+    for path in paths {
+        match get_project(pool, &path).await {
+            Ok(project) if project.stale => stale.push(project),
+            Ok(_) => {}
+            Err(e) => eprintln!("Failed to check project {}: {}", path, e),
+        }
+    }
+    if stale.is_empty() {
+        return;
+    }
+    println!("{} projects have a stale {} after updating {}", stale.len(), ENV_FILE, key_id);
+    if let Err(e) = app_handle.emit("projects-stale", &stale) {
+        eprintln!("Failed to emit projects-stale: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::api_key::{delete_api_key, insert_api_key, ApiKey};
+    use crate::database::audit::APP_SOURCE;
+    use crate::database::project_binding::upsert_binding;
+    use crate::database::TestDatabase;
+
+    fn temp_project() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("akm-project-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        PathBuf::from(normalize_project_path(&dir).unwrap())
+    }
+
+    async fn insert_key(pool: &SqlitePool, name: &str, value: &str) -> ApiKey {
+        let key = ApiKey::new(name.into(), value.into(), None, None, None, None);
+        insert_api_key(pool, &key).await.unwrap();
+        key
+    }
+
+    // 更新已有变量所在的行并保留其余内容，追加缺少的变量，跳过已删除的Key
+    #[tokio::test]
+    async fn test_sync_project() {
+        let db = TestDatabase::open().await;
+        let dir = temp_project();
+        let path = dir.to_string_lossy().to_string();
+        let existing = insert_key(&db.pool, "existing", "new value").await;
+        let added = insert_key(&db.pool, "added", "sk-added").await;
+        let removed = insert_key(&db.pool, "removed", "gone").await;
+        upsert_binding(&db.pool, &path, "EXISTING", &existing.id).await.unwrap();
+        upsert_binding(&db.pool, &path, "ADDED", &added.id).await.unwrap();
+        upsert_binding(&db.pool, &path, "REMOVED", &removed.id).await.unwrap();
+        delete_api_key(&db.pool, &removed.id).await.unwrap();
+        std::fs::write(dir.join(ENV_FILE), "# config\nexport EXISTING=old\nOTHER=1\n").unwrap();
+
+        let report = sync_project(&db.pool, APP_SOURCE, &path).await.unwrap();
+
+        assert_eq!(report.written, vec!["ADDED", "EXISTING"]);
+        assert_eq!(report.missing, vec!["REMOVED"]);
+        let content = std::fs::read_to_string(dir.join(ENV_FILE)).unwrap();
+        assert_eq!(content, "# config\nexport EXISTING=\"new value\"\nOTHER=1\nADDED=sk-added\n");
+        let project = get_project(&db.pool, &path).await.unwrap();
+        assert!(project.synced_at.is_some());
+        assert!(project.stale, "missing keys keep the project stale");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // 按项目归类绑定，子目录归属于包含它的项目
+    #[tokio::test]
+    async fn test_list_and_find_projects() {
+        let db = TestDatabase::open().await;
+        let dir = temp_project();
+        let path = dir.to_string_lossy().to_string();
+        let key = insert_key(&db.pool, "main", "value").await;
+        upsert_binding(&db.pool, &path, "A", &key.id).await.unwrap();
+        upsert_binding(&db.pool, &path, "B", &key.id).await.unwrap();
+        upsert_binding(&db.pool, "/nonexistent/other", "A", &key.id).await.unwrap();
+
+        let projects = list_projects(&db.pool).await.unwrap();
+        let summary: Vec<(&str, usize)> = projects.iter().map(|p| (p.path.as_str(), p.bindings.len())).collect();
+        assert!(summary.contains(&(path.as_str(), 2)));
+        assert!(summary.contains(&("/nonexistent/other", 1)));
+
+        assert_eq!(find_project_for(&db.pool, &dir.join("sub")).await.unwrap(), Some(path.clone()));
+        assert_eq!(find_project_for(&db.pool, &std::env::temp_dir()).await.unwrap(), None);
+        assert!(matches!(get_project(&db.pool, "/nowhere").await, Err(AppError::NotFound(_))));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
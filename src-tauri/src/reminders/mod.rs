@@ -0,0 +1,61 @@
+use sqlx::SqlitePool;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+use crate::database::error::DatabaseError;
+use crate::database::expiry::{expiring_keys, ExpiringKey};
+use crate::database::settings::{get_setting, set_setting};
+use crate::validation::MAX_PERIOD_DAYS;
+use crate::AppState;
+
+// settings 表中保存提前提醒天数的键
+pub const REMINDER_DAYS_SETTING: &str = "expiry_reminder_days";
+pub const DEFAULT_REMINDER_DAYS: i64 = 7;
+// 检查即将过期Key的间隔
+const REMINDER_TICK: Duration = Duration::from_secs(60 * 60);
+
+// 读取提前提醒天数
+pub async fn get_reminder_days(pool: &SqlitePool) -> Result<i64, DatabaseError> {
+    Ok(get_setting(pool, REMINDER_DAYS_SETTING)
+        .await?
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_REMINDER_DAYS))
+}
+
+// 保存提前提醒天数
+pub async fn set_reminder_days(pool: &SqlitePool, days: i64) -> Result<(), DatabaseError> {
+    if !(0..=MAX_PERIOD_DAYS).contains(&days) {
+        return Err(DatabaseError::InvalidInput(format!(
+            "Reminder days must be between 0 and {}",
+            MAX_PERIOD_DAYS
+        )));
+    }
+    set_setting(pool, REMINDER_DAYS_SETTING, &days.to_string()).await
+}
+
+// 检查一次并通过 keys-expiring 事件推送结果（列表为空时也推送，便于前端清除角标）
+pub async fn check_and_notify(app_handle: &tauri::AppHandle, pool: &SqlitePool) -> Result<Vec<ExpiringKey>, DatabaseError> {
+    let days = get_reminder_days(pool).await?;
+    let keys = expiring_keys(pool, days).await?;
+    if let Err(e) = app_handle.emit("keys-expiring", &keys) {
+        eprintln!("Failed to emit keys-expiring: {}", e);
+    }
+    Ok(keys)
+}
+
+// 启动后台提醒任务：数据库就绪后立即检查一次，之后每小时检查
+pub fn spawn_scheduler(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+        state.wait_for_status().await;
+        loop {
+            if let Ok(pool) = state.pool() {
+                match check_and_notify(&app_handle, &pool).await {
+                    Ok(keys) if !keys.is_empty() => println!("{} API keys need attention", keys.len()),
+                    Ok(_) => {}
+                    Err(e) => eprintln!("Expiry check failed: {}", e),
+                }
+            }
+            tokio::time::sleep(REMINDER_TICK).await;
+        }
+    });
+}
@@ -0,0 +1,226 @@
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tokio::sync::Mutex;
+use crate::database::audit::{audit, AuditEntry};
+use crate::database::settings::{delete_setting, get_setting, set_setting};
+use crate::error::AppError;
+use crate::security::password::check_master_password;
+
+// settings 表中保存连续失败次数、最近一次失败时间与锁定策略的键；
+// 保存在数据库中，重启应用或改用命令行都不会清零
+const FAILED_ATTEMPTS_SETTING: &str = "unlock_failed_attempts";
+const LAST_FAILURE_SETTING: &str = "unlock_last_failure_at";
+const THRESHOLD_SETTING: &str = "unlock_lockout_threshold";
+const LOCKOUT_MINUTES_SETTING: &str = "unlock_lockout_minutes";
+
+pub const DEFAULT_LOCKOUT_THRESHOLD: i64 = 10;
+pub const DEFAULT_LOCKOUT_MINUTES: i64 = 15;
+// 前几次输错不需要等待
+const FREE_ATTEMPTS: i64 = 3;
+// 之后每次失败的等待时间从 2 秒起翻倍，最长 5 分钟
+const BASE_BACKOFF_SECS: i64 = 2;
+const MAX_BACKOFF_SECS: i64 = 300;
+
+// 同一进程内的验证串行执行，避免并发请求绕过计数
+static UNLOCK_GUARD: Mutex<()> = Mutex::const_new(());
+
+// 锁定策略：连续失败达到 threshold 次后锁定 lockout_minutes 分钟
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct LockoutPolicy {
+    pub threshold: i64,
+    #[serde(rename = "lockoutMinutes")]
+    pub lockout_minutes: i64,
+}
+
+// 当前的失败计数与需要等待的时间，锁定状态下也可查询
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UnlockAttemptStatus {
+    #[serde(rename = "failedAttempts")]
+    pub failed_attempts: i64,
+    // 距离允许下一次尝试的秒数，0 表示可以立即尝试
+    #[serde(rename = "retryAfter")]
+    pub retry_after: i64,
+    #[serde(rename = "lockedOut")]
+    pub locked_out: bool,
+    pub policy: LockoutPolicy,
+}
+
+async fn get_i64_setting(pool: &SqlitePool, key: &str, default: i64) -> Result<i64, AppError> {
+    Ok(get_setting(pool, key)
+        .await?
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default))
+}
+
+pub async fn get_policy(pool: &SqlitePool) -> Result<LockoutPolicy, AppError> {
+    Ok(LockoutPolicy {
+        threshold: get_i64_setting(pool, THRESHOLD_SETTING, DEFAULT_LOCKOUT_THRESHOLD).await?,
+        lockout_minutes: get_i64_setting(pool, LOCKOUT_MINUTES_SETTING, DEFAULT_LOCKOUT_MINUTES).await?,
+    })
+}
+
+pub async fn set_policy(pool: &SqlitePool, policy: &LockoutPolicy) -> Result<(), AppError> {
+    if !(FREE_ATTEMPTS + 1..=100).contains(&policy.threshold) {
+        return Err(AppError::Validation(format!(
+            "Lockout threshold must be between {} and 100",
+            FREE_ATTEMPTS + 1
+        )));
+    }
+    if !(1..=24 * 60).contains(&policy.lockout_minutes) {
+        return Err(AppError::Validation("Lockout duration must be between 1 and 1440 minutes".to_string()));
+    }
+    set_setting(pool, THRESHOLD_SETTING, &policy.threshold.to_string()).await?;
+    set_setting(pool, LOCKOUT_MINUTES_SETTING, &policy.lockout_minutes.to_string()).await?;
+    Ok(())
+}
+
+// 第 failed_attempts 次失败后需要等待的秒数；达到阈值后为整段锁定时间
+fn backoff_secs(failed_attempts: i64, policy: &LockoutPolicy) -> i64 {
+    if failed_attempts >= policy.threshold {
+        return policy.lockout_minutes * 60;
+    }
+    if failed_attempts < FREE_ATTEMPTS {
+        return 0;
+    }
+    let exponent = (failed_attempts - FREE_ATTEMPTS).min(16) as u32;
+    (BASE_BACKOFF_SECS << exponent).min(MAX_BACKOFF_SECS)
+}
+
+pub async fn get_attempt_status(pool: &SqlitePool) -> Result<UnlockAttemptStatus, AppError> {
+    let policy = get_policy(pool).await?;
+    let failed_attempts = get_i64_setting(pool, FAILED_ATTEMPTS_SETTING, 0).await?;
+    let last_failure_at = get_i64_setting(pool, LAST_FAILURE_SETTING, 0).await?;
+    let elapsed = chrono::Utc::now().timestamp() - last_failure_at;
+    let retry_after = (backoff_secs(failed_attempts, &policy) - elapsed).max(0);
+    Ok(UnlockAttemptStatus {
+        failed_attempts,
+        retry_after,
+        locked_out: failed_attempts >= policy.threshold && retry_after > 0,
+        policy,
+    })
+}
+
+// 带尝试次数限制的主密码验证：等待期内直接拒绝，不校验密码；
+// 成功时清零计数，失败时累加并记录到审计日志。锁定结束后再次输错会重新锁定，直到成功一次
+pub async fn verify_unlock_attempt(pool: &SqlitePool, password: &str, source: &str) -> Result<bool, AppError> {
+    let _guard = UNLOCK_GUARD.lock().await;
+    let status = get_attempt_status(pool).await?;
+    if status.retry_after > 0 {
+        let reason = if status.locked_out { "locked out" } else { "backoff" };
+        audit(
+            pool,
+            AuditEntry::new(source, "unlock", "denied").with_detail(format!(
+                "{} after {} failed attempts, retry in {}s",
+                reason, status.failed_attempts, status.retry_after
+            )),
+        )
+        .await;
+        return Err(AppError::TooManyAttempts(status.retry_after));
+    }
+
+    if check_master_password(pool, password).await? {
+        if status.failed_attempts > 0 {
+            delete_setting(pool, FAILED_ATTEMPTS_SETTING).await?;
+            delete_setting(pool, LAST_FAILURE_SETTING).await?;
+        }
+        audit(pool, AuditEntry::new(source, "unlock", "allowed")).await;
+        return Ok(true);
+    }
+
+    let failed_attempts = status.failed_attempts + 1;
+    set_setting(pool, FAILED_ATTEMPTS_SETTING, &failed_attempts.to_string()).await?;
+    set_setting(pool, LAST_FAILURE_SETTING, &chrono::Utc::now().timestamp().to_string()).await?;
+    let wait = backoff_secs(failed_attempts, &status.policy);
+    let mut detail = format!("incorrect password, {} failed attempts", failed_attempts);
+    if failed_attempts >= status.policy.threshold {
+        detail.push_str(&format!(", locked for {} minutes", status.policy.lockout_minutes));
+        eprintln!("Master password locked out after {} failed attempts", failed_attempts);
+    } else if wait > 0 {
+        detail.push_str(&format!(", next attempt in {}s", wait));
+    }
+    audit(pool, AuditEntry::new(source, "unlock", "denied").with_detail(detail)).await;
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::TestDatabase;
+    use crate::security::password::store_master_password;
+
+    const POLICY: LockoutPolicy = LockoutPolicy {
+        threshold: DEFAULT_LOCKOUT_THRESHOLD,
+        lockout_minutes: DEFAULT_LOCKOUT_MINUTES,
+    };
+
+    #[test]
+    fn test_backoff_secs() {
+        // 前几次不等待，之后从 2 秒起翻倍，达到阈值后锁定整段时间
+        assert_eq!(backoff_secs(0, &POLICY), 0);
+        assert_eq!(backoff_secs(FREE_ATTEMPTS - 1, &POLICY), 0);
+        assert_eq!(backoff_secs(FREE_ATTEMPTS, &POLICY), 2);
+        assert_eq!(backoff_secs(FREE_ATTEMPTS + 1, &POLICY), 4);
+        assert_eq!(backoff_secs(FREE_ATTEMPTS + 2, &POLICY), 8);
+        assert_eq!(backoff_secs(POLICY.threshold, &POLICY), DEFAULT_LOCKOUT_MINUTES * 60);
+        assert_eq!(backoff_secs(POLICY.threshold + 50, &POLICY), DEFAULT_LOCKOUT_MINUTES * 60);
+    }
+
+    #[test]
+    fn test_backoff_secs_is_capped() {
+        // 阈值很高时等待时间不超过上限，也不会溢出
+        let policy = LockoutPolicy { threshold: 100, lockout_minutes: 1 };
+        assert_eq!(backoff_secs(30, &policy), MAX_BACKOFF_SECS);
+        assert_eq!(backoff_secs(99, &policy), MAX_BACKOFF_SECS);
+    }
+
+    #[tokio::test]
+    async fn test_get_attempt_status() {
+        let db = TestDatabase::open().await;
+        let status = get_attempt_status(&db.pool).await.unwrap();
+        assert_eq!(status.failed_attempts, 0);
+        assert_eq!(status.retry_after, 0);
+        assert!(!status.locked_out);
+
+        // 刚达到阈值时处于锁定状态
+        let now = chrono::Utc::now().timestamp();
+        set_setting(&db.pool, FAILED_ATTEMPTS_SETTING, &DEFAULT_LOCKOUT_THRESHOLD.to_string()).await.unwrap();
+        set_setting(&db.pool, LAST_FAILURE_SETTING, &now.to_string()).await.unwrap();
+        let status = get_attempt_status(&db.pool).await.unwrap();
+        assert!(status.locked_out);
+        assert!(status.retry_after > DEFAULT_LOCKOUT_MINUTES * 60 - 5);
+
+        // 锁定时间过后可以再次尝试，计数保留
+        let expired = now - DEFAULT_LOCKOUT_MINUTES * 60 - 1;
+        set_setting(&db.pool, LAST_FAILURE_SETTING, &expired.to_string()).await.unwrap();
+        let status = get_attempt_status(&db.pool).await.unwrap();
+        assert!(!status.locked_out);
+        assert_eq!(status.retry_after, 0);
+        assert_eq!(status.failed_attempts, DEFAULT_LOCKOUT_THRESHOLD);
+    }
+
+    #[tokio::test]
+    async fn test_verify_unlock_attempt() {
+        let db = TestDatabase::open().await;
+        store_master_password(&db.pool, "correct horse").await.unwrap();
+
+        // 免等待的次数内输错只累加计数
+        for _ in 0..FREE_ATTEMPTS - 1 {
+            assert!(!verify_unlock_attempt(&db.pool, "wrong", "app").await.unwrap());
+        }
+        assert_eq!(get_attempt_status(&db.pool).await.unwrap().failed_attempts, FREE_ATTEMPTS - 1);
+
+        // 再错一次后需要等待，等待期内即使密码正确也被拒绝
+        assert!(!verify_unlock_attempt(&db.pool, "wrong", "app").await.unwrap());
+        assert!(matches!(
+            verify_unlock_attempt(&db.pool, "correct horse", "app").await,
+            Err(AppError::TooManyAttempts(secs)) if secs > 0
+        ));
+
+        // 等待结束后输入正确密码清零计数
+        set_setting(&db.pool, LAST_FAILURE_SETTING, "0").await.unwrap();
+        assert!(verify_unlock_attempt(&db.pool, "correct horse", "app").await.unwrap());
+        assert_eq!(get_attempt_status(&db.pool).await.unwrap().failed_attempts, 0);
+    }
+}
@@ -1,9 +1,10 @@
 pub mod password;
+pub mod lockout;
 pub mod encryption;
 
 use argon2::{
     password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
-    Argon2,
+    Algorithm, Argon2, Params, Version,
 };
 use aes_gcm::{
     aead::{Aead, KeyInit},
@@ -25,7 +26,6 @@ pub fn hash_password(password: &str) -> Result<String, String> {
 }
 
 // 验证密码
-#[allow(dead_code)]
 pub fn verify_password(password: &str, hash: &str) -> Result<bool, String> {
     let argon2 = Argon2::default();
     let parsed_hash = PasswordHash::new(hash)
@@ -53,6 +53,33 @@ pub fn generate_encryption_key(password: &str) -> Result<[u8; 32], String> {
     Ok(key)
 }
 
+// 生成随机盐值
+pub fn generate_salt() -> [u8; 16] {
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    salt
+}
+
+// 使用指定的Argon2id参数和盐值派生加密密钥
+pub fn derive_key(
+    password: &str,
+    salt: &[u8],
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+) -> Result<[u8; 32], String> {
+    let params = Params::new(memory_kib, iterations, parallelism, Some(32))
+        .map_err(|e| format!("Invalid key derivation params: {:?}", e))?;
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+
+    let mut key = [0u8; 32];
+    argon2
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Key derivation error: {:?}", e))?;
+
+    Ok(key)
+}
+
 // 加密数据
 pub fn encrypt_data(
     data: &str,
@@ -103,4 +130,34 @@ pub fn decrypt_data(
     
     String::from_utf8(plaintext)
         .map_err(|e| format!("UTF-8 conversion error: {:?}", e))
-}
\ No newline at end of file
+}
+// 生成随机访问令牌（URL 安全的 Base64）
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+}
+
+// 以固定时间比较两个字符串，避免通过响应时间猜测令牌
+pub fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+// 写入包含敏感信息的文件，Unix 上仅允许当前用户读写
+pub fn write_private_file(path: &std::path::Path, content: &str) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // 文件已存在时 mode 不生效，需要显式收紧权限
+        if path.exists() {
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+        }
+    }
+    let mut file = options.open(path)?;
+    file.write_all(content.as_bytes())
+}
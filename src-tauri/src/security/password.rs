@@ -1 +1,60 @@
-// 密码相关功能
\ No newline at end of file
+use std::sync::atomic::{AtomicBool, Ordering};
+use sqlx::SqlitePool;
+use crate::database::settings::{get_setting, set_setting};
+use crate::error::AppError;
+use crate::security;
+
+// settings 表中保存主密码哈希的键
+pub const MASTER_PASSWORD_SETTING: &str = "master_password_hash";
+
+// 当前会话的保险库解锁状态
+#[derive(Debug, Default)]
+pub struct VaultSession {
+    unlocked: AtomicBool,
+}
+
+impl VaultSession {
+    pub fn is_unlocked(&self) -> bool {
+        self.unlocked.load(Ordering::SeqCst)
+    }
+
+    pub fn unlock(&self) {
+        self.unlocked.store(true, Ordering::SeqCst);
+    }
+
+    pub fn lock(&self) {
+        self.unlocked.store(false, Ordering::SeqCst);
+    }
+
+    // 未设置主密码时视为已解锁；否则要求本次会话已验证过主密码
+    pub async fn ensure_unlocked(&self, pool: &SqlitePool) -> Result<(), AppError> {
+        if self.is_unlocked() || !has_master_password(pool).await? {
+            return Ok(());
+        }
+        Err(AppError::VaultLocked)
+    }
+}
+
+// 是否已设置主密码
+pub async fn has_master_password(pool: &SqlitePool) -> Result<bool, AppError> {
+    Ok(get_master_password_hash(pool).await?.is_some())
+}
+
+// 读取主密码哈希
+pub async fn get_master_password_hash(pool: &SqlitePool) -> Result<Option<String>, AppError> {
+    Ok(get_setting(pool, MASTER_PASSWORD_SETTING).await?)
+}
+
+// 哈希并保存主密码
+pub async fn store_master_password(pool: &SqlitePool, password: &str) -> Result<(), AppError> {
+    let hash = security::hash_password(password).map_err(AppError::Encryption)?;
+    Ok(set_setting(pool, MASTER_PASSWORD_SETTING, &hash).await?)
+}
+
+// 校验主密码；未设置主密码时返回 true
+pub async fn check_master_password(pool: &SqlitePool, password: &str) -> Result<bool, AppError> {
+    match get_master_password_hash(pool).await? {
+        Some(hash) => security::verify_password(password, &hash).map_err(AppError::Encryption),
+        None => Ok(true),
+    }
+}
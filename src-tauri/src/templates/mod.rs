@@ -0,0 +1,366 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use crate::database::api_key::{get_all_api_keys, ApiKey};
+use crate::database::audit::{audit, AuditEntry};
+use crate::database::usage_history::record_usage;
+use crate::error::AppError;
+use crate::export::formats::{assign_env_var_names, quote_env_value};
+use crate::providers::find_provider;
+use crate::security::write_private_file;
+
+// 显式引用：${akm:openai-prod}，冒号后为Key的ID或名称
+fn reference_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\$\{akm:([^}]+)\}").expect("valid regex"))
+}
+
+// .env 风格的赋值行：[export ]NAME=value
+pub(crate) fn assignment_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"^(\s*(?:export\s+)?)([A-Za-z_][A-Za-z0-9_]*)(\s*=\s*)(.*?)\s*$").expect("valid regex")
+    })
+}
+
+// 已填入的引用，不包含Key的值
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedPlaceholder {
+    pub line: usize,
+    // ${akm:...} 引用或变量名
+    pub placeholder: String,
+    #[serde(rename = "keyId")]
+    pub key_id: String,
+    #[serde(rename = "keyName")]
+    pub key_name: String,
+}
+
+// 无法填入的引用及原因
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UnresolvedPlaceholder {
+    pub line: usize,
+    pub placeholder: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderReport {
+    #[serde(rename = "outputPath")]
+    pub output_path: String,
+    // 存在未解析的引用且未允许部分填充时不写入文件
+    pub written: bool,
+    pub resolved: Vec<ResolvedPlaceholder>,
+    pub unresolved: Vec<UnresolvedPlaceholder>,
+}
+
+pub struct RenderedTemplate {
+    pub content: String,
+    pub resolved: Vec<ResolvedPlaceholder>,
+    pub unresolved: Vec<UnresolvedPlaceholder>,
+}
+
+// 按 ID 或名称（忽略大小写）在给定的Key中查找，名称重复时视为无法确定
+fn lookup<'a>(keys: &'a [ApiKey], id_or_name: &str) -> Result<&'a ApiKey, String> {
+    let id_or_name = id_or_name.trim();
+    if let Some(key) = keys.iter().find(|k| k.id == id_or_name) {
+        return Ok(key);
+    }
+    let matches: Vec<&ApiKey> = keys.iter().filter(|k| k.name.eq_ignore_ascii_case(id_or_name)).collect();
+    match matches.as_slice() {
+        [key] => Ok(key),
+        [] => Err(format!("No API key named {}", id_or_name)),
+        _ => Err(format!("Multiple API keys are named {}, use the id instead", id_or_name)),
+    }
+}
+
+// 模板中的示例值，如空值、your-api-key-here、<OPENAI_KEY>、sk-xxx
+fn is_placeholder_value(value: &str) -> bool {
+    let value = value.trim_matches(|c| c == '"' || c == '\'').trim().to_lowercase();
+    value.is_empty()
+        || value.starts_with('<')
+        || ["your", "xxx", "...", "changeme", "change_me", "replace", "todo", "placeholder"]
+            .iter()
+            .any(|p| value.contains(p))
+}
+
+// 看起来应当存放密钥的变量名
+fn is_secret_name(name: &str) -> bool {
+    let name = name.to_ascii_uppercase();
+    ["_KEY", "_TOKEN", "_SECRET", "_PASSWORD"].iter().any(|s| name.ends_with(s))
+}
+
+// 按变量名匹配Key：显式映射 > 同名Key > 平台约定的变量名（仅一个Key时）> 导出时使用的变量名
+struct VariableMatcher<'a> {
+    keys: &'a [ApiKey],
+    mappings: &'a HashMap<String, String>,
+    env_names: HashMap<String, &'a ApiKey>,
+}
+
+impl<'a> VariableMatcher<'a> {
+    fn new(keys: &'a [ApiKey], mappings: &'a HashMap<String, String>) -> Self {
+        let env_names = assign_env_var_names(keys).into_iter().zip(keys).collect();
+        Self { keys, mappings, env_names }
+    }
+
+    // Ok(None) 表示该变量与Key无关
+    fn resolve(&self, variable: &str) -> Result<Option<&'a ApiKey>, String> {
+        if let Some(target) = self.mappings.get(variable) {
+            return lookup(self.keys, target).map(Some);
+        }
+        if let Some(key) = self.keys.iter().find(|k| k.name.trim() == variable) {
+            return Ok(Some(key));
+        }
+        let by_provider: Vec<&ApiKey> = self
+            .keys
+            .iter()
+            .filter(|k| {
+                k.platform
+                    .as_deref()
+                    .and_then(find_provider)
+                    .is_some_and(|p| p.env_var == variable)
+            })
+            .collect();
+        match by_provider.as_slice() {
+            [key] => return Ok(Some(key)),
+            [] => {}
+            keys => {
+                return Err(format!(
+                    "{} API keys match {}, add a mapping to choose one",
+                    keys.len(),
+                    variable
+                ))
+            }
+        }
+        Ok(self.env_names.get(variable).copied())
+    }
+}
+
+// 填充模板内容：替换所有 ${akm:...} 引用，并为 .env 风格中值为空或示例值的变量填入匹配的Key
+pub fn render_template(content: &str, keys: &[ApiKey], mappings: &HashMap<String, String>) -> RenderedTemplate {
+    let matcher = VariableMatcher::new(keys, mappings);
+    let mut resolved = Vec::new();
+    let mut unresolved = Vec::new();
+    let mut lines = Vec::new();
+
+    for (index, line) in content.split('\n').enumerate() {
+        let number = index + 1;
+        let mut rendered = line.to_string();
+
+        if reference_regex().is_match(line) {
+            rendered = reference_regex()
+                .replace_all(line, |caps: &regex::Captures| {
+                    let placeholder = caps[0].to_string();
+                    match lookup(keys, &caps[1]) {
+                        Ok(key) => {
+                            resolved.push(ResolvedPlaceholder {
+                                line: number,
+                                placeholder,
+                                key_id: key.id.clone(),
+                                key_name: key.name.clone(),
+                            });
+                            key.key_value.clone()
+                        }
+                        Err(reason) => {
+                            unresolved.push(UnresolvedPlaceholder { line: number, placeholder: placeholder.clone(), reason });
+                            placeholder
+                        }
+                    }
+                })
+                .into_owned();
+        } else if let Some(caps) = assignment_regex().captures(line.trim_end_matches('\r')) {
+            let variable = &caps[2];
+            if is_placeholder_value(&caps[4]) {
+                match matcher.resolve(variable) {
+                    Ok(Some(key)) => {
+                        rendered = format!("{}{}{}{}", &caps[1], variable, &caps[3], quote_env_value(&key.key_value));
+                        if line.ends_with('\r') {
+                            rendered.push('\r');
+                        }
+                        resolved.push(ResolvedPlaceholder {
+                            line: number,
+                            placeholder: variable.to_string(),
+                            key_id: key.id.clone(),
+                            key_name: key.name.clone(),
+                        });
+                    }
+                    Ok(None) if is_secret_name(variable) => unresolved.push(UnresolvedPlaceholder {
+                        line: number,
+                        placeholder: variable.to_string(),
+                        reason: format!("No API key matches {}", variable),
+                    }),
+                    Ok(None) => {}
+                    Err(reason) => unresolved.push(UnresolvedPlaceholder {
+                        line: number,
+                        placeholder: variable.to_string(),
+                        reason,
+                    }),
+                }
+            }
+        }
+        lines.push(rendered);
+    }
+
+    RenderedTemplate {
+        content: lines.join("\n"),
+        resolved,
+        unresolved,
+    }
+}
+
+// 默认输出路径：.env.example -> .env，config.template.yaml -> config.yaml，app.conf.tpl -> app.conf
+pub fn default_output_path(template: &Path) -> Option<PathBuf> {
+    let file_name = template.file_name()?.to_str()?;
+    let output = [".example", ".sample", ".template", ".tpl", ".dist"]
+        .iter()
+        .find_map(|suffix| file_name.strip_suffix(suffix))
+        .map(str::to_string)
+        .or_else(|| {
+            [".example.", ".sample.", ".template.", ".tpl."].iter().find_map(|infix| {
+                file_name
+                    .split_once(infix)
+                    .map(|(stem, ext)| format!("{}.{}", stem, ext))
+            })
+        })?;
+    (!output.is_empty()).then(|| template.with_file_name(output))
+}
+
+// 读取模板、填入Key并写入输出文件（仅当前用户可读写），写入的每个Key记录审计日志
+pub async fn render_file(
+    pool: &SqlitePool,
+    source: &str,
+    template: &Path,
+    output: Option<&Path>,
+    mappings: &HashMap<String, String>,
+    allow_unresolved: bool,
+) -> Result<RenderReport, AppError> {
+    let output = match output {
+        Some(path) => path.to_path_buf(),
+        None => default_output_path(template).ok_or_else(|| {
+            AppError::Validation(format!("Cannot derive an output path from {}, specify one", template.display()))
+        })?,
+    };
+    if output == template {
+        return Err(AppError::Validation("Output path must differ from the template".to_string()));
+    }
+
+    let content = std::fs::read_to_string(template)
+        .map_err(|e| AppError::Io(format!("Failed to read {}: {}", template.display(), e)))?;
+    let keys = get_all_api_keys(pool).await?;
+    let rendered = render_template(&content, &keys, mappings);
+
+    let written = rendered.unresolved.is_empty() || allow_unresolved;
+    if written {
+        write_private_file(&output, &rendered.content)?;
+        let output_path = output.to_string_lossy();
+        let used: HashSet<&str> = rendered.resolved.iter().map(|r| r.key_id.as_str()).collect();
+        for key_id in used {
+            if let Err(e) = record_usage(pool, key_id, "template").await {
+                eprintln!("Failed to record usage for {}: {}", key_id, e);
+            }
+            audit(
+                pool,
+                AuditEntry::new(source, "render", "allowed")
+                    .with_key(key_id)
+                    .with_target(&output_path),
+            )
+            .await;
+        }
+    }
+
+    Ok(RenderReport {
+        output_path: output.to_string_lossy().to_string(),
+        written,
+        resolved: rendered.resolved,
+        unresolved: rendered.unresolved,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(name: &str, value: &str, platform: Option<&str>) -> ApiKey {
+        ApiKey::new(name.to_string(), value.to_string(), platform.map(str::to_string), None, None, None)
+    }
+
+    #[test]
+    fn test_render_explicit_references() {
+        // 按名称（忽略大小写）或 ID 替换引用，找不到时保留原文
+        let keys = vec![key("openai-prod", "sk-prod", Some("openai"))];
+        let content = format!("a=${{akm:OpenAI-Prod}}\nb=${{akm:{}}}\nc=${{akm:missing}}", keys[0].id);
+        let rendered = render_template(&content, &keys, &HashMap::new());
+
+        assert_eq!(rendered.content, "a=sk-prod\nb=sk-prod\nc=${akm:missing}");
+        assert_eq!(rendered.resolved.len(), 2);
+        assert_eq!(rendered.unresolved.len(), 1);
+        assert_eq!(rendered.unresolved[0].line, 3);
+    }
+
+    #[test]
+    fn test_render_ambiguous_name() {
+        // 名称重复时不随意选择
+        let keys = vec![key("dup", "one", None), key("dup", "two", None)];
+        let rendered = render_template("X=${akm:dup}", &keys, &HashMap::new());
+        assert_eq!(rendered.content, "X=${akm:dup}");
+        assert!(rendered.unresolved[0].reason.contains("Multiple"));
+    }
+
+    #[test]
+    fn test_render_env_assignments() {
+        // 示例值按平台约定的变量名填入，已有真实值的行与无关变量保持不变
+        let keys = vec![key("main", "sk-openai", Some("openai"))];
+        let content = "export OPENAI_API_KEY=your-api-key-here\r\nDEBUG=\nOTHER_TOKEN=<token>\nKEEP_KEY=real-value";
+        let rendered = render_template(content, &keys, &HashMap::new());
+
+        assert_eq!(
+            rendered.content,
+            "export OPENAI_API_KEY=sk-openai\r\nDEBUG=\nOTHER_TOKEN=<token>\nKEEP_KEY=real-value"
+        );
+        assert_eq!(rendered.resolved.len(), 1);
+        assert_eq!(rendered.resolved[0].placeholder, "OPENAI_API_KEY");
+        // 像密钥的变量名找不到Key时报告
+        assert_eq!(rendered.unresolved.len(), 1);
+        assert_eq!(rendered.unresolved[0].placeholder, "OTHER_TOKEN");
+    }
+
+    #[test]
+    fn test_render_mappings_and_quoting() {
+        // 显式映射优先，含特殊字符的值加引号转义
+        let keys = vec![key("a", "plain", Some("openai")), key("b", "has space$", Some("openai"))];
+        let mut mappings = HashMap::new();
+        mappings.insert("OPENAI_API_KEY".to_string(), "b".to_string());
+        let rendered = render_template("OPENAI_API_KEY=", &keys, &mappings);
+        assert_eq!(rendered.content, "OPENAI_API_KEY=\"has space\\$\"");
+
+        // 没有映射时多个同平台Key无法确定
+        let rendered = render_template("OPENAI_API_KEY=", &keys, &HashMap::new());
+        assert_eq!(rendered.content, "OPENAI_API_KEY=");
+        assert_eq!(rendered.unresolved.len(), 1);
+    }
+
+    #[test]
+    fn test_default_output_path() {
+        let cases = [
+            ("dir/.env.example", Some("dir/.env")),
+            ("config.template.yaml", Some("config.yaml")),
+            ("app.conf.tpl", Some("app.conf")),
+            ("settings.sample.json", Some("settings.json")),
+            (".dist", None),
+            ("plain.txt", None),
+        ];
+        for (template, expected) in cases {
+            assert_eq!(
+                default_output_path(Path::new(template)),
+                expected.map(PathBuf::from),
+                "{}",
+                template
+            );
+        }
+    }
+}
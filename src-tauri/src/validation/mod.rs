@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use crate::database::api_key::ApiKey;
+use crate::providers::canonicalize_platform;
+
+// 字段长度上限，与前端 inputValidation.ts 保持一致
+pub const MAX_NAME_LENGTH: usize = 100;
+pub const MAX_KEY_VALUE_LENGTH: usize = 1000;
+pub const MAX_PLATFORM_LENGTH: usize = 50;
+pub const MAX_DESCRIPTION_LENGTH: usize = 500;
+pub const MAX_TAGS_LENGTH: usize = 500;
+// 轮换周期与提醒天数的上限（约 100 年）
+pub const MAX_PERIOD_DAYS: i64 = 36500;
+
+// 单个字段的校验错误，field 使用前端的 camelCase 字段名
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    fn new(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+// 去除控制字符与首尾空白
+fn clean_text(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+// 清理可选字段，空字符串视为未填写
+fn clean_optional(value: Option<&str>) -> Option<String> {
+    value.map(clean_text).filter(|v| !v.is_empty())
+}
+
+fn check_length(errors: &mut Vec<FieldError>, field: &str, value: &str, max: usize) {
+    if value.chars().count() > max {
+        errors.push(FieldError::new(field, format!("Must be at most {} characters", max)));
+    }
+}
+
+// 规范化逗号分隔的标签：去除空白与重复项
+fn normalize_tags(tags: Option<&str>) -> Option<String> {
+    let mut seen = HashSet::new();
+    let tags: Vec<String> = tags?
+        .split(',')
+        .map(clean_text)
+        .filter(|t| !t.is_empty() && seen.insert(t.to_lowercase()))
+        .collect();
+    if tags.is_empty() {
+        None
+    } else {
+        Some(tags.join(","))
+    }
+}
+
+fn check_schedule(errors: &mut Vec<FieldError>, expires_at: Option<i64>, rotate_every_days: Option<i64>) {
+    if expires_at.is_some_and(|t| t <= 0) {
+        errors.push(FieldError::new("expiresAt", "Expiry must be a valid timestamp"));
+    }
+    if rotate_every_days.is_some_and(|d| d <= 0) {
+        errors.push(FieldError::new("rotateEveryDays", "Rotation period must be at least 1 day"));
+    } else if rotate_every_days.is_some_and(|d| d > MAX_PERIOD_DAYS) {
+        errors.push(FieldError::new(
+            "rotateEveryDays",
+            format!("Rotation period cannot exceed {} days", MAX_PERIOD_DAYS),
+        ));
+    }
+}
+
+// 校验单独设置的过期时间与轮换周期
+pub fn validate_schedule(expires_at: Option<i64>, rotate_every_days: Option<i64>) -> Result<(), Vec<FieldError>> {
+    let mut errors = Vec::new();
+    check_schedule(&mut errors, expires_at, rotate_every_days);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+// 校验并规范化用户提交的API Key字段，一次返回所有字段错误
+pub fn normalize_api_key(api_key: &mut ApiKey) -> Result<(), Vec<FieldError>> {
+    let mut errors = Vec::new();
+
+    api_key.name = clean_text(&api_key.name);
+    if api_key.name.is_empty() {
+        errors.push(FieldError::new("name", "Name is required"));
+    }
+    check_length(&mut errors, "name", &api_key.name, MAX_NAME_LENGTH);
+
+    api_key.key_value = api_key.key_value.trim().to_string();
+    if api_key.key_value.is_empty() {
+        errors.push(FieldError::new("keyValue", "Key value is required"));
+    } else if api_key.key_value.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        errors.push(FieldError::new("keyValue", "Key value must not contain whitespace"));
+    }
+    check_length(&mut errors, "keyValue", &api_key.key_value, MAX_KEY_VALUE_LENGTH);
+
+    api_key.platform = clean_optional(api_key.platform.as_deref()).map(|p| canonicalize_platform(&p));
+    if let Some(platform) = &api_key.platform {
+        check_length(&mut errors, "platform", platform, MAX_PLATFORM_LENGTH);
+    }
+
+    api_key.description = clean_optional(api_key.description.as_deref());
+    if let Some(description) = &api_key.description {
+        check_length(&mut errors, "description", description, MAX_DESCRIPTION_LENGTH);
+    }
+
+    api_key.group_id = clean_optional(api_key.group_id.as_deref());
+
+    api_key.tags = normalize_tags(api_key.tags.as_deref());
+    if let Some(tags) = &api_key.tags {
+        check_length(&mut errors, "tags", tags, MAX_TAGS_LENGTH);
+    }
+
+    check_schedule(&mut errors, api_key.expires_at, api_key.rotate_every_days);
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(name: &str, value: &str) -> ApiKey {
+        ApiKey::new(name.into(), value.into(), None, None, None, None)
+    }
+
+    fn fields(errors: &[FieldError]) -> Vec<&str> {
+        errors.iter().map(|e| e.field.as_str()).collect()
+    }
+
+    // 去除控制字符与空白，平台名称规范化为 id，空的可选字段视为未填写
+    #[test]
+    fn test_normalize_api_key() {
+        let mut api_key = key(" my\tkey ", " sk-value\n");
+        api_key.platform = Some(" Anthropic ".into());
+        api_key.description = Some("   ".into());
+        api_key.group_id = Some("".into());
+        api_key.tags = Some(" work , Work,, prod ".into());
+
+        normalize_api_key(&mut api_key).unwrap();
+
+        assert_eq!(api_key.name, "mykey");
+        assert_eq!(api_key.key_value, "sk-value");
+        assert_eq!(api_key.platform.as_deref(), Some("claude"));
+        assert_eq!(api_key.description, None);
+        assert_eq!(api_key.group_id, None);
+        assert_eq!(api_key.tags.as_deref(), Some("work,prod"));
+    }
+
+    // 未知平台保留用户输入
+    #[test]
+    fn test_normalize_unknown_platform() {
+        let mut api_key = key("name", "value");
+        api_key.platform = Some("  Internal Service ".into());
+        normalize_api_key(&mut api_key).unwrap();
+        assert_eq!(api_key.platform.as_deref(), Some("Internal Service"));
+    }
+
+    // 一次返回所有字段的错误
+    #[test]
+    fn test_normalize_reports_all_errors() {
+        let mut api_key = key("  ", "has space");
+        api_key.platform = Some("p".repeat(MAX_PLATFORM_LENGTH + 1));
+        api_key.description = Some("d".repeat(MAX_DESCRIPTION_LENGTH + 1));
+
+        let errors = normalize_api_key(&mut api_key).unwrap_err();
+        assert_eq!(fields(&errors), vec!["name", "keyValue", "platform", "description"]);
+
+        let mut api_key = key(&"n".repeat(MAX_NAME_LENGTH + 1), &"k".repeat(MAX_KEY_VALUE_LENGTH + 1));
+        let errors = normalize_api_key(&mut api_key).unwrap_err();
+        assert_eq!(fields(&errors), vec!["name", "keyValue"]);
+    }
+
+    // 过期时间必须为正数，轮换周期在 1 天到上限之间
+    #[test]
+    fn test_validate_schedule() {
+        assert!(validate_schedule(None, None).is_ok());
+        assert!(validate_schedule(Some(1_700_000_000), Some(MAX_PERIOD_DAYS)).is_ok());
+
+        let errors = validate_schedule(Some(0), Some(0)).unwrap_err();
+        assert_eq!(fields(&errors), vec!["expiresAt", "rotateEveryDays"]);
+        let errors = validate_schedule(None, Some(MAX_PERIOD_DAYS + 1)).unwrap_err();
+        assert_eq!(fields(&errors), vec!["rotateEveryDays"]);
+    }
+
+    // 长度按字符而不是字节计算
+    #[test]
+    fn test_length_counts_characters() {
+        let mut api_key = key(&"密".repeat(MAX_NAME_LENGTH), "value");
+        assert!(normalize_api_key(&mut api_key).is_ok());
+    }
+}
@@ -0,0 +1,269 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use crate::database::error::DatabaseError;
+
+// 保险库注册表文件，位于应用数据目录
+pub const REGISTRY_FILE: &str = "vaults.json";
+// 默认保险库沿用旧版本的数据库位置（应用数据目录根下）
+pub const DEFAULT_VAULT_ID: &str = "default";
+const DEFAULT_VAULT_NAME: &str = "Default";
+// 其他保险库各自存放在 vaults/<id>/ 目录下
+const VAULTS_DIR: &str = "vaults";
+const MAX_VAULT_NAME_LENGTH: usize = 100;
+
+// 注册表中的保险库条目
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultEntry {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultRegistry {
+    // 当前（下次启动时）打开的保险库
+    pub active: String,
+    pub vaults: Vec<VaultEntry>,
+}
+
+impl Default for VaultRegistry {
+    fn default() -> Self {
+        Self {
+            active: DEFAULT_VAULT_ID.to_string(),
+            vaults: vec![VaultEntry {
+                id: DEFAULT_VAULT_ID.to_string(),
+                name: DEFAULT_VAULT_NAME.to_string(),
+                created_at: 0,
+            }],
+        }
+    }
+}
+
+impl VaultRegistry {
+    pub fn find(&self, id: &str) -> Option<&VaultEntry> {
+        self.vaults.iter().find(|v| v.id == id)
+    }
+
+    // 当前保险库；注册表中的 active 无效时回退到默认保险库
+    pub fn active_vault(&self) -> VaultEntry {
+        self.find(&self.active)
+            .or_else(|| self.find(DEFAULT_VAULT_ID))
+            .cloned()
+            .unwrap_or_else(|| VaultRegistry::default().vaults.remove(0))
+    }
+}
+
+// 返回给前端的保险库信息
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultInfo {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: i64,
+    // 是否为当前打开的保险库
+    pub active: bool,
+}
+
+impl VaultInfo {
+    pub fn new(entry: &VaultEntry, active: bool) -> Self {
+        Self {
+            id: entry.id.clone(),
+            name: entry.name.clone(),
+            created_at: entry.created_at,
+            active,
+        }
+    }
+}
+
+// 读取注册表，文件不存在时返回只包含默认保险库的注册表
+pub fn load_registry(app_dir: &Path) -> Result<VaultRegistry, DatabaseError> {
+    let path = app_dir.join(REGISTRY_FILE);
+    if !path.exists() {
+        return Ok(VaultRegistry::default());
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| DatabaseError::Io(format!("Failed to read vault registry: {}", e)))?;
+    let mut registry: VaultRegistry = serde_json::from_str(&content)
+        .map_err(|e| DatabaseError::Io(format!("Invalid vault registry: {}", e)))?;
+    // 默认保险库始终存在
+    if registry.find(DEFAULT_VAULT_ID).is_none() {
+        registry.vaults.insert(0, VaultRegistry::default().vaults.remove(0));
+    }
+    Ok(registry)
+}
+
+// 写入注册表：先写临时文件再替换，避免写入中断导致注册表损坏
+pub fn save_registry(app_dir: &Path, registry: &VaultRegistry) -> Result<(), DatabaseError> {
+    let content = serde_json::to_string_pretty(registry)
+        .map_err(|e| DatabaseError::Io(format!("Failed to serialize vault registry: {}", e)))?;
+    let tmp_path = app_dir.join(format!("{}.tmp", REGISTRY_FILE));
+    std::fs::write(&tmp_path, content)
+        .map_err(|e| DatabaseError::Io(format!("Failed to write vault registry: {}", e)))?;
+    std::fs::rename(&tmp_path, app_dir.join(REGISTRY_FILE))
+        .map_err(|e| DatabaseError::Io(format!("Failed to write vault registry: {}", e)))
+}
+
+// 保险库的数据目录，必要时创建
+pub fn vault_dir(app_dir: &Path, id: &str) -> Result<PathBuf, DatabaseError> {
+    if id == DEFAULT_VAULT_ID {
+        return Ok(app_dir.to_path_buf());
+    }
+    let dir = app_dir.join(VAULTS_DIR).join(id);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| DatabaseError::Io(format!("Failed to create vault directory: {}", e)))?;
+    Ok(dir)
+}
+
+// 列出所有保险库
+pub fn list_vaults(app_dir: &Path) -> Result<Vec<VaultInfo>, DatabaseError> {
+    let registry = load_registry(app_dir)?;
+    let active = registry.active_vault().id;
+    Ok(registry
+        .vaults
+        .iter()
+        .map(|v| VaultInfo::new(v, v.id == active))
+        .collect())
+}
+
+// 由名称生成目录安全的 id，如 "Work Keys" -> "work-keys"
+fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+// 校验名称并生成新的保险库条目（尚未写入注册表）
+pub fn new_vault_entry(app_dir: &Path, name: &str) -> Result<VaultEntry, DatabaseError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(DatabaseError::InvalidInput("Vault name cannot be empty".to_string()));
+    }
+    if name.chars().count() > MAX_VAULT_NAME_LENGTH {
+        return Err(DatabaseError::InvalidInput(format!(
+            "Vault name cannot exceed {} characters",
+            MAX_VAULT_NAME_LENGTH
+        )));
+    }
+
+    let registry = load_registry(app_dir)?;
+    if registry.vaults.iter().any(|v| v.name.eq_ignore_ascii_case(name)) {
+        return Err(DatabaseError::Duplicate(format!("Vault {} already exists", name)));
+    }
+
+    // 非 ASCII 名称（如中文）无法生成 slug 时使用随机 id
+    let base = match slugify(name) {
+        slug if slug.is_empty() || slug == DEFAULT_VAULT_ID => {
+            uuid::Uuid::new_v4().simple().to_string()[..8].to_string()
+        }
+        slug => slug,
+    };
+    let mut id = base.clone();
+    let mut n = 2;
+    while registry.find(&id).is_some() || app_dir.join(VAULTS_DIR).join(&id).exists() {
+        id = format!("{}-{}", base, n);
+        n += 1;
+    }
+
+    Ok(VaultEntry {
+        id,
+        name: name.to_string(),
+        created_at: chrono::Utc::now().timestamp(),
+    })
+}
+
+// 将新保险库写入注册表
+pub fn add_vault(app_dir: &Path, entry: VaultEntry) -> Result<(), DatabaseError> {
+    let mut registry = load_registry(app_dir)?;
+    if registry.find(&entry.id).is_some() {
+        return Err(DatabaseError::Duplicate(format!("Vault {} already exists", entry.id)));
+    }
+    registry.vaults.push(entry);
+    save_registry(app_dir, &registry)
+}
+
+// 记录当前保险库，下次启动时自动打开
+pub fn set_active_vault(app_dir: &Path, id: &str) -> Result<(), DatabaseError> {
+    let mut registry = load_registry(app_dir)?;
+    if registry.active == id {
+        return Ok(());
+    }
+    registry.active = id.to_string();
+    save_registry(app_dir, &registry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 测试用的应用数据目录，离开作用域时删除
+    struct TempAppDir(PathBuf);
+
+    impl TempAppDir {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!("vaults-test-{}", uuid::Uuid::new_v4()));
+            std::fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+    }
+
+    impl Drop for TempAppDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Work Keys"), "work-keys");
+        assert_eq!(slugify("  --Client #42 / Prod--  "), "client-42-prod");
+        assert_eq!(slugify("a___b"), "a-b");
+        assert_eq!(slugify("工作"), "");
+        assert_eq!(slugify("../.."), "");
+    }
+
+    #[test]
+    fn test_new_vault_entry() {
+        let app_dir = TempAppDir::new();
+        let entry = new_vault_entry(&app_dir.0, "  Work Keys ").unwrap();
+        assert_eq!(entry.id, "work-keys");
+        assert_eq!(entry.name, "Work Keys");
+
+        // 名称不区分大小写不能重复，id 冲突时追加序号
+        add_vault(&app_dir.0, entry).unwrap();
+        assert!(matches!(new_vault_entry(&app_dir.0, "work keys"), Err(DatabaseError::Duplicate(_))));
+        assert_eq!(new_vault_entry(&app_dir.0, "Work-Keys!").unwrap().id, "work-keys-2");
+
+        // 已存在同名目录（如注册表丢失后残留）时同样避开
+        std::fs::create_dir_all(app_dir.0.join(VAULTS_DIR).join("personal")).unwrap();
+        assert_eq!(new_vault_entry(&app_dir.0, "Personal").unwrap().id, "personal-2");
+    }
+
+    #[test]
+    fn test_new_vault_entry_ids() {
+        // 无法生成 slug 或与默认保险库同名时使用随机 id
+        let app_dir = TempAppDir::new();
+        let chinese = new_vault_entry(&app_dir.0, "工作").unwrap();
+        assert_eq!(chinese.id.len(), 8);
+        assert!(chinese.id.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(new_vault_entry(&app_dir.0, "Default!").unwrap().id, DEFAULT_VAULT_ID);
+    }
+
+    #[test]
+    fn test_new_vault_entry_validation() {
+        let app_dir = TempAppDir::new();
+        assert!(matches!(new_vault_entry(&app_dir.0, "   "), Err(DatabaseError::InvalidInput(_))));
+        let long_name = "a".repeat(MAX_VAULT_NAME_LENGTH + 1);
+        assert!(matches!(new_vault_entry(&app_dir.0, &long_name), Err(DatabaseError::InvalidInput(_))));
+        assert!(new_vault_entry(&app_dir.0, &"密".repeat(MAX_VAULT_NAME_LENGTH)).is_ok());
+    }
+}
@@ -0,0 +1,300 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::time::Duration;
+use crate::database::api_key::{record_verification, ApiKey};
+use crate::database::error::DatabaseError;
+use crate::database::settings::get_setting;
+use crate::providers::{find_provider, AuthStyle, PROVIDERS};
+
+// settings 表中覆盖验证接口地址的键前缀，如 verifier_base_url.openai
+pub const BASE_URL_SETTING_PREFIX: &str = "verifier_base_url.";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+// 验证结果状态
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum VerificationStatus {
+    Valid,
+    Revoked,
+    QuotaExceeded,
+    NetworkError,
+    // 服务返回了无法归类的响应
+    Unknown,
+}
+
+impl VerificationStatus {
+    // 写入数据库的取值，与 serde 序列化结果一致
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerificationStatus::Valid => "valid",
+            VerificationStatus::Revoked => "revoked",
+            VerificationStatus::QuotaExceeded => "quotaExceeded",
+            VerificationStatus::NetworkError => "networkError",
+            VerificationStatus::Unknown => "unknown",
+        }
+    }
+
+    // 根据 HTTP 状态码归类
+    pub fn from_http_status(status: u16) -> Self {
+        match status {
+            200..=299 => VerificationStatus::Valid,
+            401 | 403 => VerificationStatus::Revoked,
+            402 | 429 => VerificationStatus::QuotaExceeded,
+            _ => VerificationStatus::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationResult {
+    #[serde(rename = "keyId")]
+    pub key_id: String,
+    pub status: VerificationStatus,
+    #[serde(rename = "checkedAt")]
+    pub checked_at: i64,
+    pub message: Option<String>,
+}
+
+impl VerificationResult {
+    pub fn new(key_id: &str, status: VerificationStatus, message: Option<String>) -> Self {
+        Self {
+            key_id: key_id.to_string(),
+            status,
+            checked_at: chrono::Utc::now().timestamp(),
+            message,
+        }
+    }
+}
+
+// 通过一次低成本的认证请求验证Key是否可用
+#[async_trait]
+pub trait KeyVerifier: Send + Sync {
+    // 对应的平台 id
+    fn provider(&self) -> &str;
+
+    // 返回状态与可选的说明信息
+    async fn verify(&self, client: &reqwest::Client, key: &str) -> (VerificationStatus, Option<String>);
+}
+
+// 基于 HTTP GET 的通用验证器，各平台只需配置地址与认证方式
+#[derive(Debug, Clone)]
+pub struct HttpVerifier {
+    pub provider: String,
+    pub base_url: String,
+    pub path: &'static str,
+    pub auth: AuthStyle,
+    pub extra_headers: &'static [(&'static str, &'static str)],
+}
+
+#[async_trait]
+impl KeyVerifier for HttpVerifier {
+    fn provider(&self) -> &str {
+        &self.provider
+    }
+
+    async fn verify(&self, client: &reqwest::Client, key: &str) -> (VerificationStatus, Option<String>) {
+        let url = format!("{}{}", self.base_url.trim_end_matches('/'), self.path);
+        let mut request = client.get(&url).timeout(REQUEST_TIMEOUT);
+        request = match self.auth {
+            AuthStyle::Bearer => request.bearer_auth(key),
+            AuthStyle::Header(name) => request.header(name, key),
+            AuthStyle::Query(name) => request.query(&[(name, key)]),
+        };
+        for (name, value) in self.extra_headers {
+            request = request.header(*name, *value);
+        }
+
+        match request.send().await {
+            Ok(response) => {
+                let status = response.status();
+                let verification = VerificationStatus::from_http_status(status.as_u16());
+                let message = if verification == VerificationStatus::Valid {
+                    None
+                } else {
+                    Some(format!("HTTP {}", status))
+                };
+                (verification, message)
+            }
+            Err(e) => (VerificationStatus::NetworkError, Some(e.to_string())),
+        }
+    }
+}
+
+// 根据平台名称创建验证器；base_url 为空时使用注册表中的默认地址
+pub fn verifier_for(platform: &str, base_url: Option<String>) -> Option<Box<dyn KeyVerifier>> {
+    let provider = find_provider(platform)?;
+    let endpoint = provider.verifier?;
+    Some(Box::new(HttpVerifier {
+        provider: provider.id.to_string(),
+        base_url: base_url.unwrap_or_else(|| endpoint.base_url.to_string()),
+        path: endpoint.path,
+        auth: endpoint.auth,
+        extra_headers: endpoint.extra_headers,
+    }))
+}
+
+// 支持在线验证的平台 id
+pub fn supported_providers() -> Vec<&'static str> {
+    PROVIDERS.iter().filter(|p| p.verifier.is_some()).map(|p| p.id).collect()
+}
+
+// 读取平台验证地址，settings 中有覆盖值时优先使用（便于指向本地模拟服务）
+pub async fn load_verifier(pool: &SqlitePool, platform: &str) -> Result<Option<Box<dyn KeyVerifier>>, DatabaseError> {
+    let id = find_provider(platform).map(|p| p.id).unwrap_or(platform);
+    let base_url = get_setting(pool, &format!("{}{}", BASE_URL_SETTING_PREFIX, id)).await?;
+    Ok(verifier_for(id, base_url))
+}
+
+// 验证单个Key并保存结果（包括网络错误，便于界面显示最近一次检查失败的原因）；
+// 平台不支持在线验证时返回 None
+pub async fn verify_and_record(
+    pool: &SqlitePool,
+    client: &reqwest::Client,
+    api_key: &ApiKey,
+) -> Result<Option<VerificationResult>, DatabaseError> {
+    let Some(platform) = api_key.platform.as_deref() else {
+        return Ok(None);
+    };
+    let Some(verifier) = load_verifier(pool, platform).await? else {
+        return Ok(None);
+    };
+
+    let (status, message) = verifier.verify(client, &api_key.key_value).await;
+    let result = VerificationResult::new(&api_key.id, status, message);
+    record_verification(pool, &api_key.id, status.as_str(), result.message.as_deref(), result.checked_at).await?;
+    println!("Verified API key {} ({}): {:?}", api_key.id, verifier.provider(), status);
+    Ok(Some(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::get;
+    use axum::Router;
+    use crate::database::api_key::{get_api_key_by_id, insert_api_key};
+    use crate::database::settings::set_setting;
+    use crate::database::TestDatabase;
+
+    // 本地模拟服务：按请求携带的Key返回不同的状态码
+    async fn mock_models(headers: HeaderMap) -> StatusCode {
+        let key = headers
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .or_else(|| headers.get("x-api-key").and_then(|v| v.to_str().ok()))
+            .unwrap_or_default();
+        match key {
+            "valid" => StatusCode::OK,
+            "revoked" => StatusCode::UNAUTHORIZED,
+            "forbidden" => StatusCode::FORBIDDEN,
+            "limited" => StatusCode::TOO_MANY_REQUESTS,
+            "broken" => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    async fn spawn_mock_server() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let app = Router::new().route("/v1/models", get(mock_models));
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        format!("http://{}", address)
+    }
+
+    fn mock_verifier(base_url: String, auth: AuthStyle) -> HttpVerifier {
+        HttpVerifier {
+            provider: "mock".to_string(),
+            base_url,
+            path: "/v1/models",
+            auth,
+            extra_headers: &[],
+        }
+    }
+
+    #[test]
+    fn test_from_http_status() {
+        assert_eq!(VerificationStatus::from_http_status(200), VerificationStatus::Valid);
+        assert_eq!(VerificationStatus::from_http_status(204), VerificationStatus::Valid);
+        assert_eq!(VerificationStatus::from_http_status(401), VerificationStatus::Revoked);
+        assert_eq!(VerificationStatus::from_http_status(403), VerificationStatus::Revoked);
+        assert_eq!(VerificationStatus::from_http_status(402), VerificationStatus::QuotaExceeded);
+        assert_eq!(VerificationStatus::from_http_status(429), VerificationStatus::QuotaExceeded);
+        assert_eq!(VerificationStatus::from_http_status(500), VerificationStatus::Unknown);
+        assert_eq!(VerificationStatus::from_http_status(404), VerificationStatus::Unknown);
+    }
+
+    #[tokio::test]
+    async fn test_http_verifier_responses() {
+        let base_url = spawn_mock_server().await;
+        let client = reqwest::Client::new();
+        let verifier = mock_verifier(base_url.clone(), AuthStyle::Bearer);
+
+        let cases = [
+            ("valid", VerificationStatus::Valid),
+            ("revoked", VerificationStatus::Revoked),
+            ("forbidden", VerificationStatus::Revoked),
+            ("limited", VerificationStatus::QuotaExceeded),
+            ("broken", VerificationStatus::Unknown),
+        ];
+        for (key, expected) in cases {
+            let (status, message) = verifier.verify(&client, key).await;
+            assert_eq!(status, expected, "{}", key);
+            // 只有失败时附带 HTTP 状态说明
+            assert_eq!(message.is_none(), expected == VerificationStatus::Valid, "{}", key);
+        }
+
+        // 自定义请求头方式认证，base_url 末尾的斜杠不影响路径
+        let verifier = mock_verifier(format!("{}/", base_url), AuthStyle::Header("x-api-key"));
+        assert_eq!(verifier.verify(&client, "valid").await.0, VerificationStatus::Valid);
+    }
+
+    #[tokio::test]
+    async fn test_http_verifier_connection_refused() {
+        // 先占用再释放端口，确保该端口上没有服务监听
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+
+        let verifier = mock_verifier(format!("http://{}", address), AuthStyle::Bearer);
+        let (status, message) = verifier.verify(&reqwest::Client::new(), "valid").await;
+        assert_eq!(status, VerificationStatus::NetworkError);
+        assert!(message.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_verify_and_record() {
+        // settings 中覆盖的地址指向模拟服务，结果写回Key记录
+        let db = TestDatabase::open().await;
+        let base_url = spawn_mock_server().await;
+        set_setting(&db.pool, &format!("{}openai", BASE_URL_SETTING_PREFIX), &base_url).await.unwrap();
+        let client = reqwest::Client::new();
+
+        let key = ApiKey::new("k".to_string(), "revoked".to_string(), Some("openai".to_string()), None, None, None);
+        insert_api_key(&db.pool, &key).await.unwrap();
+        let result = verify_and_record(&db.pool, &client, &key).await.unwrap().unwrap();
+        assert_eq!(result.status, VerificationStatus::Revoked);
+        let stored = get_api_key_by_id(&db.pool, &key.id).await.unwrap().unwrap();
+        assert_eq!(stored.verification_status.as_deref(), Some("revoked"));
+        assert_eq!(stored.verified_at, Some(result.checked_at));
+
+        // 网络错误同样保存
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let closed = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        set_setting(&db.pool, &format!("{}openai", BASE_URL_SETTING_PREFIX), &closed).await.unwrap();
+        let result = verify_and_record(&db.pool, &client, &key).await.unwrap().unwrap();
+        assert_eq!(result.status, VerificationStatus::NetworkError);
+        let stored = get_api_key_by_id(&db.pool, &key.id).await.unwrap().unwrap();
+        assert_eq!(stored.verification_status.as_deref(), Some("networkError"));
+        assert!(stored.verification_message.is_some());
+
+        // 不支持在线验证的平台跳过
+        let other = ApiKey::new("o".to_string(), "valid".to_string(), Some("acme".to_string()), None, None, None);
+        assert!(verify_and_record(&db.pool, &client, &other).await.unwrap().is_none());
+    }
+}
@@ -1,6 +1,6 @@
 import { useEffect, useRef, useState, useMemo } from "react";
 import { useTranslation } from "react-i18next";
-import { ApiKey } from "../../types/apiKey";
+import { ApiKeySummary } from "../../types/apiKey";
 import { searchService } from "../../services/searchService";
 import { RadialMenu } from "../RadialMenu/RadialMenu";
 import { SearchResults } from "../SearchResults/SearchResults";
@@ -11,7 +11,11 @@ import {
   GearIcon,
   CloseIcon,
 } from "../Icon/Icon";
-import { PROVIDERS, matchProvider } from "../../constants/providers";
+import {
+  PROVIDERS,
+  loadProviders,
+  matchProvider,
+} from "../../constants/providers";
 import { AddApiKeyDialog } from "../AddApiKey/AddApiKeyDialog";
 import { invoke } from "@tauri-apps/api/core";
 import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
@@ -19,6 +23,7 @@ import { LogicalSize, LogicalPosition } from "@tauri-apps/api/dpi";
 import { currentMonitor } from "@tauri-apps/api/window";
 import { SettingsPanel } from "../SettingsPanel/SettingsPanel";
 import { apiKeyService } from "../../services/apiKeyService";
+import { vaultService } from "../../services/vaultService";
 import "./FloatingToolbar.css";
 
 // 检查是否在Tauri环境中 - 更可靠的检测方法
@@ -35,7 +40,7 @@ interface FloatingToolbarProps {
 export function FloatingToolbar({ onClose }: FloatingToolbarProps) {
   const { t } = useTranslation();
   const [searchTerm, setSearchTerm] = useState("");
-  const [searchResults, setSearchResults] = useState<ApiKey[]>([]);
+  const [searchResults, setSearchResults] = useState<ApiKeySummary[]>([]);
   const [providerLabel, setProviderLabel] = useState<string | undefined>(
     undefined,
   );
@@ -239,6 +244,26 @@ export function FloatingToolbar({ onClose }: FloatingToolbarProps) {
     setActivePanel("search");
   };
 
+  // 切换或关闭保险库后清空当前窗口中属于旧保险库的数据
+  useEffect(() => {
+    if (!isTauri) return;
+
+    const unlisten = vaultService.onVaultChanged((vault) => {
+      console.log("Vault changed:", vault?.id ?? "closed");
+      setSearchTerm("");
+      setSearchResults([]);
+      setProviderLabel(undefined);
+      setPlatforms([]);
+      setProviderCounts({});
+      setShowRadialMenu(false);
+      setActivePanel("none");
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   const handleKeyDown = (e: React.KeyboardEvent) => {
     if (e.key === "Escape") onClose();
   };
@@ -282,7 +307,7 @@ export function FloatingToolbar({ onClose }: FloatingToolbarProps) {
     };
   }, []);
 
-  const copyToClipboard = async (key: ApiKey) => {
+  const copyToClipboard = async (key: ApiKeySummary) => {
     // 仅记录一次复制行为（不再折叠面板，避免打断子组件的模态提示）
     try {
       await apiKeyService.copyToClipboard(key.id);
@@ -342,6 +367,8 @@ export function FloatingToolbar({ onClose }: FloatingToolbarProps) {
       if (!showRadialMenu) return;
       setIsLoadingPlatforms(true);
       try {
+        await loadProviders();
+
         // 获取所有platform
         const res = await apiKeyService.getAllPlatforms();
         if (res.success) {
@@ -1,5 +1,5 @@
 import { useState, useEffect, useMemo, useCallback, memo } from "react";
-import { ApiKey } from "../../types/apiKey";
+import { ApiKeySummary } from "../../types/apiKey";
 import { apiKeyService } from "../../services/apiKeyService";
 import { useApiKeys } from "../../hooks/useApiKey";
 import { useApiToast } from "../../hooks/useToast";
@@ -16,51 +16,28 @@ const KeyManagerComponent = () => {
   const [selectedGroup, setSelectedGroup] = useState<string | null>(null);
   const [isCopying, setIsCopying] = useState(false);
 
-  // 模拟复制到剪贴板的函数
-  const copyToClipboard = async (text: string) => {
-    setIsCopying(true);
-    try {
-      // 在浏览器环境中使用navigator.clipboard
-      if (navigator.clipboard) {
-        await navigator.clipboard.writeText(text);
-        return true;
-      } else {
-        // 降级方案：创建临时textarea元素
-        const textarea = document.createElement("textarea");
-        textarea.value = text;
-        textarea.style.position = "fixed";
-        textarea.style.opacity = "0";
-        document.body.appendChild(textarea);
-        textarea.select();
-        const success = document.execCommand("copy");
-        document.body.removeChild(textarea);
-        return success;
-      }
-    } catch (error) {
-      console.error("复制失败:", error);
-      return false;
-    } finally {
-      setIsCopying(false);
-    }
-  };
-
   // 初始化时获取数据
   useEffect(() => {
     refetch();
   }, []);
 
-  // 复制API Key到剪贴板 - 使用useCallback避免重渲染
+  // 复制API Key到剪贴板 - 由后端按ID读取Key的值，使用useCallback避免重渲染
   const handleCopyToClipboard = useCallback(
-    async (keyValue: string) => {
-      const result = await copyToClipboard(keyValue);
-      if (result) {
-        // 显示成功提示
-        toast.showCopySuccess();
-      } else {
-        toast.showCopyError();
+    async (id: string) => {
+      setIsCopying(true);
+      try {
+        const result = await apiKeyService.copyToClipboard(id);
+        if (result.success) {
+          // 显示成功提示
+          toast.showCopySuccess();
+        } else {
+          toast.showCopyError();
+        }
+      } finally {
+        setIsCopying(false);
       }
     },
-    [copyToClipboard, toast],
+    [toast],
   );
 
   // 删除API Key - 使用useCallback避免重渲染
@@ -97,7 +74,7 @@ const KeyManagerComponent = () => {
 
   // 渲染单个API Key项 - 使用useCallback和groupMap优化性能
   const renderApiKeyItem = useCallback(
-    (key: ApiKey, index: number) => {
+    (key: ApiKeySummary, index: number) => {
       const group = key.groupId ? groupMap.get(key.groupId) : undefined;
 
       return (
@@ -135,7 +112,7 @@ const KeyManagerComponent = () => {
           <div className="col-span-3 text-sm font-medium">
             <div className="flex space-x-2">
               <button
-                onClick={() => handleCopyToClipboard(key.keyValue)}
+                onClick={() => handleCopyToClipboard(key.id)}
                 className="text-blue-600 hover:text-blue-900 dark:text-blue-400 dark:hover:text-blue-300 px-2 py-1 rounded"
                 disabled={isCopying}
               >
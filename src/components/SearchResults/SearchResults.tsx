@@ -1,15 +1,15 @@
 import { useState, useRef, useEffect } from "react";
 import { useTranslation } from "react-i18next";
 import { createPortal } from "react-dom";
-import { ApiKey } from "../../types/apiKey";
+import { ApiKey, ApiKeySummary } from "../../types/apiKey";
 import { CopyIcon, CheckIcon, EditIcon, CloseIcon } from "../Icon/Icon";
 import "./SearchResults.css";
 import { apiKeyService } from "../../services/apiKeyService";
 import { invoke } from "@tauri-apps/api/core";
 
 interface SearchResultsProps {
-  results: ApiKey[];
-  onCopy: (key: ApiKey) => void;
+  results: ApiKeySummary[];
+  onCopy: (key: ApiKeySummary) => void;
   onRefresh?: () => void; // 添加刷新回调
   onCopyConfirmed?: () => void; // 复制确认后收起父面板
 }
@@ -61,11 +61,11 @@ export function SearchResults({
     }
   }, [modalPos]);
 
-  const handleCopy = async (key: ApiKey) => {
+  const handleCopy = async (key: ApiKeySummary) => {
     try {
-      // 通过后端命令复制到系统剪贴板（不在前端日志中打印敏感值）
+      // 通过后端命令复制到系统剪贴板（不在前端日志中打印敏感值），后端记录审计日志
       const result = await invoke("copy_to_clipboard", {
-        content: key.keyValue,
+        keyId: key.id,
       });
 
       if (result) {
@@ -84,7 +84,7 @@ export function SearchResults({
         if (clearClipboardTimerRef.current)
           clearTimeout(clearClipboardTimerRef.current);
         clearClipboardTimerRef.current = window.setTimeout(() => {
-          invoke("copy_to_clipboard", { content: "" }).catch(() => {});
+          invoke("clear_clipboard").catch(() => {});
           clearClipboardTimerRef.current = null;
         }, 30000);
 
@@ -133,22 +133,24 @@ export function SearchResults({
     }
   };
 
-  const formatApiKey = (keyValue: string) => {
-    const prefixLen = 9;
-    const suffixLen = 9;
-    if (keyValue.length <= prefixLen + suffixLen) return keyValue;
-    const prefix = keyValue.substring(0, prefixLen);
-    const suffix = keyValue.substring(keyValue.length - suffixLen);
-    return `${prefix}...${suffix}`;
-  };
-
-  const startEditing = (key: ApiKey) => {
+  // 列表中不包含Key的值，编辑前先向后端查看（会记录审计日志）
+  const startEditing = async (key: ApiKeySummary) => {
+    const revealed = await apiKeyService.revealApiKey(key.id);
+    if (!revealed.success || revealed.data === undefined) {
+      setModal({
+        isOpen: true,
+        type: "error",
+        title: t("searchResults.editFailed"),
+        message: revealed.error?.message || t("searchResults.editFailedMessage"),
+      });
+      return;
+    }
     setEditingId(key.id);
     setEditName(key.name);
-    setEditKeyValue(key.keyValue);
+    setEditKeyValue(revealed.data);
   };
 
-  const saveEdit = async (key: ApiKey) => {
+  const saveEdit = async (key: ApiKeySummary) => {
     if (!editingId) return;
 
     const updatedKey: ApiKey = {
@@ -342,7 +344,7 @@ export function SearchResults({
                             )}
                           </div>
                           <div className="search-results-item-key">
-                            {formatApiKey(key.keyValue)}
+                            {key.keyPreview}
                           </div>
                         </div>
                         <div className="search-results-item-right-section">
@@ -1,7 +1,14 @@
+import { invoke } from "@tauri-apps/api/core";
+
+// 平台定义以后端注册表为准（list_providers），这里的静态列表仅作为加载前的回退
 export interface ProviderDef {
   id: string;
   label: string;
   aliases: string[];
+  keyPatterns?: string[];
+  envVar?: string;
+  docsUrl?: string;
+  verifiable?: boolean;
 }
 
 export const PROVIDERS: ProviderDef[] = [
@@ -20,8 +27,25 @@ export const PROVIDERS: ProviderDef[] = [
   { id: "cohere", label: "Cohere", aliases: ["cohere"] },
   { id: "azure", label: "Azure", aliases: ["azure", "microsoft"] },
   { id: "aws", label: "AWS", aliases: ["aws", "amazon"] },
+  { id: "deepseek", label: "DeepSeek", aliases: ["deepseek"] },
+  { id: "github", label: "GitHub", aliases: ["github", "gh"] },
 ];
 
+let providersLoaded = false;
+
+// 从后端加载平台注册表并原地替换 PROVIDERS，加载失败时保留静态列表
+export async function loadProviders(): Promise<ProviderDef[]> {
+  if (providersLoaded) return PROVIDERS;
+  try {
+    const providers = (await invoke("list_providers")) as ProviderDef[];
+    PROVIDERS.splice(0, PROVIDERS.length, ...providers);
+    providersLoaded = true;
+  } catch (error) {
+    console.error("Failed to load providers:", error);
+  }
+  return PROVIDERS;
+}
+
 export function matchProvider(
   name?: string,
   platform?: string,
@@ -1,10 +1,10 @@
 import { useState, useEffect } from "react";
-import { ApiKey, Group } from "../types/apiKey";
+import { ApiKeySummary, Group } from "../types/apiKey";
 import { apiKeyService, groupService } from "../services/apiKeyService";
 
 // 自定义Hook用于管理API Key相关的状态和逻辑
 export const useApiKeys = () => {
-  const [apiKeys, setApiKeys] = useState<ApiKey[]>([]);
+  const [apiKeys, setApiKeys] = useState<ApiKeySummary[]>([]);
   const [groups, setGroups] = useState<Group[]>([]);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
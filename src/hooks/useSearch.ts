@@ -1,11 +1,11 @@
 import { useState, useEffect, useMemo } from "react";
-import { ApiKey } from "../types/apiKey";
+import { ApiKeySummary } from "../types/apiKey";
 import { searchService } from "../services/searchService";
 
 // 自定义Hook用于管理搜索相关的状态和逻辑
-export const useSearch = (items: ApiKey[], searchFields: (keyof ApiKey)[]) => {
+export const useSearch = (items: ApiKeySummary[], searchFields: (keyof ApiKeySummary)[]) => {
   const [searchTerm, setSearchTerm] = useState("");
-  const [searchResults, setSearchResults] = useState<ApiKey[]>([]);
+  const [searchResults, setSearchResults] = useState<ApiKeySummary[]>([]);
   const [isSearching, setIsSearching] = useState(false);
   const [searchError, setSearchError] = useState<string | null>(null);
 
@@ -0,0 +1,40 @@
+import { invoke } from "@tauri-apps/api/core";
+import { AgentStatus, AuditEntry } from "../types/apiKey";
+import { ServiceResult, ErrorCode, wrapServiceOperation } from "./errors";
+
+export const agentService = {
+  /**
+   * Gets the state of the local key agent that answers requests from the CLI and other tools
+   * @returns Promise<ServiceResult<AgentStatus>> - Result containing the agent status or error information
+   */
+  async getStatus(): Promise<ServiceResult<AgentStatus>> {
+    return wrapServiceOperation(
+      () => invoke("get_agent_status") as Promise<AgentStatus>,
+      ErrorCode.DATABASE_ERROR,
+    );
+  },
+
+  /**
+   * Sets whether key requests from the agent must be approved in a confirmation prompt
+   * @param enabled - Whether to ask before handing out a key
+   * @returns Promise<ServiceResult<boolean>> - Result containing the saved value or error information
+   */
+  async setConfirmRequests(enabled: boolean): Promise<ServiceResult<boolean>> {
+    return wrapServiceOperation(
+      () => invoke("set_agent_confirm_requests", { enabled }) as Promise<boolean>,
+      ErrorCode.DATABASE_ERROR,
+    );
+  },
+
+  /**
+   * Lists the most recent audit log entries, newest first
+   * @param limit - Maximum number of entries to return
+   * @returns Promise<ServiceResult<AuditEntry[]>> - Result containing the entries or error information
+   */
+  async listAuditLog(limit?: number): Promise<ServiceResult<AuditEntry[]>> {
+    return wrapServiceOperation(
+      () => invoke("list_audit_log", { limit: limit ?? null }) as Promise<AuditEntry[]>,
+      ErrorCode.DATABASE_ERROR,
+    );
+  },
+};
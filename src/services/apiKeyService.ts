@@ -1,5 +1,6 @@
 import {
   ApiKey,
+  ApiKeySummary,
   Group,
   UsageHistory,
   BatchApiKey,
@@ -117,12 +118,19 @@ export const apiKeyService = {
       updatedAt: Date.now(),
     };
 
+    // 后端会重新分配 id 与时间戳并规范化字段，以返回的记录为准
     return executeOperation(
-      () => invoke("add_api_key", { apiKey: newApiKey }) as Promise<boolean>,
+      () => invoke("add_api_key", { apiKey: newApiKey }) as Promise<ApiKey>,
       OperationContext.API_KEY_ADD,
     ).then((result) => {
       if (result.success && result.data) {
-        return createSuccessResult(newApiKey);
+        return createSuccessResult(result.data);
+      } else if (result.error) {
+        return createErrorResult<ApiKey>(
+          result.error.code,
+          result.error.message,
+          result.error.details,
+        );
       } else {
         return createErrorResult(ErrorCode.API_KEY_INVALID, "添加API Key失败");
       }
@@ -133,9 +141,9 @@ export const apiKeyService = {
   /**
    * Updates an existing API key with validation and security checks
    * @param apiKey - Complete API key object including id and system fields
-   * @returns Promise<ServiceResult<ApiKey>> - Result containing the updated API key or error information
+   * @returns Promise<ServiceResult<ApiKeySummary>> - Result containing the updated key summary (without the key value) or error information
    */
-  async editApiKey(apiKey: ApiKey): Promise<ServiceResult<ApiKey>> {
+  async editApiKey(apiKey: ApiKey): Promise<ServiceResult<ApiKeySummary>> {
     // 验证和清理输入
     const validation = validateAndSanitizeApiKey(apiKey);
     if (!validation.isValid) {
@@ -158,15 +166,29 @@ export const apiKeyService = {
       tags: apiKey.tags,
       createdAt: apiKey.createdAt, // 保持原有创建时间
       updatedAt: Date.now(),
+      // 过期与轮换设置、最近使用时间原样带回；清除过期设置使用 setKeySchedule
+      lastUsedAt: apiKey.lastUsedAt,
+      expiresAt: apiKey.expiresAt,
+      rotateEveryDays: apiKey.rotateEveryDays,
     };
 
+    // expectedUpdatedAt 为读取时的版本，其他窗口已修改该记录时后端返回 CONFLICT
     return executeOperation(
       () =>
-        invoke("edit_api_key", { apiKey: updatedApiKey }) as Promise<boolean>,
+        invoke("edit_api_key", {
+          apiKey: updatedApiKey,
+          expectedUpdatedAt: apiKey.updatedAt,
+        }) as Promise<ApiKeySummary>,
       OperationContext.API_KEY_EDIT,
     ).then((result) => {
       if (result.success && result.data) {
-        return createSuccessResult(updatedApiKey);
+        return createSuccessResult(result.data);
+      } else if (result.error) {
+        return createErrorResult<ApiKey>(
+          result.error.code,
+          result.error.message,
+          result.error.details,
+        );
       } else {
         return createErrorResult(ErrorCode.API_KEY_INVALID, "编辑API Key失败");
       }
@@ -204,14 +226,68 @@ export const apiKeyService = {
     });
   },
 
+  // 查看API Key的值
+  /**
+   * Reveals the value of an API key; every reveal is recorded in the audit log
+   * @param id - The unique identifier of the API key to reveal
+   * @returns Promise<ServiceResult<string>> - Result containing the key value or error information
+   */
+  async revealApiKey(id: string): Promise<ServiceResult<string>> {
+    // 验证ID
+    const idValidation = validateAndHandleId(id);
+    if (!idValidation.success) {
+      return createErrorResult(
+        ErrorCode.INVALID_INPUT,
+        idValidation.error?.message || "Invalid ID",
+      );
+    }
+
+    return executeOperation(
+      () => invoke("reveal_api_key", { keyId: id }) as Promise<string>,
+      OperationContext.API_KEY_SEARCH,
+    );
+  },
+
+  // 设置或清除Key的过期时间与轮换周期
+  /**
+   * Sets or clears the expiry time and rotation period of an API key
+   * @param id - The unique identifier of the API key
+   * @param expiresAt - Expiry timestamp in seconds, undefined to clear
+   * @param rotateEveryDays - Rotation period in days, undefined to clear
+   * @returns Promise<ServiceResult<boolean>> - Result indicating success/failure
+   */
+  async setKeySchedule(
+    id: string,
+    expiresAt?: number,
+    rotateEveryDays?: number,
+  ): Promise<ServiceResult<boolean>> {
+    const idValidation = validateAndHandleId(id);
+    if (!idValidation.success) {
+      return createErrorResult(
+        ErrorCode.INVALID_INPUT,
+        idValidation.error?.message || "Invalid ID",
+      );
+    }
+
+    return executeOperation(
+      () =>
+        invoke("set_key_schedule", {
+          keyId: id,
+          expiresAt: expiresAt ?? null,
+          rotateEveryDays: rotateEveryDays ?? null,
+        }) as Promise<boolean>,
+      OperationContext.API_KEY_EDIT,
+    );
+  },
+
   // 获取API Key列表
   /**
-   * Retrieves all API keys from the system
-   * @returns Promise<ServiceResult<ApiKey[]>> - Result containing array of all API keys or error information
+   * Retrieves all API keys from the system, without their values
+   * @returns Promise<ServiceResult<ApiKeySummary[]>> - Result containing array of all API key summaries or error information
    */
-  async listApiKeys(): Promise<ServiceResult<ApiKey[]>> {
+  async listApiKeys(): Promise<ServiceResult<ApiKeySummary[]>> {
     return executeOperation(
-      () => invoke("list_api_keys") as Promise<ApiKey[]>,
+      () => invoke("list_api_keys") as Promise<ApiKeySummary[]>,
       OperationContext.API_KEY_SEARCH,
       { operation: "list_keys" },
     );
@@ -221,9 +297,9 @@ export const apiKeyService = {
   /**
    * Searches API keys by keyword across name, platform, and description
    * @param keyword - Search term to match against API key fields
-   * @returns Promise<ServiceResult<ApiKey[]>> - Result containing matching API keys or error information
+   * @returns Promise<ServiceResult<ApiKeySummary[]>> - Result containing matching API key summaries or error information
    */
-  async searchKeys(keyword: string): Promise<ServiceResult<ApiKey[]>> {
+  async searchKeys(keyword: string): Promise<ServiceResult<ApiKeySummary[]>> {
     // 验证和清理搜索关键词
     if (typeof keyword !== "string") {
       return createErrorResult(
@@ -251,7 +327,7 @@ export const apiKeyService = {
     return executeOperation(
       () =>
         invoke("search_api_keys", { keyword: sanitizedKeyword }) as Promise<
-          ApiKey[]
+          ApiKeySummary[]
         >,
       OperationContext.API_KEY_SEARCH,
       { operation: "search_keys" },
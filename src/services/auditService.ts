@@ -0,0 +1,28 @@
+import { invoke } from "@tauri-apps/api/core";
+import { AuditChainReport, AuditEntry, AuditFilter } from "../types/apiKey";
+import { ServiceResult, ErrorCode, wrapServiceOperation } from "./errors";
+
+export const auditService = {
+  /**
+   * Lists audit log entries matching the filter, newest first
+   * @param filter - Optional source, action, key, outcome and time range conditions
+   * @returns Promise<ServiceResult<AuditEntry[]>> - Result containing the entries or error information
+   */
+  async listAuditEvents(filter?: AuditFilter): Promise<ServiceResult<AuditEntry[]>> {
+    return wrapServiceOperation(
+      () => invoke("list_audit_events", { filter: filter ?? null }) as Promise<AuditEntry[]>,
+      ErrorCode.DATABASE_ERROR,
+    );
+  },
+
+  /**
+   * Recomputes the audit log hash chain to detect modified, removed or inserted entries
+   * @returns Promise<ServiceResult<AuditChainReport>> - Result containing the verification report or error information
+   */
+  async verifyAuditChain(): Promise<ServiceResult<AuditChainReport>> {
+    return wrapServiceOperation(
+      () => invoke("verify_audit_chain") as Promise<AuditChainReport>,
+      ErrorCode.DATABASE_ERROR,
+    );
+  },
+};
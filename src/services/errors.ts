@@ -46,6 +46,34 @@ export enum ErrorCode {
   DATABASE_ERROR = "DATABASE_ERROR",
   DATABASE_CONNECTION_FAILED = "DATABASE_CONNECTION_FAILED",
   DATABASE_QUERY_FAILED = "DATABASE_QUERY_FAILED",
+  DATABASE_NOT_READY = "DATABASE_NOT_READY",
+  DATABASE_CORRUPTED = "DATABASE_CORRUPTED",
+
+  // 后端命令错误 (8000-8999)，与 src-tauri/src/error.rs 的 AppError 对应
+  VAULT_LOCKED = "VAULT_LOCKED",
+  TOO_MANY_ATTEMPTS = "TOO_MANY_ATTEMPTS",
+  IO_ERROR = "IO_ERROR",
+  WINDOW_ERROR = "WINDOW_ERROR",
+}
+
+// Tauri 命令返回的错误结构
+export interface BackendError {
+  code: ErrorCode;
+  message: string;
+  // 字段校验失败时返回的逐字段错误
+  fields?: { field: string; message: string }[];
+  // 主密码尝试次数过多时需要等待的秒数
+  retryAfter?: number;
+}
+
+// 判断是否为后端 AppError
+export function isBackendError(error: unknown): error is BackendError {
+  return (
+    typeof error === "object" &&
+    error !== null &&
+    typeof (error as BackendError).code === "string" &&
+    typeof (error as BackendError).message === "string"
+  );
 }
 
 // 服务结果接口
@@ -162,6 +190,17 @@ export function errorToResult<T = never>(
     return createErrorResult(error.code, error.message, error.details);
   }
 
+  if (isBackendError(error)) {
+    const details: Record<string, unknown> = {};
+    if (error.fields) details.fields = error.fields;
+    if (error.retryAfter !== undefined) details.retryAfter = error.retryAfter;
+    return createErrorResult(
+      error.code,
+      error.message,
+      Object.keys(details).length > 0 ? details : undefined,
+    );
+  }
+
   if (error instanceof Error) {
     return createErrorResult(defaultCode, error.message, {
       stack: error.stack,
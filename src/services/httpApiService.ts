@@ -0,0 +1,82 @@
+import { invoke } from "@tauri-apps/api/core";
+import {
+  ApiToken,
+  HttpApiConfig,
+  HttpApiStatus,
+  IssuedApiToken,
+} from "../types/apiKey";
+import { ServiceResult, ErrorCode, wrapServiceOperation } from "./errors";
+
+export const httpApiService = {
+  /**
+   * Gets the configuration of the loopback HTTP API and the port it listens on
+   * @returns Promise<ServiceResult<HttpApiStatus>> - Result containing the status or error information
+   */
+  async getStatus(): Promise<ServiceResult<HttpApiStatus>> {
+    return wrapServiceOperation(
+      () => invoke("get_http_api_status") as Promise<HttpApiStatus>,
+      ErrorCode.DATABASE_ERROR,
+    );
+  },
+
+  /**
+   * Saves the configuration and starts or stops the server accordingly
+   * @param config - New HTTP API configuration
+   * @returns Promise<ServiceResult<HttpApiStatus>> - Result containing the new status or error information
+   */
+  async setConfig(config: HttpApiConfig): Promise<ServiceResult<HttpApiStatus>> {
+    return wrapServiceOperation(
+      () => invoke("set_http_api_config", { config }) as Promise<HttpApiStatus>,
+      ErrorCode.DATABASE_ERROR,
+    );
+  },
+
+  /**
+   * Lists access tokens without their secrets
+   * @returns Promise<ServiceResult<ApiToken[]>> - Result containing the tokens or error information
+   */
+  async listTokens(): Promise<ServiceResult<ApiToken[]>> {
+    return wrapServiceOperation(
+      () => invoke("list_api_tokens") as Promise<ApiToken[]>,
+      ErrorCode.DATABASE_ERROR,
+    );
+  },
+
+  /**
+   * Creates an access token limited to the given groups or tags; the secret is only returned once
+   * @param name - Name of the client using the token
+   * @param groupIds - Groups the token may access
+   * @param tags - Tags the token may access
+   * @param allowSecrets - Whether the token may read key values
+   * @returns Promise<ServiceResult<IssuedApiToken>> - Result containing the token and its secret or error information
+   */
+  async createToken(
+    name: string,
+    groupIds: string[],
+    tags: string[],
+    allowSecrets: boolean,
+  ): Promise<ServiceResult<IssuedApiToken>> {
+    return wrapServiceOperation(
+      () =>
+        invoke("create_api_token", {
+          name,
+          groupIds,
+          tags,
+          allowSecrets,
+        }) as Promise<IssuedApiToken>,
+      ErrorCode.DATABASE_ERROR,
+    );
+  },
+
+  /**
+   * Revokes an access token
+   * @param tokenId - ID of the token to revoke
+   * @returns Promise<ServiceResult<boolean>> - Whether the token existed
+   */
+  async revokeToken(tokenId: string): Promise<ServiceResult<boolean>> {
+    return wrapServiceOperation(
+      () => invoke("revoke_api_token", { tokenId }) as Promise<boolean>,
+      ErrorCode.DATABASE_ERROR,
+    );
+  },
+};
@@ -0,0 +1,83 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import { ProjectInfo, ProjectSyncReport } from "../types/apiKey";
+import { ServiceResult, ErrorCode, wrapServiceOperation } from "./errors";
+
+export const projectService = {
+  /**
+   * Lists project directories with bound keys, flagging those whose .env is stale
+   * @returns Promise<ServiceResult<ProjectInfo[]>> - Result containing the projects or error information
+   */
+  async listProjects(): Promise<ServiceResult<ProjectInfo[]>> {
+    return wrapServiceOperation(
+      () => invoke("list_projects") as Promise<ProjectInfo[]>,
+      ErrorCode.DATABASE_ERROR,
+    );
+  },
+
+  /**
+   * Binds an environment variable of a project to a key
+   * @param projectPath - Project root directory
+   * @param envVar - Variable name written to the project's .env
+   * @param key - Key id or name
+   * @returns Promise<ServiceResult<ProjectInfo>> - Result containing the updated project or error information
+   */
+  async bindKey(
+    projectPath: string,
+    envVar: string,
+    key: string,
+  ): Promise<ServiceResult<ProjectInfo>> {
+    return wrapServiceOperation(
+      () =>
+        invoke("bind_project_key", { projectPath, envVar, key }) as Promise<ProjectInfo>,
+      ErrorCode.DATABASE_ERROR,
+    );
+  },
+
+  /**
+   * Removes one variable binding, or the whole project when no variable is given
+   * @param projectPath - Project root directory
+   * @param envVar - Optional variable name
+   * @returns Promise<ServiceResult<number>> - Number of removed bindings
+   */
+  async unbind(
+    projectPath: string,
+    envVar?: string,
+  ): Promise<ServiceResult<number>> {
+    return wrapServiceOperation(
+      () =>
+        invoke("unbind_project", {
+          projectPath,
+          envVar: envVar ?? null,
+        }) as Promise<number>,
+      ErrorCode.DATABASE_ERROR,
+    );
+  },
+
+  /**
+   * Writes or refreshes the project's .env from its bindings
+   * @param projectPath - Project root directory
+   * @returns Promise<ServiceResult<ProjectSyncReport>> - Result containing the sync report or error information
+   */
+  async syncProject(
+    projectPath: string,
+  ): Promise<ServiceResult<ProjectSyncReport>> {
+    return wrapServiceOperation(
+      () => invoke("sync_project", { projectPath }) as Promise<ProjectSyncReport>,
+      ErrorCode.IO_ERROR,
+    );
+  },
+
+  /**
+   * Subscribes to notifications about projects whose .env became stale after a key was rotated
+   * @param handler - Called with the stale projects
+   * @returns Promise<UnlistenFn> - Function to remove the listener
+   */
+  onProjectsStale(
+    handler: (projects: ProjectInfo[]) => void,
+  ): Promise<UnlistenFn> {
+    return listen<ProjectInfo[]>("projects-stale", (event) =>
+      handler(event.payload),
+    );
+  },
+};
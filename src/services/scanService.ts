@@ -0,0 +1,30 @@
+import { invoke } from "@tauri-apps/api/core";
+import { GitScanReport, ScanReport } from "../types/apiKey";
+import { ServiceResult, ErrorCode, wrapServiceOperation } from "./errors";
+
+export const scanService = {
+  /**
+   * Scans a directory for leaked keys, flagging those that match keys stored in the vault
+   * @param path - Directory to scan
+   * @param includeIgnored - Also scan files excluded by .gitignore
+   * @returns Promise<ServiceResult<ScanReport>> - Result containing the report or error information
+   */
+  async scanDirectory(path: string, includeIgnored = false): Promise<ServiceResult<ScanReport>> {
+    return wrapServiceOperation(
+      () => invoke("scan_directory", { path, includeIgnored }) as Promise<ScanReport>,
+      ErrorCode.IO_ERROR,
+    );
+  },
+
+  /**
+   * Scans the full commit history of a local git repository for committed keys
+   * @param path - Repository directory or any directory inside it
+   * @returns Promise<ServiceResult<GitScanReport>> - Result containing the report, including vault keys that must be rotated
+   */
+  async scanGitHistory(path: string): Promise<ServiceResult<GitScanReport>> {
+    return wrapServiceOperation(
+      () => invoke("scan_git_history", { path }) as Promise<GitScanReport>,
+      ErrorCode.IO_ERROR,
+    );
+  },
+};
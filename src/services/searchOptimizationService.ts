@@ -3,13 +3,13 @@
  * 提供搜索缓存、结果排序、智能搜索建议等功能
  */
 
-import { ApiKey } from "../types/apiKey";
+import { ApiKeySummary } from "../types/apiKey";
 import { searchService } from "./searchService";
 
 // 缓存接口
 interface SearchCache {
   keyword: string;
-  results: ApiKey[];
+  results: ApiKeySummary[];
   timestamp: number;
   ttl: number; // Time to live in milliseconds
 }
@@ -56,7 +56,7 @@ export class SearchOptimizationService {
    */
   async searchKeys(
     keyword: string,
-  ): Promise<{ data: ApiKey[]; error?: string }> {
+  ): Promise<{ data: ApiKeySummary[]; error?: string }> {
     // 验证输入
     if (!keyword || typeof keyword !== "string") {
       return { data: [], error: "搜索关键词无效" };
@@ -96,7 +96,7 @@ export class SearchOptimizationService {
    */
   async smartSearch(
     keyword: string,
-  ): Promise<{ data: ApiKey[]; error?: string }> {
+  ): Promise<{ data: ApiKeySummary[]; error?: string }> {
     if (!this.config.enableFuzzySearch) {
       return this.searchKeys(keyword);
     }
@@ -161,7 +161,7 @@ export class SearchOptimizationService {
   /**
    * 从缓存获取结果
    */
-  private getFromCache(keyword: string): ApiKey[] | null {
+  private getFromCache(keyword: string): ApiKeySummary[] | null {
     const cache = this.cache.get(keyword);
     if (!cache) {
       return null;
@@ -179,7 +179,7 @@ export class SearchOptimizationService {
   /**
    * 添加结果到缓存
    */
-  private addToCache(keyword: string, results: ApiKey[]): void {
+  private addToCache(keyword: string, results: ApiKeySummary[]): void {
     // 如果缓存已满，先清理
     if (this.cache.size >= this.config.maxCacheSize) {
       this.cleanupCache();
@@ -196,7 +196,7 @@ export class SearchOptimizationService {
   /**
    * 更新搜索建议
    */
-  private updateSuggestions(_keyword: string, results: ApiKey[]): void {
+  private updateSuggestions(_keyword: string, results: ApiKeySummary[]): void {
     results.forEach((result) => {
       // 从名称提取建议
       if (result.name) {
@@ -251,7 +251,7 @@ export class SearchOptimizationService {
    */
   private async performFuzzySearch(
     keyword: string,
-  ): Promise<{ data: ApiKey[]; error?: string }> {
+  ): Promise<{ data: ApiKeySummary[]; error?: string }> {
     try {
       // 获取所有API Keys
       const allKeysResult = await searchService.searchKeys("");
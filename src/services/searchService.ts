@@ -1,4 +1,4 @@
-import { ApiKey } from "../types/apiKey";
+import { ApiKeySummary } from "../types/apiKey";
 import { invoke } from "@tauri-apps/api/core";
 import {
   logSecureError,
@@ -11,7 +11,7 @@ export const searchService = {
   // 搜索API Key
   async searchKeys(
     keyword: string,
-  ): Promise<{ data: ApiKey[]; error?: string }> {
+  ): Promise<{ data: ApiKeySummary[]; error?: string }> {
     try {
       // 验证搜索关键词
       if (typeof keyword !== "string") {
@@ -23,7 +23,7 @@ export const searchService = {
       // 直接调用Tauri后端命令，不进行环境检测
       const result = await invoke("search_api_keys", { keyword });
       console.log("SearchService: Tauri search successful, results:", result);
-      return { data: result as ApiKey[], error: undefined };
+      return { data: result as ApiKeySummary[], error: undefined };
     } catch (error) {
       console.error("SearchService: Tauri search failed:", error);
       logSecureError(OperationContext.API_KEY_SEARCH, error);
@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { LockoutPolicy, UnlockAttemptStatus } from "../types/apiKey";
 import { logSecureError, OperationContext } from "./secureLogging";
 
 // 安全服务
@@ -25,6 +26,26 @@ export const securityService = {
     }
   },
 
+  // 查询主密码连续失败次数与需要等待的时间
+  async getUnlockAttemptStatus(): Promise<UnlockAttemptStatus | null> {
+    try {
+      return await invoke("get_unlock_attempt_status");
+    } catch (error) {
+      logSecureError(OperationContext.MASTER_PASSWORD_VERIFY, error);
+      return null;
+    }
+  },
+
+  // 设置主密码锁定策略
+  async setLockoutPolicy(policy: LockoutPolicy): Promise<boolean> {
+    try {
+      return await invoke("set_lockout_policy", { policy });
+    } catch (error) {
+      logSecureError(OperationContext.MASTER_PASSWORD_SET, error);
+      return false;
+    }
+  },
+
   // 加密API Key
   async encryptKey(key: string): Promise<string> {
     try {
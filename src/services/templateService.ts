@@ -0,0 +1,31 @@
+import { invoke } from "@tauri-apps/api/core";
+import { RenderReport } from "../types/apiKey";
+import { ServiceResult, ErrorCode, wrapServiceOperation } from "./errors";
+
+export const templateService = {
+  /**
+   * Fills a template such as .env.example with stored keys and writes it with owner-only permissions
+   * @param path - Path of the template file
+   * @param mappings - Optional variable name to key id or name mappings
+   * @param outputPath - Output file; derived from the template name when omitted
+   * @param allowUnresolved - Write the file even if some placeholders cannot be resolved
+   * @returns Promise<ServiceResult<RenderReport>> - Result containing the report or error information
+   */
+  async renderTemplate(
+    path: string,
+    mappings?: Record<string, string>,
+    outputPath?: string,
+    allowUnresolved = false,
+  ): Promise<ServiceResult<RenderReport>> {
+    return wrapServiceOperation(
+      () =>
+        invoke("render_template", {
+          path,
+          mappings: mappings ?? null,
+          outputPath: outputPath ?? null,
+          allowUnresolved,
+        }) as Promise<RenderReport>,
+      ErrorCode.IO_ERROR,
+    );
+  },
+};
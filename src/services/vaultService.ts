@@ -0,0 +1,81 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import { VaultInfo } from "../types/vault";
+import { ServiceResult, ErrorCode, wrapServiceOperation } from "./errors";
+
+export const vaultService = {
+  /**
+   * Lists all vaults registered on this device
+   * @returns Promise<ServiceResult<VaultInfo[]>> - Result containing the vaults or error information
+   */
+  async listVaults(): Promise<ServiceResult<VaultInfo[]>> {
+    return wrapServiceOperation(
+      () => invoke("list_vaults") as Promise<VaultInfo[]>,
+      ErrorCode.DATABASE_ERROR,
+    );
+  },
+
+  /**
+   * Creates a new vault with its own database file, optionally protected by a master password
+   * @param name - Display name of the vault
+   * @param masterPassword - Optional master password for the new vault
+   * @returns Promise<ServiceResult<VaultInfo>> - Result containing the created vault or error information
+   */
+  async createVault(
+    name: string,
+    masterPassword?: string,
+  ): Promise<ServiceResult<VaultInfo>> {
+    return wrapServiceOperation(
+      () =>
+        invoke("create_vault", {
+          name,
+          masterPassword: masterPassword || null,
+        }) as Promise<VaultInfo>,
+      ErrorCode.DATABASE_ERROR,
+    );
+  },
+
+  /**
+   * Switches to another vault; the vault is unlocked when the correct password is given
+   * @param vaultId - ID of the vault to open
+   * @param password - Optional master password of the vault
+   * @returns Promise<ServiceResult<VaultInfo>> - Result containing the opened vault or error information
+   */
+  async openVault(
+    vaultId: string,
+    password?: string,
+  ): Promise<ServiceResult<VaultInfo>> {
+    return wrapServiceOperation(
+      () =>
+        invoke("open_vault", {
+          vaultId,
+          password: password || null,
+        }) as Promise<VaultInfo>,
+      ErrorCode.DATABASE_ERROR,
+    );
+  },
+
+  /**
+   * Closes the current vault and locks it
+   * @returns Promise<ServiceResult<boolean>> - Whether a vault was open
+   */
+  async closeVault(): Promise<ServiceResult<boolean>> {
+    return wrapServiceOperation(
+      () => invoke("close_vault") as Promise<boolean>,
+      ErrorCode.DATABASE_ERROR,
+    );
+  },
+
+  /**
+   * Subscribes to vault switches from any window; payload is null when the vault was closed
+   * @param handler - Called with the newly opened vault
+   * @returns Promise<UnlistenFn> - Function to remove the listener
+   */
+  onVaultChanged(
+    handler: (vault: VaultInfo | null) => void,
+  ): Promise<UnlistenFn> {
+    return listen<VaultInfo | null>("vault-changed", (event) =>
+      handler(event.payload),
+    );
+  },
+};
@@ -9,6 +9,31 @@ export interface ApiKey {
   createdAt: number;
   updatedAt: number;
   lastUsedAt?: number;
+  deletedAt?: number;
+  expiresAt?: number;
+  rotateEveryDays?: number;
+  verificationStatus?: VerificationStatus;
+  verificationMessage?: string;
+  verifiedAt?: number;
+}
+
+// 列表与搜索返回的Key摘要，不包含Key的值；需要值时调用 revealApiKey 或 copyToClipboard
+export interface ApiKeySummary extends Omit<ApiKey, "keyValue"> {
+  keyPreview: string;
+}
+
+export type VerificationStatus =
+  | "valid"
+  | "revoked"
+  | "quotaExceeded"
+  | "networkError"
+  | "unknown";
+
+export interface VerificationResult {
+  keyId: string;
+  status: VerificationStatus;
+  checkedAt: number;
+  message?: string;
 }
 
 export interface Group {
@@ -23,6 +48,33 @@ export interface UsageHistory {
   id: string;
   keyId: string;
   usedAt: number;
+  action?: string;
+}
+
+// 历史版本不包含旧的Key值；需要时调用 reveal_key_version
+export interface KeyVersion {
+  id: string;
+  keyId: string;
+  name: string;
+  keyPreview: string;
+  platform?: string;
+  description?: string;
+  groupId?: string;
+  tags?: string;
+  validFrom?: number;
+  replacedAt: number;
+  reason?: string;
+  changedFields?: string;
+}
+
+// keys-expiring 事件与 expiring_keys 命令返回的提醒项
+export interface ExpiringKey {
+  keyId: string;
+  name: string;
+  platform?: string;
+  kind: "expired" | "expiringSoon" | "rotationOverdue" | "rotationDue";
+  dueAt: number;
+  daysLeft: number;
 }
 
 export interface Setting {
@@ -52,6 +104,7 @@ export interface BatchApiKey {
   keyValue: string;
   platform?: string;
   description?: string;
+  tags?: string;
 }
 
 export interface BatchImportResult {
@@ -62,6 +115,199 @@ export interface BatchImportResult {
   errors: string[];
 }
 
+// 审计日志记录（外部程序对Key的访问）
+export interface AuditEntry {
+  id: string;
+  createdAt: number;
+  source: string;
+  action: string;
+  target?: string;
+  keyId?: string;
+  outcome: "allowed" | "denied" | "failed";
+  detail?: string;
+  // 哈希链中的序号与哈希
+  seq?: number;
+  prevHash?: string;
+  entryHash?: string;
+}
+
+// 主密码锁定策略：连续输错 threshold 次后锁定 lockoutMinutes 分钟
+export interface LockoutPolicy {
+  threshold: number;
+  lockoutMinutes: number;
+}
+
+// 主密码连续失败次数与需要等待的时间
+export interface UnlockAttemptStatus {
+  failedAttempts: number;
+  // 距离允许下一次尝试的秒数，0 表示可以立即尝试
+  retryAfter: number;
+  lockedOut: boolean;
+  policy: LockoutPolicy;
+}
+
+// 审计日志查询条件
+export interface AuditFilter {
+  source?: string;
+  action?: string;
+  keyId?: string;
+  outcome?: "allowed" | "denied" | "failed";
+  since?: number;
+  until?: number;
+  limit?: number;
+  offset?: number;
+}
+
+// 审计日志哈希链校验结果
+export interface AuditChainReport {
+  valid: boolean;
+  entriesChecked: number;
+  brokenSeq?: number;
+  brokenEntryId?: string;
+  reason?: string;
+}
+
+// 本地代理状态
+export interface AgentStatus {
+  running: boolean;
+  socketPath?: string;
+  confirmRequests: boolean;
+}
+
+// 本地 HTTP API 配置
+export interface HttpApiConfig {
+  enabled: boolean;
+  port: number;
+  rateLimitPerMinute: number;
+}
+
+export interface HttpApiStatus {
+  config: HttpApiConfig;
+  // 正在监听的端口，未运行时为空
+  port?: number;
+}
+
+// HTTP API 访问令牌；groupIds 与 tags 为逗号分隔，均为空时可访问全部Key
+export interface ApiToken {
+  id: string;
+  name: string;
+  groupIds?: string;
+  tags?: string;
+  allowSecrets: boolean;
+  createdAt: number;
+  lastUsedAt?: number;
+}
+
+// 新建令牌的结果，secret 只返回这一次
+export interface IssuedApiToken {
+  token: ApiToken;
+  secret: string;
+}
+
+// 模板填充结果，不包含Key的值
+export interface ResolvedPlaceholder {
+  line: number;
+  placeholder: string;
+  keyId: string;
+  keyName: string;
+}
+
+export interface UnresolvedPlaceholder {
+  line: number;
+  placeholder: string;
+  reason: string;
+}
+
+export interface RenderReport {
+  outputPath: string;
+  // 存在未解析的引用且未允许部分填充时不写入文件
+  written: boolean;
+  resolved: ResolvedPlaceholder[];
+  unresolved: UnresolvedPlaceholder[];
+}
+
+// 项目目录中变量与Key的绑定
+export interface ProjectBinding {
+  id: string;
+  projectPath: string;
+  envVar: string;
+  keyId: string;
+  createdAt: number;
+  syncedAt?: number;
+  keyName?: string;
+  // Key已删除或在回收站中
+  missing: boolean;
+  // 同步后Key的值被修改过，.env 需要刷新
+  stale: boolean;
+}
+
+export interface ProjectInfo {
+  path: string;
+  bindings: ProjectBinding[];
+  syncedAt?: number;
+  stale: boolean;
+}
+
+export interface ProjectSyncReport {
+  envFile: string;
+  written: string[];
+  missing: string[];
+}
+
+export interface ScanFinding {
+  // 相对于扫描根目录的路径
+  path: string;
+  line: number;
+  column: number;
+  provider?: string;
+  // 脱敏后的片段，不包含Key的值
+  redacted: string;
+  // 与保险库中已保存的Key相同
+  knownKey: boolean;
+  keyId?: string;
+  keyName?: string;
+}
+
+export interface ScanReport {
+  root: string;
+  filesScanned: number;
+  filesSkipped: number;
+  findings: ScanFinding[];
+  truncated: boolean;
+}
+
+export interface GitFinding {
+  // 引入该内容的提交
+  commit: string;
+  path: string;
+  line: number;
+  column: number;
+  author: string;
+  authorEmail: string;
+  committedAt: number;
+  provider?: string;
+  redacted: string;
+  knownKey: boolean;
+  keyId?: string;
+  keyName?: string;
+}
+
+// 曾被提交到仓库中的保险库Key，需要轮换
+export interface LeakedKey {
+  keyId: string;
+  keyName: string;
+  commits: string[];
+}
+
+export interface GitScanReport {
+  repository: string;
+  commitsScanned: number;
+  blobsScanned: number;
+  findings: GitFinding[];
+  leakedKeys: LeakedKey[];
+  truncated: boolean;
+}
+
 // Tauri 环境接口
 declare global {
   interface Window {
@@ -0,0 +1,8 @@
+// 保险库（独立的数据库文件与主密码）
+export interface VaultInfo {
+  id: string;
+  name: string;
+  createdAt: number;
+  // 是否为当前打开的保险库
+  active: boolean;
+}